@@ -1,8 +1,6 @@
 use std::any;
 use std::thread;
 
-use tls_api::runtime::AsyncReadExt;
-use tls_api::runtime::AsyncWriteExt;
 use tls_api::TlsAcceptor;
 use tls_api::TlsAcceptorBuilder;
 use tls_api::TlsConnector;
@@ -12,6 +10,8 @@ use tls_api::TlsStreamDyn;
 use crate::block_on;
 use crate::new_acceptor;
 use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
 use crate::TcpListener;
 use crate::TcpStream;
 use crate::BIND_HOST;
@@ -23,7 +23,7 @@ where
 {
     drop(env_logger::try_init());
 
-    if !C::IMPLEMENTED {
+    if !C::CAPABILITIES.implemented {
         eprintln!(
             "connector {} is not implemented; skipping",
             any::type_name::<C>()
@@ -31,7 +31,7 @@ where
         return;
     }
 
-    if !A::IMPLEMENTED {
+    if !A::CAPABILITIES.implemented {
         eprintln!(
             "acceptor {} is not implemented; skipping",
             any::type_name::<A>()
@@ -39,12 +39,12 @@ where
         return;
     }
 
-    if !C::SUPPORTS_ALPN {
+    if !C::CAPABILITIES.alpn {
         eprintln!("connector {} does not support ALPN", any::type_name::<C>());
         return;
     }
 
-    if !A::SUPPORTS_ALPN {
+    if !A::CAPABILITIES.alpn {
         eprintln!("acceptor {} does not support ALPN", any::type_name::<A>());
         return;
     }
@@ -73,6 +73,7 @@ where
             assert_eq!(&buf, b"hello");
 
             t!(socket.write_all(b"world").await);
+            t!(crate::shutdown(&mut socket).await);
         };
         block_on(f);
     });
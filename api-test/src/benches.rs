@@ -1,5 +1,8 @@
 #![cfg(all(rustc_nightly, feature = "runtime-tokio"))]
 
+use std::future;
+use std::future::Future;
+use std::task::Poll;
 use std::thread;
 
 use crate::new_acceptor_dyn_from_der_keys;
@@ -13,8 +16,11 @@ use test::Bencher;
 use tls_api::AsyncSocketBox;
 use tls_api::TlsAcceptorType;
 use tls_api::TlsConnectorType;
+use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
 use tokio::io::AsyncWriteExt;
+use tokio::runtime;
 use tokio::runtime::Runtime;
 
 pub fn bench_1<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(bencher: &mut Bencher) {
@@ -60,7 +66,200 @@ pub fn bench_1<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(bencher: &mut
         })
     });
 
-    drop(tls_stream);
+    // Server reports EOF without `close_notify` as an error
+    rt.block_on(tls_stream.shutdown()).unwrap();
+
+    t.join().unwrap();
+}
+
+/// Buffer size of in-memory pipe used in `duplex` benches.
+const DUPLEX_BUF_LEN: usize = 64 * 1024;
+
+/// Poll both futures on the current task until both complete.
+async fn join<FA: Future, FB: Future>(a: FA, b: FB) -> (FA::Output, FB::Output) {
+    let mut a = Box::pin(a);
+    let mut b = Box::pin(b);
+    let mut ra = None;
+    let mut rb = None;
+    future::poll_fn(|cx| {
+        if ra.is_none() {
+            if let Poll::Ready(r) = a.as_mut().poll(cx) {
+                ra = Some(r);
+            }
+        }
+        if rb.is_none() {
+            if let Poll::Ready(r) = b.as_mut().poll(cx) {
+                rb = Some(r);
+            }
+        }
+        if ra.is_some() && rb.is_some() {
+            Poll::Ready((ra.take().unwrap(), rb.take().unwrap()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+fn current_thread_runtime() -> Runtime {
+    runtime::Builder::new_current_thread().build().unwrap()
+}
+
+/// Write a byte to one stream, echo it back from the other one.
+async fn round_trip<C, A>(client: &mut C, server: &mut A)
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+    A: AsyncRead + AsyncWrite + Unpin,
+{
+    let client = async {
+        assert_eq!(1, client.write(&[10]).await.unwrap());
+        let mut buf = [0];
+        assert_eq!(1, client.read(&mut buf).await.unwrap());
+        assert_eq!(10, buf[0]);
+    };
+    let server = async {
+        let mut buf = [0];
+        assert_eq!(1, server.read(&mut buf).await.unwrap());
+        assert_eq!(1, server.write(&buf).await.unwrap());
+    };
+    join(client, server).await;
+}
+
+/// Like [`bench_1`], but over in-memory pipe, on single thread
+/// (so the API overhead is not hidden by the network latency).
+pub fn bench_1_duplex<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(bencher: &mut Bencher) {
+    let acceptor = new_acceptor_from_der_keys::<A>();
+    let connector: C = new_connector_with_root_ca();
+
+    let rt = current_thread_runtime();
+
+    let (client, server) = tokio::io::duplex(DUPLEX_BUF_LEN);
+    let (client, server) = rt.block_on(join(
+        connector.connect("localhost", client),
+        acceptor.accept(server),
+    ));
+    let (mut client, mut server) = (client.unwrap(), server.unwrap());
+
+    bencher.iter(|| rt.block_on(round_trip(&mut client, &mut server)));
+}
+
+/// Like [`bench_1_duplex`], but with unboxed streams
+/// returned by `connect_static` and `accept_static`.
+pub fn bench_1_duplex_static<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(
+    bencher: &mut Bencher,
+) {
+    let acceptor = new_acceptor_from_der_keys::<A>();
+    let connector: C = new_connector_with_root_ca();
+
+    let rt = current_thread_runtime();
+
+    let (client, server) = tokio::io::duplex(DUPLEX_BUF_LEN);
+    let (client, server) = rt.block_on(join(
+        connector.connect_static("localhost", client),
+        acceptor.accept_static(server),
+    ));
+    let (mut client, mut server) = (client.unwrap(), server.unwrap());
+
+    bencher.iter(|| rt.block_on(round_trip(&mut client, &mut server)));
+}
+
+/// Full handshake over in-memory pipe with `connect` and `accept`.
+pub fn bench_handshake<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(bencher: &mut Bencher) {
+    let acceptor = new_acceptor_from_der_keys::<A>();
+    let connector: C = new_connector_with_root_ca();
+
+    let rt = current_thread_runtime();
+
+    bencher.iter(|| {
+        let (client, server) = tokio::io::duplex(DUPLEX_BUF_LEN);
+        let (client, server) = rt.block_on(join(
+            connector.connect("localhost", client),
+            acceptor.accept(server),
+        ));
+        (client.unwrap(), server.unwrap())
+    });
+}
+
+/// Full handshake over in-memory pipe with `connect_static` and `accept_static`.
+pub fn bench_handshake_static<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(
+    bencher: &mut Bencher,
+) {
+    let acceptor = new_acceptor_from_der_keys::<A>();
+    let connector: C = new_connector_with_root_ca();
+
+    let rt = current_thread_runtime();
+
+    bencher.iter(|| {
+        let (client, server) = tokio::io::duplex(DUPLEX_BUF_LEN);
+        let (client, server) = rt.block_on(join(
+            connector.connect_static("localhost", client),
+            acceptor.accept_static(server),
+        ));
+        (client.unwrap(), server.unwrap())
+    });
+}
+
+/// Size of the message sent in single iteration of [`bench_bulk`].
+const BULK_MESSAGE_LEN: usize = 256 * 1024;
+
+/// Send large message in chunks, wait for 1-byte acknowledgement.
+pub fn bench_bulk<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>(bencher: &mut Bencher) {
+    let acceptor = new_acceptor_from_der_keys::<A>();
+
+    let server_rt = Runtime::new().unwrap();
+
+    #[allow(unused_mut)]
+    let mut listener = server_rt
+        .block_on(TcpListener::bind((crate::BIND_HOST, 0)))
+        .unwrap();
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let t = thread::spawn(move || {
+        Runtime::new().unwrap().block_on(async {
+            let socket = listener.accept().await.unwrap().0;
+            // Otherwise the bench measures delayed ACK of the acknowledgement
+            socket.set_nodelay(true).unwrap();
+            let mut stream = acceptor.accept(socket).await.unwrap();
+            let mut buf = vec![0; 16 * 1024];
+            let mut received = 0;
+            loop {
+                let read = stream.read(&mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                received += read;
+                if received == BULK_MESSAGE_LEN {
+                    received = 0;
+                    stream.write_all(&[1]).await.unwrap();
+                }
+            }
+        });
+    });
+
+    let rt = Runtime::new().unwrap();
+
+    let socket = rt.block_on(TcpStream::connect((BIND_HOST, port))).unwrap();
+    socket.set_nodelay(true).unwrap();
+
+    let connector: C = new_connector_with_root_ca();
+    let mut tls_stream = rt.block_on(connector.connect("localhost", socket)).unwrap();
+
+    let message = vec![17; BULK_MESSAGE_LEN];
+    bencher.bytes = BULK_MESSAGE_LEN as u64;
+    bencher.iter(|| {
+        rt.block_on(async {
+            for chunk in message.chunks(16 * 1024) {
+                tls_stream.write_all(chunk).await.unwrap();
+            }
+            tls_stream.flush().await.unwrap();
+            let mut buf = [0];
+            tls_stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(1, buf[0]);
+        })
+    });
+
+    // Server reports EOF without `close_notify` as an error
+    rt.block_on(tls_stream.shutdown()).unwrap();
 
     t.join().unwrap();
 }
@@ -112,7 +311,8 @@ pub fn bench_1_dyn(
         })
     });
 
-    drop(tls_stream);
+    // Server reports EOF without `close_notify` as an error
+    rt.block_on(tls_stream.shutdown()).unwrap();
 
     t.join().unwrap();
 }
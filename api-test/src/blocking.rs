@@ -0,0 +1,76 @@
+use std::any;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::thread;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsStreamBlockingDyn;
+
+use crate::new_acceptor;
+use crate::new_connector_with_root_ca;
+use crate::BIND_HOST;
+
+pub fn test_blocking<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    if !C::CAPABILITIES.blocking || !A::CAPABILITIES.blocking {
+        eprintln!(
+            "connector {} or acceptor {} does not support blocking API; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    let acceptor: A = new_acceptor::<A>(None).build().expect("acceptor build");
+    let listener = t!(TcpListener::bind((BIND_HOST, 0)));
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let server_thread_name = format!("{}-server", thread::current().name().unwrap_or("test"));
+    let j = thread::Builder::new()
+        .name(server_thread_name)
+        .spawn(move || {
+            let socket = t!(listener.accept()).0;
+            let mut socket = t!(acceptor.accept_blocking(socket));
+            assert_eq!(A::info().name, socket.impl_info().name);
+
+            let mut buf = [0; 5];
+            t!(socket.read_exact(&mut buf));
+            assert_eq!(&buf, b"hello");
+
+            t!(socket.write_all(b"world"));
+            t!(socket.shutdown());
+        })
+        .unwrap();
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)));
+
+    let connector = new_connector_with_root_ca::<C>().into_dyn();
+    let mut socket = t!(connector.connect_blocking("localhost", socket));
+    assert_eq!(C::info().name, socket.impl_info().name);
+    assert_eq!(port, t!(socket.get_socket_ref().peer_addr()).port());
+
+    t!(socket.write_all(b"hello"));
+    let mut buf = vec![];
+    t!(socket.read_to_end(&mut buf));
+    assert_eq!(buf, b"world");
+
+    j.join().expect("thread join");
+}
@@ -0,0 +1,139 @@
+//! Keying material exporter and channel binding tests.
+
+use std::any;
+use std::thread;
+
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api::TlsStreamDyn;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Result of the operation on the stream, `None` if the implementation does not support it.
+type Outcome = Option<Vec<u8>>;
+
+fn unsupported_is_none(r: anyhow::Result<Vec<u8>>) -> Outcome {
+    match r {
+        Ok(r) => Some(r),
+        Err(e) => match e.downcast_ref::<ChannelBindingError>() {
+            Some(ChannelBindingError::ExportNotSupported(..))
+            | Some(ChannelBindingError::NotSupported(..)) => None,
+            _ => panic!("{}", e),
+        },
+    }
+}
+
+/// Values computed on one side of the connection.
+fn collect(stream: &dyn TlsStreamDyn) -> [Outcome; 3] {
+    [
+        unsupported_is_none(stream.export_keying_material(
+            "EXPERIMENTAL-tls-api",
+            Some(b"context"),
+            42,
+        )),
+        unsupported_is_none(stream.channel_binding(ChannelBindingType::TlsExporter)),
+        unsupported_is_none(stream.channel_binding(ChannelBindingType::TlsServerEndPoint)),
+    ]
+}
+
+/// Connect, and return values computed on the client and the server.
+async fn connect_collect<C, A>() -> ([Outcome; 3], [Outcome; 3])
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    let acceptor: A = t!(new_acceptor::<A>(None).build());
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            let mut socket = t!(acceptor.accept(socket).await);
+
+            let mut buf = [0; 5];
+            t!(socket.read_exact(&mut buf).await);
+            assert_eq!(b"hello", &buf);
+
+            let server = collect(&socket);
+            t!(socket.write_all(b"world").await);
+            t!(crate::shutdown(&mut socket).await);
+            server
+        };
+        block_on(future)
+    });
+
+    let connector: C = t!(new_connector_builder_with_root_ca::<C>().build());
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+    let mut socket = t!(connector.connect("localhost", socket).await);
+    t!(socket.write_all(b"hello").await);
+    let mut buf = vec![];
+    t!(socket.read_to_end(&mut buf).await);
+    assert_eq!(b"world", &buf[..]);
+
+    let client = collect(&socket);
+
+    let server = j.join().expect("thread join");
+
+    (client, server)
+}
+
+async fn test_channel_binding_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let (client, server) = connect_collect::<C, A>().await;
+
+    let names = ["exporter", "tls-exporter", "tls-server-end-point"];
+    let lens = [42, 32, 32];
+    for ((name, len), (client, server)) in names.iter().zip(lens).zip(client.iter().zip(&server)) {
+        match (client, server) {
+            (Some(client), Some(server)) => {
+                assert_eq!(len, client.len(), "{}", name);
+                assert_eq!(client, server, "{}", name);
+            }
+            _ => eprintln!(
+                "{} is not supported by connector {} or acceptor {}",
+                name,
+                any::type_name::<C>(),
+                any::type_name::<A>(),
+            ),
+        }
+    }
+}
+
+/// Exported keying material and channel bindings are equal on both sides.
+///
+/// Test certificate is signed with SHA-256, so `tls-server-end-point` is 32 bytes.
+pub fn test_channel_binding<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_channel_binding_impl::<C, A>())
+}
@@ -1,8 +1,6 @@
 use std::any;
 use std::thread;
 
-use tls_api::runtime::AsyncReadExt;
-use tls_api::runtime::AsyncWriteExt;
 use tls_api::TlsAcceptor;
 use tls_api::TlsAcceptorBuilder;
 use tls_api::TlsConnector;
@@ -10,6 +8,8 @@ use tls_api::TlsConnector;
 use crate::block_on;
 use crate::new_acceptor;
 use crate::new_connector_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
 use crate::AcceptorKeyKind;
 use crate::TcpListener;
 use crate::TcpStream;
@@ -22,7 +22,7 @@ where
 {
     drop(env_logger::try_init());
 
-    if !C::IMPLEMENTED {
+    if !C::CAPABILITIES.implemented {
         eprintln!(
             "connector {} is not implemented; skipping",
             any::type_name::<C>()
@@ -30,7 +30,7 @@ where
         return;
     }
 
-    if !A::IMPLEMENTED {
+    if !A::CAPABILITIES.implemented {
         eprintln!(
             "acceptor {} is not implemented; skipping",
             any::type_name::<A>()
@@ -38,6 +38,19 @@ where
         return;
     }
 
+    let (supported, key_name) = match key {
+        AcceptorKeyKind::Der => (A::CAPABILITIES.der_keys, "DER"),
+        AcceptorKeyKind::Pkcs12 => (A::CAPABILITIES.pkcs12_keys, "PKCS #12"),
+    };
+    if !supported {
+        eprintln!(
+            "acceptor {} does not support {} keys; skipping",
+            any::type_name::<A>(),
+            key_name
+        );
+        return;
+    }
+
     let acceptor = new_acceptor::<A>(Some(key));
 
     let acceptor: A = acceptor.build().expect("acceptor build");
@@ -58,6 +71,7 @@ where
                 assert_eq!(&buf, b"hello");
 
                 t!(socket.write_all(b"world").await);
+                t!(crate::shutdown(&mut socket).await);
             };
             block_on(future);
         })
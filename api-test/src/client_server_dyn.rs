@@ -1,14 +1,14 @@
 use std::io;
 use std::thread;
 
-use tls_api::runtime::AsyncReadExt;
-use tls_api::runtime::AsyncWriteExt;
 use tls_api::TlsAcceptorType;
 use tls_api::TlsConnectorType;
 
 use crate::block_on;
 use crate::new_acceptor_dyn;
 use crate::new_connector_builder_dyn_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
 use crate::AcceptorKeyKind;
 use crate::TcpListener;
 use crate::TcpStream;
@@ -21,12 +21,12 @@ async fn test_client_server_dyn_impl(
 ) {
     drop(env_logger::try_init());
 
-    if !connector.implemented() {
+    if !connector.capabilities().implemented {
         eprintln!("connector {} is not implemented; skipping", connector);
         return;
     }
 
-    if !acceptor.implemented() {
+    if !acceptor.capabilities().implemented {
         eprintln!("acceptor {} is not implemented; skipping", acceptor);
         return;
     }
@@ -51,6 +51,7 @@ async fn test_client_server_dyn_impl(
                 assert_eq!(&buf, b"hello");
 
                 t!(socket.write_all(b"world").await);
+                t!(crate::shutdown(&mut socket).await);
             };
             block_on(future);
         })
@@ -0,0 +1,79 @@
+//! Client-server test with statically dispatched streams.
+
+use std::any;
+use std::thread;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api::TlsStreamDyn;
+use tls_api::TlsStreamWithSocketDyn;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+async fn test_client_server_static_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let acceptor: A = t!(new_acceptor::<A>(None).build());
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            let mut socket: A::Stream<TcpStream> = t!(acceptor.accept_static(socket).await);
+            assert_eq!(A::info().name, socket.impl_info().name);
+            let mut buf = [0; 5];
+            t!(socket.read_exact(&mut buf).await);
+            assert_eq!(b"hello", &buf);
+            t!(socket.write_all(b"world").await);
+            t!(crate::shutdown(&mut socket).await);
+        };
+        block_on(future)
+    });
+
+    let connector: C = t!(new_connector_builder_with_root_ca::<C>().build());
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+    let local_addr = t!(socket.local_addr());
+    let mut socket: C::Stream<TcpStream> = t!(connector.connect_static("localhost", socket).await);
+    // Typed access to the socket, no downcast needed
+    assert_eq!(local_addr, t!(socket.get_socket_ref().local_addr()));
+    t!(socket.write_all(b"hello").await);
+    let mut buf = vec![];
+    t!(socket.read_to_end(&mut buf).await);
+    assert_eq!(b"world", &buf[..]);
+
+    j.join().expect("thread join");
+}
+
+/// `connect_static` and `accept_static`.
+pub fn test_client_server_static<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_client_server_static_impl::<C, A>())
+}
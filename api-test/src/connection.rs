@@ -0,0 +1,113 @@
+use std::any;
+use std::io;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnection;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+
+/// Move all pending ciphertext from one connection to another.
+fn transfer(from: &mut TlsConnection, to: &mut TlsConnection) -> usize {
+    let mut transferred = 0;
+    let mut buf = [0; 4096];
+    while from.wants_write() {
+        let len = t!(from.take_ciphertext(&mut buf));
+        let mut fed = 0;
+        while fed != len {
+            fed += t!(to.feed_ciphertext(&buf[fed..len]));
+        }
+        transferred += len;
+    }
+    transferred
+}
+
+fn read_plaintext_to_end(conn: &mut TlsConnection) -> Vec<u8> {
+    let mut r = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        match conn.read_plaintext(&mut buf) {
+            Ok(0) => return r,
+            Ok(len) => r.extend_from_slice(&buf[..len]),
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => return r,
+            Err(e) => panic!("read_plaintext: {}", e),
+        }
+    }
+}
+
+fn write_plaintext_all(conn: &mut TlsConnection, data: &[u8]) {
+    let written = t!(conn.write_plaintext(data));
+    assert_eq!(data.len(), written);
+}
+
+pub fn test_connection<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    if !C::CAPABILITIES.connection || !A::CAPABILITIES.connection {
+        eprintln!(
+            "connector {} or acceptor {} does not support sans-IO connection; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    let alpn = C::CAPABILITIES.alpn && A::CAPABILITIES.alpn;
+
+    let mut acceptor = new_acceptor::<A>(None);
+    let mut connector = new_connector_builder_with_root_ca::<C>();
+    if alpn {
+        t!(acceptor.set_alpn_protocols(&[b"abc", b"de"]));
+        t!(connector.set_alpn_protocols(&[b"de", b"f"]));
+    }
+    let acceptor: A = t!(acceptor.build());
+    let connector = t!(connector.build()).into_dyn();
+
+    let mut client = t!(connector.new_connection("localhost"));
+    let mut server = t!(acceptor.new_connection());
+    assert_eq!(C::info().name, client.impl_info().name);
+    assert_eq!(A::info().name, server.impl_info().name);
+
+    // Handshake, ciphertext is moved between connections until both are done
+    let mut round_trips = 0;
+    while client.is_handshaking() || server.is_handshaking() {
+        let transferred = transfer(&mut client, &mut server) + transfer(&mut server, &mut client);
+        assert!(transferred != 0, "handshake is stuck");
+        round_trips += 1;
+        assert!(round_trips < 10, "too many round trips");
+    }
+    // Session tickets and other post-handshake messages
+    transfer(&mut server, &mut client);
+
+    if alpn {
+        assert_eq!(Some(&b"de"[..]), t!(client.get_alpn_protocol()).as_deref());
+        assert_eq!(Some(&b"de"[..]), t!(server.get_alpn_protocol()).as_deref());
+    }
+
+    write_plaintext_all(&mut client, b"hello");
+    transfer(&mut client, &mut server);
+    assert_eq!(b"hello", &read_plaintext_to_end(&mut server)[..]);
+
+    write_plaintext_all(&mut server, b"world");
+    t!(server.send_close_notify());
+    transfer(&mut server, &mut client);
+    assert_eq!(b"world", &read_plaintext_to_end(&mut client)[..]);
+    assert_eq!(0, t!(client.read_plaintext(&mut [0; 10])));
+}
@@ -0,0 +1,125 @@
+/// Version requirement of dependency `dep` in `Cargo.toml` contents.
+///
+/// Only `dep = "version"` and `dep = { version = "version", ... }`
+/// forms written on a single line are recognized,
+/// anything else is reported as an error rather than skipped.
+fn dep_version_in_manifest<'a>(cargo_toml: &'a str, dep: &str) -> Result<&'a str, String> {
+    let mut found = None;
+    for line in cargo_toml.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(&format!("dependencies.{}]", dep)) {
+            return Err(format!("`{}` is declared as a table: {}", dep, line));
+        }
+        let (name, value) = match line.split_once('=') {
+            Some((name, value)) if name.trim() == dep => (name, value),
+            _ => continue,
+        };
+        let value = value.trim();
+        let version = match value.strip_prefix('{') {
+            Some(table) => table.split(',').find_map(|kv| match kv.split_once('=') {
+                Some((k, v)) if k.trim() == "version" => Some(v.trim()),
+                _ => None,
+            }),
+            None => Some(value),
+        };
+        let version = version
+            .and_then(|v| v.strip_prefix('"')?.split_once('"'))
+            .map(|(v, _)| v)
+            .ok_or_else(|| format!("cannot parse version of `{}`: {}", name.trim(), line))?;
+        match found {
+            Some(prev) if prev != version => {
+                return Err(format!(
+                    "`{}` has different versions: {} and {}",
+                    dep, prev, version
+                ));
+            }
+            _ => found = Some(version),
+        }
+    }
+    found.ok_or_else(|| format!("`{}` not found", dep))
+}
+
+/// Requirement as reported in [`ImplInfo`](tls_api::ImplInfo): with explicit operator,
+/// `Cargo.toml` requirement without operator means `^`.
+fn requirement_with_operator(requirement: &str) -> String {
+    if requirement.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("^{}", requirement)
+    } else {
+        requirement.to_owned()
+    }
+}
+
+/// Check that a version requirement of the underlying library reported by an implementation
+/// in [`ImplInfo`](tls_api::ImplInfo) is the requirement of `dep` in `Cargo.toml`.
+///
+/// Implementations keep the requirement in a constant when the library version
+/// is not available at runtime, because the resolved version
+/// is not known to the build of the crate.
+///
+/// # Panics
+///
+/// If versions differ, or the requirement cannot be found in `cargo_toml`.
+pub fn test_dep_version(cargo_toml: &str, dep: &str, version: &str) {
+    let requirement = dep_version_in_manifest(cargo_toml, dep)
+        .unwrap_or_else(|e| panic!("failed to parse `Cargo.toml`: {}", e));
+    assert_eq!(
+        requirement_with_operator(requirement),
+        version,
+        "version of `{}` does not match `Cargo.toml`",
+        dep
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CARGO_TOML: &str = r#"
+[dependencies]
+anyhow       = "1.0.44"
+native-tls = { version ="0.2", features = ["alpn"] }
+rustls       = { version = "0.23.29", default-features = false, features = ["ring"] }
+tls-api = { path = "../api", version = "=0.10.0-pre", default-features = false }
+test-cert-gen = { path = "../test-cert-gen" }
+
+[target.'cfg(unix)'.dependencies]
+anyhow = "1.0.44"
+openssl = "0.10"
+
+[target.'cfg(windows)'.dependencies]
+openssl = "0.9"
+
+[dependencies.webpki-roots]
+version = "0.26"
+"#;
+
+    #[test]
+    fn dep_version() {
+        let version = |dep| dep_version_in_manifest(CARGO_TOML, dep);
+        assert_eq!(Ok("1.0.44"), version("anyhow"));
+        assert_eq!(Ok("0.2"), version("native-tls"));
+        assert_eq!(Ok("0.23.29"), version("rustls"));
+        assert_eq!(Ok("=0.10.0-pre"), version("tls-api"));
+    }
+
+    #[test]
+    fn dep_version_error() {
+        let error = |dep| dep_version_in_manifest(CARGO_TOML, dep).unwrap_err();
+        assert!(error("test-cert-gen").contains("cannot parse"));
+        assert!(error("openssl").contains("different versions"));
+        assert!(error("webpki-roots").contains("table"));
+        assert!(error("s2n-tls").contains("not found"));
+    }
+
+    #[test]
+    fn operator() {
+        assert_eq!("^0.2", requirement_with_operator("0.2"));
+        assert_eq!("=0.10.0-pre", requirement_with_operator("=0.10.0-pre"));
+    }
+
+    #[test]
+    #[should_panic(expected = "not found")]
+    fn test_dep_version_not_found() {
+        test_dep_version(CARGO_TOML, "s2n-tls", "^0.3.0");
+    }
+}
@@ -0,0 +1,217 @@
+use std::any;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::thread;
+
+use tls_api::AsyncDatagramSocket;
+use tls_api::DtlsAcceptor;
+use tls_api::DtlsAcceptorBuilder;
+use tls_api::DtlsConnector;
+use tls_api::DtlsConnectorBuilder;
+use tls_api::DtlsStreamDyn;
+use tokio::net::UdpSocket;
+
+use crate::block_on;
+use crate::BIND_HOST;
+
+/// Socket which loses datagrams with given indices.
+#[derive(Debug)]
+struct LossySocket {
+    socket: UdpSocket,
+    sent: usize,
+    lose: &'static [usize],
+}
+
+impl AsyncDatagramSocket for LossySocket {
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().socket).poll_recv(cx, buf)
+    }
+
+    fn poll_send(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.lose.contains(&this.sent) {
+            this.sent += 1;
+            return Poll::Ready(Ok(buf.len()));
+        }
+        match Pin::new(&mut this.socket).poll_send(cx, buf) {
+            Poll::Ready(Ok(len)) => {
+                this.sent += 1;
+                Poll::Ready(Ok(len))
+            }
+            r => r,
+        }
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.peer_addr()
+    }
+}
+
+/// Pair of UDP sockets connected to each other.
+fn socket_pair() -> (std::net::UdpSocket, std::net::UdpSocket) {
+    let a = t!(std::net::UdpSocket::bind((BIND_HOST, 0)));
+    let b = t!(std::net::UdpSocket::bind((BIND_HOST, 0)));
+    t!(a.connect(t!(b.local_addr())));
+    t!(b.connect(t!(a.local_addr())));
+    t!(a.set_nonblocking(true));
+    t!(b.set_nonblocking(true));
+    (a, b)
+}
+
+fn new_dtls_acceptor_builder<A: DtlsAcceptor>() -> A::Builder {
+    let keys = &test_cert_gen::keys().server.cert_and_key;
+    t!(A::builder_from_der_key(
+        keys.cert.get_der(),
+        keys.key.get_der()
+    ))
+}
+
+fn new_dtls_connector_builder<C: DtlsConnector>() -> C::Builder {
+    let mut connector = C::builder().expect("connector builder");
+    t!(connector.add_root_certificate(test_cert_gen::keys().client.ca.get_der()));
+    connector
+}
+
+fn implemented<C: DtlsConnector, A: DtlsAcceptor>() -> bool {
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return false;
+    }
+    true
+}
+
+/// Handshake over a pair of sockets losing given datagrams, exchange messages.
+fn client_server<C, A>(
+    connector: C,
+    acceptor: A,
+    client_lose: &'static [usize],
+    server_lose: &'static [usize],
+    expected_alpn: Option<&'static [u8]>,
+) where
+    C: DtlsConnector,
+    A: DtlsAcceptor,
+{
+    let (client_socket, server_socket) = socket_pair();
+
+    let j = thread::spawn(move || {
+        block_on(async {
+            let socket = LossySocket {
+                socket: t!(UdpSocket::from_std(server_socket)),
+                sent: 0,
+                lose: server_lose,
+            };
+            let mut stream = t!(acceptor.accept(socket).await);
+            assert_eq!(
+                expected_alpn,
+                stream.get_alpn_protocol().unwrap().as_deref()
+            );
+
+            let mut buf = [0; 100];
+            let len = t!(stream.recv(&mut buf).await);
+            assert_eq!(b"hello", &buf[..len]);
+
+            t!(stream.send(b"world").await);
+        })
+    });
+
+    block_on(async {
+        let socket = LossySocket {
+            socket: t!(UdpSocket::from_std(client_socket)),
+            sent: 0,
+            lose: client_lose,
+        };
+        let mut stream = t!(connector.connect("localhost", socket).await);
+        assert_eq!(
+            expected_alpn,
+            stream.get_alpn_protocol().unwrap().as_deref()
+        );
+
+        t!(stream.send(b"hello").await);
+
+        let mut buf = [0; 100];
+        let len = t!(stream.recv(&mut buf).await);
+        assert_eq!(b"world", &buf[..len]);
+    });
+
+    j.join().expect("thread join");
+}
+
+pub fn test_dtls_client_server<C, A>()
+where
+    C: DtlsConnector,
+    A: DtlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !implemented::<C, A>() {
+        return;
+    }
+
+    let connector: C = t!(new_dtls_connector_builder::<C>().build());
+    let acceptor: A = t!(new_dtls_acceptor_builder::<A>().build());
+    client_server(connector, acceptor, &[], &[], None);
+}
+
+/// Handshake completes when handshake messages are lost and retransmitted.
+pub fn test_dtls_packet_loss<C, A>()
+where
+    C: DtlsConnector,
+    A: DtlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !implemented::<C, A>() {
+        return;
+    }
+
+    let connector: C = t!(new_dtls_connector_builder::<C>().build());
+    let acceptor: A = t!(new_dtls_acceptor_builder::<A>().build());
+    // First `ClientHello` and the first datagram of the server flight after the cookie exchange
+    client_server(connector, acceptor, &[0], &[1], None);
+}
+
+pub fn test_dtls_alpn<C, A>()
+where
+    C: DtlsConnector,
+    A: DtlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !implemented::<C, A>() {
+        return;
+    }
+
+    if !C::CAPABILITIES.alpn || !A::CAPABILITIES.alpn {
+        eprintln!(
+            "connector {} or acceptor {} does not support ALPN",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    let mut connector = new_dtls_connector_builder::<C>();
+    t!(connector.set_alpn_protocols(&[b"xyz", b"de", b"u"]));
+    let connector: C = t!(connector.build());
+
+    let mut acceptor = new_dtls_acceptor_builder::<A>();
+    t!(acceptor.set_alpn_protocols(&[b"abc", b"de", b"f"]));
+    let acceptor: A = t!(acceptor.build());
+
+    client_server(connector, acceptor, &[], &[], Some(b"de"));
+}
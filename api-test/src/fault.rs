@@ -0,0 +1,133 @@
+//! Fault injection with real implementations.
+
+use std::any;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use tls_api::FaultConfig;
+use tls_api::TlsAcceptor;
+use tls_api::TlsConnector;
+
+use crate::block_on;
+use crate::new_acceptor_dyn;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Connect once with `faults` injected into the connector, and exchange a message.
+async fn exchange<C, A>(faults: FaultConfig) -> anyhow::Result<()>
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    let acceptor = t!(new_acceptor_dyn(A::TYPE_DYN, None).build());
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            // Errors are expected when the client is damaged
+            if let Ok(mut socket) = acceptor.accept(socket).await {
+                let mut buf = [0; 5];
+                if socket.read_exact(&mut buf).await.is_ok() {
+                    drop(socket.write_all(b"world").await);
+                    drop(socket.flush().await);
+                }
+            }
+        };
+        block_on(future)
+    });
+
+    let keys = test_cert_gen::keys();
+    let mut connector = t!(faults.connector_builder(C::TYPE_DYN));
+    t!(connector.add_root_certificate(keys.client.ca.get_der()));
+    let connector = t!(connector.build());
+
+    let result = async {
+        let socket = TcpStream::connect((BIND_HOST, port)).await?;
+        let mut socket = connector.connect("localhost", socket).await?;
+        socket.write_all(b"hello").await?;
+        let mut buf = [0; 5];
+        socket.read_exact(&mut buf).await?;
+        anyhow::ensure!(&buf == b"world", "unexpected reply: {:?}", buf);
+        Ok(())
+    }
+    .await;
+
+    j.join().expect("thread join");
+    result
+}
+
+async fn test_fault_injection_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let delay = Duration::from_millis(100);
+
+    let start = Instant::now();
+    t!(exchange::<C, A>(FaultConfig {
+        handshake_latency: delay,
+        ..FaultConfig::default()
+    })
+    .await);
+    assert!(start.elapsed() >= delay);
+
+    let start = Instant::now();
+    t!(exchange::<C, A>(FaultConfig {
+        stall_reads_after: Some(0),
+        stall_duration: Some(delay),
+        ..FaultConfig::default()
+    })
+    .await);
+    assert!(start.elapsed() >= delay);
+
+    let err = exchange::<C, A>(FaultConfig {
+        handshake_failure_probability: 1.0,
+        ..FaultConfig::default()
+    })
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("fault injection"), "{}", err);
+
+    // Damaged server handshake
+    exchange::<C, A>(FaultConfig {
+        corrupt_after: Some(0),
+        ..FaultConfig::default()
+    })
+    .await
+    .unwrap_err();
+
+    exchange::<C, A>(FaultConfig {
+        truncate_after: Some(10),
+        ..FaultConfig::default()
+    })
+    .await
+    .unwrap_err();
+}
+
+/// Handshake and record faults injected into the connector.
+pub fn test_fault_injection<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_fault_injection_impl::<C, A>())
+}
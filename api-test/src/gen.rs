@@ -53,6 +53,76 @@ fn client_server_pkcs12() {
 fn alpn() {
     tls_api_test::test_alpn::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
 }
+
+#[test]
+fn crl_not_revoked() {
+    tls_api_test::test_crl_not_revoked::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn crl_revoked() {
+    tls_api_test::test_crl_revoked::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn crl_unknown_status() {
+    tls_api_test::test_crl_unknown_status::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn client_auth_crl() {
+    tls_api_test::test_client_auth_crl::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn ocsp_stapling() {
+    tls_api_test::test_ocsp_stapling::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn ocsp_must_staple() {
+    tls_api_test::test_ocsp_must_staple::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn channel_binding() {
+    tls_api_test::test_channel_binding::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn key_log() {
+    tls_api_test::test_key_log::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn client_server_static() {
+    tls_api_test::test_client_server_static::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn server_name() {
+    tls_api_test::test_server_name::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn blocking() {
+    tls_api_test::test_blocking::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn connection() {
+    tls_api_test::test_connection::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn mixed_runtimes() {
+    tls_api_test::test_mixed_runtimes::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
+
+#[test]
+fn fault_injection() {
+    tls_api_test::test_fault_injection::<CRATE::TlsConnector, CRATE::TlsAcceptor>()
+}
 ";
 
 const BENCHES_TEMPLATE: &str = "\
@@ -66,6 +136,31 @@ fn bench_1(b: &mut test::Bencher) {
     tls_api_test::benches::bench_1::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
 }
 
+#[bench]
+fn bench_1_duplex(b: &mut test::Bencher) {
+    tls_api_test::benches::bench_1_duplex::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
+}
+
+#[bench]
+fn bench_1_duplex_static(b: &mut test::Bencher) {
+    tls_api_test::benches::bench_1_duplex_static::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
+}
+
+#[bench]
+fn bench_handshake(b: &mut test::Bencher) {
+    tls_api_test::benches::bench_handshake::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
+}
+
+#[bench]
+fn bench_handshake_static(b: &mut test::Bencher) {
+    tls_api_test::benches::bench_handshake_static::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
+}
+
+#[bench]
+fn bench_bulk(b: &mut test::Bencher) {
+    tls_api_test::benches::bench_bulk::<CRATE::TlsConnector, CRATE::TlsAcceptor>(b)
+}
+
 #[bench]
 fn bench_1_dyn(b: &mut test::Bencher) {
     tls_api_test::benches::bench_1_dyn(
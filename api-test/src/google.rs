@@ -1,18 +1,18 @@
 use std::any;
 use std::net::ToSocketAddrs;
 
-use tls_api::runtime::AsyncReadExt;
-use tls_api::runtime::AsyncWriteExt;
 use tls_api::TlsConnector;
 use tls_api::TlsConnectorBuilder;
 
 use crate::block_on;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
 use crate::TcpStream;
 
 async fn test_google_impl<C: TlsConnector>() {
     drop(env_logger::try_init());
 
-    if !C::IMPLEMENTED {
+    if !C::CAPABILITIES.implemented {
         eprintln!(
             "connector {} is not implemented; skipping",
             any::type_name::<C>()
@@ -0,0 +1,127 @@
+//! Key log tests.
+
+use std::any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread;
+
+use tls_api::KeyLog;
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Secrets by label and client random.
+type Secrets = HashMap<(String, Vec<u8>), Vec<u8>>;
+
+#[derive(Debug, Default)]
+struct RecordingKeyLog(Mutex<Secrets>);
+
+impl KeyLog for RecordingKeyLog {
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert((label.to_owned(), client_random.to_vec()), secret.to_vec());
+    }
+}
+
+impl RecordingKeyLog {
+    fn secrets(&self) -> Secrets {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+async fn test_key_log_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    if !C::CAPABILITIES.key_log || !A::CAPABILITIES.key_log {
+        eprintln!(
+            "connector {} or acceptor {} does not support key log; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let server_key_log = Arc::new(RecordingKeyLog::default());
+    let client_key_log = Arc::new(RecordingKeyLog::default());
+
+    let mut acceptor = new_acceptor::<A>(None);
+    t!(acceptor.set_key_log(server_key_log.clone()));
+    let acceptor: A = t!(acceptor.build());
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            let mut socket = t!(acceptor.accept(socket).await);
+            t!(socket.write_all(b"hello").await);
+            t!(crate::shutdown(&mut socket).await);
+        };
+        block_on(future)
+    });
+
+    let mut connector = new_connector_builder_with_root_ca::<C>();
+    t!(connector.set_key_log(client_key_log.clone()));
+    let connector: C = t!(connector.build());
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+    let mut socket = t!(connector.connect("localhost", socket).await);
+    let mut buf = vec![];
+    t!(socket.read_to_end(&mut buf).await);
+    assert_eq!(b"hello", &buf[..]);
+
+    j.join().expect("thread join");
+
+    let client_secrets = client_key_log.secrets();
+    let server_secrets = server_key_log.secrets();
+    assert!(!client_secrets.is_empty());
+    assert!(!server_secrets.is_empty());
+
+    // Implementations may log different sets of secrets,
+    // but secrets logged by both must be equal.
+    let mut common = 0;
+    for (key, client_secret) in &client_secrets {
+        if let Some(server_secret) = server_secrets.get(key) {
+            assert_eq!(client_secret, server_secret, "{}", key.0);
+            common += 1;
+        }
+    }
+    assert!(common > 0, "no common secrets");
+}
+
+/// Client and server report the same secrets to key log.
+pub fn test_key_log<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_key_log_impl::<C, A>())
+}
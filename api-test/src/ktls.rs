@@ -0,0 +1,75 @@
+use std::any;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::thread;
+
+use tls_api::KtlsAcceptor;
+use tls_api::KtlsConnector;
+use tls_api::TlsAcceptorBuilder;
+
+use crate::new_acceptor;
+use crate::new_connector_with_root_ca;
+use crate::BIND_HOST;
+
+/// Exchange data over kTLS.
+///
+/// Without kernel support (`tls` module not loaded) connection falls back to
+/// userspace TLS, and only the fallback is tested.
+pub fn test_ktls<C, A>()
+where
+    C: KtlsConnector,
+    A: KtlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    let acceptor: A = new_acceptor::<A>(None).build().expect("acceptor build");
+    let listener = t!(TcpListener::bind((BIND_HOST, 0)));
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let server_thread_name = format!("{}-server", thread::current().name().unwrap_or("test"));
+    let j = thread::Builder::new()
+        .name(server_thread_name)
+        .spawn(move || {
+            let socket = t!(listener.accept()).0;
+            let mut socket = t!(acceptor.accept_ktls(socket));
+            assert_eq!(A::info().name, socket.impl_info().name);
+
+            let mut buf = [0; 5];
+            t!(socket.read_exact(&mut buf));
+            assert_eq!(&buf, b"hello");
+
+            t!(socket.write_all(b"world"));
+            t!(socket.shutdown());
+            socket.is_ktls()
+        })
+        .unwrap();
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)));
+
+    let connector: C = new_connector_with_root_ca::<C>();
+    let mut socket = t!(connector.connect_ktls("localhost", socket));
+    assert_eq!(C::info().name, socket.impl_info().name);
+    assert_eq!(port, t!(socket.get_socket_ref().peer_addr()).port());
+
+    t!(socket.write_all(b"hello"));
+    // Byte at a time: session tickets and `close_notify` do not fit the buffer
+    let mut buf = vec![];
+    let mut byte = [0];
+    while t!(socket.read(&mut byte)) != 0 {
+        buf.push(byte[0]);
+    }
+    assert_eq!(buf, b"world");
+
+    let server_ktls = j.join().expect("thread join");
+    if !socket.is_ktls() || !server_ktls {
+        eprintln!(
+            "kTLS is not available for connector {} or acceptor {}; tested userspace fallback only",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+    assert_eq!(None, t!(socket.get_alpn_protocol()));
+}
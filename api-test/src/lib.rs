@@ -5,6 +5,15 @@
 //! Probably you don't need this crate outside of `rust-tls-api` repository.
 
 #![cfg_attr(rustc_nightly, feature(test))]
+// Helpers of async tests are unused without runtime
+#![cfg_attr(
+    not(any(
+        feature = "runtime-tokio",
+        feature = "runtime-async-std",
+        feature = "runtime-futures-io"
+    )),
+    allow(dead_code, unused_imports)
+)]
 
 #[macro_use]
 extern crate log;
@@ -18,20 +27,86 @@ use std::str;
 #[macro_use]
 mod t;
 
-mod alpn;
-pub mod benches;
-mod client_server;
-mod client_server_dyn;
-mod google;
-mod version;
-
-pub use alpn::test_alpn;
-pub use client_server::test_client_server_der;
-pub use client_server::test_client_server_pkcs12;
-pub use client_server_dyn::test_client_server_dyn_der;
-pub use client_server_dyn::test_client_server_dyn_pkcs12;
-pub use google::test_google;
-pub use version::test_version;
+/// Items which need an async runtime.
+///
+/// Only blocking tests are available when no runtime feature is enabled.
+macro_rules! cfg_runtime {
+    ($($item:item)*) => {
+        $(
+            #[cfg(any(
+                feature = "runtime-tokio",
+                feature = "runtime-async-std",
+                feature = "runtime-futures-io"
+            ))]
+            $item
+        )*
+    };
+}
+
+cfg_runtime! {
+    mod alpn;
+    pub mod benches;
+    mod channel_binding;
+    mod client_server;
+    mod client_server_dyn;
+    mod client_server_static;
+    mod fault;
+    mod google;
+    mod key_log;
+    mod mixed_runtimes;
+    mod ocsp;
+    mod revocation;
+    mod server_name;
+    mod truncation;
+    mod version;
+
+    pub use alpn::test_alpn;
+    pub use channel_binding::test_channel_binding;
+    pub use client_server::test_client_server_der;
+    pub use client_server::test_client_server_pkcs12;
+    pub use client_server_dyn::test_client_server_dyn_der;
+    pub use client_server_dyn::test_client_server_dyn_pkcs12;
+    pub use client_server_static::test_client_server_static;
+    pub use fault::test_fault_injection;
+    pub use google::test_google;
+    pub use key_log::test_key_log;
+    pub use mixed_runtimes::test_mixed_runtimes;
+    pub use ocsp::test_ocsp_must_staple;
+    pub use ocsp::test_ocsp_stapling;
+    pub use revocation::test_client_auth_crl;
+    pub use revocation::test_crl_not_revoked;
+    pub use revocation::test_crl_revoked;
+    pub use revocation::test_crl_unknown_status;
+    pub use server_name::test_server_name;
+    pub use truncation::test_truncation;
+    pub use version::test_version;
+}
+
+mod blocking;
+mod connection;
+mod dep_version;
+
+pub use blocking::test_blocking;
+pub use connection::test_connection;
+pub use dep_version::test_dep_version;
+
+mod quic;
+pub use quic::test_quic_connection;
+
+#[cfg(target_os = "linux")]
+mod ktls;
+#[cfg(target_os = "linux")]
+pub use ktls::test_ktls;
+
+// DTLS tests use tokio UDP sockets
+#[cfg(feature = "runtime-tokio")]
+mod dtls;
+#[cfg(feature = "runtime-tokio")]
+pub use dtls::test_dtls_alpn;
+#[cfg(feature = "runtime-tokio")]
+pub use dtls::test_dtls_client_server;
+#[cfg(feature = "runtime-tokio")]
+pub use dtls::test_dtls_packet_loss;
 
 mod gen;
 pub use gen::gen_tests_and_benches;
@@ -52,19 +127,38 @@ use tls_api::TlsConnectorType;
 
 use std::net::ToSocketAddrs;
 
-#[cfg(feature = "runtime-async-std")]
+// When several runtimes are enabled, tests run over tokio, then async-std, then smol,
+// `test_mixed_runtimes` uses `futures` runtime explicitly.
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 pub use async_std::net::TcpListener;
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 pub use async_std::net::TcpStream;
 
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+pub use smol::net::TcpListener;
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+pub use smol::net::TcpStream;
+
 #[cfg(feature = "runtime-tokio")]
 pub use tokio::net::TcpListener;
 #[cfg(feature = "runtime-tokio")]
 pub use tokio::net::TcpStream;
 
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 pub use async_std::task::block_on;
 
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+pub use smol::block_on;
+
 #[cfg(feature = "runtime-tokio")]
 pub fn block_on<F, T>(future: F) -> T
 where
@@ -73,59 +167,105 @@ where
     t!(tokio::runtime::Runtime::new()).block_on(future)
 }
 
-async fn connect_bad_hostname_impl<C: TlsConnector, F: FnOnce(anyhow::Error)>(check_error: F) {
-    drop(env_logger::try_init());
-
-    if !C::IMPLEMENTED {
-        eprintln!(
-            "connector {} is not implemented; skipping",
-            any::type_name::<C>()
-        );
-        return;
-    }
-
-    // First up, resolve google.com
-    let addr = t!("google.com:443".to_socket_addrs()).next().unwrap();
+/// IO traits of the runtime tests run over, same preference as for [`TcpStream`].
+pub mod runtime {
+    #[cfg(not(feature = "runtime-tokio"))]
+    pub use tls_api::runtime::futures::AsyncRead;
+    #[cfg(not(feature = "runtime-tokio"))]
+    pub use tls_api::runtime::futures::AsyncReadExt;
+    #[cfg(not(feature = "runtime-tokio"))]
+    pub use tls_api::runtime::futures::AsyncWrite;
+    #[cfg(not(feature = "runtime-tokio"))]
+    pub use tls_api::runtime::futures::AsyncWriteExt;
+
+    #[cfg(feature = "runtime-tokio")]
+    pub use tls_api::runtime::tokio::AsyncRead;
+    #[cfg(feature = "runtime-tokio")]
+    pub use tls_api::runtime::tokio::AsyncReadExt;
+    #[cfg(feature = "runtime-tokio")]
+    pub use tls_api::runtime::tokio::AsyncWrite;
+    #[cfg(feature = "runtime-tokio")]
+    pub use tls_api::runtime::tokio::AsyncWriteExt;
+}
 
-    let connector: C = C::builder().expect("builder").build().expect("build");
-    let tcp_stream = t!(TcpStream::connect(addr).await);
-    let error = connector
-        .connect("goggle.com", tcp_stream)
-        .await
-        .unwrap_err();
-    check_error(error);
+/// Send `close_notify` and shut down the socket, so the peer reading
+/// to the end of the stream sees clean EOF.
+#[cfg(feature = "runtime-tokio")]
+async fn shutdown<S>(socket: &mut S) -> std::io::Result<()>
+where
+    S: runtime::AsyncWrite + Unpin,
+{
+    runtime::AsyncWriteExt::shutdown(socket).await
 }
 
-pub fn connect_bad_hostname<C: TlsConnector, F: FnOnce(anyhow::Error)>(check_error: F) {
-    block_on(connect_bad_hostname_impl::<C, F>(check_error))
+/// Send `close_notify` and shut down the socket, so the peer reading
+/// to the end of the stream sees clean EOF.
+#[cfg(all(
+    any(feature = "runtime-async-std", feature = "runtime-futures-io"),
+    not(feature = "runtime-tokio")
+))]
+async fn shutdown<S>(socket: &mut S) -> std::io::Result<()>
+where
+    S: runtime::AsyncWrite + Unpin,
+{
+    runtime::AsyncWriteExt::close(socket).await
 }
 
-async fn connect_bad_hostname_ignored_impl<C: TlsConnector>() {
-    drop(env_logger::try_init());
+cfg_runtime! {
+    async fn connect_bad_hostname_impl<C: TlsConnector, F: FnOnce(anyhow::Error)>(check_error: F) {
+        drop(env_logger::try_init());
+
+        if !C::CAPABILITIES.implemented {
+            eprintln!(
+                "connector {} is not implemented; skipping",
+                any::type_name::<C>()
+            );
+            return;
+        }
+
+        // First up, resolve google.com
+        let addr = t!("google.com:443".to_socket_addrs()).next().unwrap();
 
-    if !C::IMPLEMENTED {
-        eprintln!(
-            "connector {} is not implemented; skipping",
-            any::type_name::<C>()
-        );
-        return;
+        let connector: C = C::builder().expect("builder").build().expect("build");
+        let tcp_stream = t!(TcpStream::connect(addr).await);
+        let error = connector
+            .connect("goggle.com", tcp_stream)
+            .await
+            .unwrap_err();
+        check_error(error);
     }
 
-    // First up, resolve google.com
-    let addr = t!("google.com:443".to_socket_addrs()).next().unwrap();
+    pub fn connect_bad_hostname<C: TlsConnector, F: FnOnce(anyhow::Error)>(check_error: F) {
+        block_on(connect_bad_hostname_impl::<C, F>(check_error))
+    }
 
-    let tcp_stream = t!(TcpStream::connect(addr).await);
+    async fn connect_bad_hostname_ignored_impl<C: TlsConnector>() {
+        drop(env_logger::try_init());
 
-    let mut builder = C::builder().expect("builder");
-    builder
-        .set_verify_hostname(false)
-        .expect("set_verify_hostname");
-    let connector: C = builder.build().expect("build");
-    t!(connector.connect("ignore", tcp_stream).await);
-}
+        if !C::CAPABILITIES.implemented {
+            eprintln!(
+                "connector {} is not implemented; skipping",
+                any::type_name::<C>()
+            );
+            return;
+        }
+
+        // First up, resolve google.com
+        let addr = t!("google.com:443".to_socket_addrs()).next().unwrap();
 
-pub fn connect_bad_hostname_ignored<C: TlsConnector>() {
-    block_on(connect_bad_hostname_ignored_impl::<C>())
+        let tcp_stream = t!(TcpStream::connect(addr).await);
+
+        let mut builder = C::builder().expect("builder");
+        builder
+            .set_verify_hostname(false)
+            .expect("set_verify_hostname");
+        let connector: C = builder.build().expect("build");
+        t!(connector.connect("ignore", tcp_stream).await);
+    }
+
+    pub fn connect_bad_hostname_ignored<C: TlsConnector>() {
+        block_on(connect_bad_hostname_ignored_impl::<C>())
+    }
 }
 
 fn new_acceptor_builder_from_pkcs12_keys<A>() -> A::Builder
@@ -186,9 +326,9 @@ where
         Some(AcceptorKeyKind::Der) => new_acceptor_builder_from_der_keys::<A>(),
         Some(AcceptorKeyKind::Pkcs12) => new_acceptor_builder_from_pkcs12_keys::<A>(),
         None => {
-            if A::SUPPORTS_PKCS12_KEYS {
+            if A::CAPABILITIES.pkcs12_keys {
                 new_acceptor_builder_from_pkcs12_keys::<A>()
-            } else if A::SUPPORTS_DER_KEYS {
+            } else if A::CAPABILITIES.der_keys {
                 new_acceptor_builder_from_der_keys::<A>()
             } else {
                 panic!(
@@ -208,9 +348,9 @@ fn new_acceptor_dyn(
         Some(AcceptorKeyKind::Der) => new_acceptor_builder_dyn_from_der_keys(acceptor),
         Some(AcceptorKeyKind::Pkcs12) => new_acceptor_builder_dyn_from_pkcs12_keys(acceptor),
         None => {
-            if acceptor.supports_pkcs12_keys() {
+            if acceptor.capabilities().pkcs12_keys {
                 new_acceptor_builder_dyn_from_pkcs12_keys(acceptor)
-            } else if acceptor.supports_der_keys() {
+            } else if acceptor.capabilities().der_keys {
                 new_acceptor_builder_dyn_from_der_keys(acceptor)
             } else {
                 panic!("no constructor supported for acceptor {}", acceptor);
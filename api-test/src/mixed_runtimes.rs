@@ -0,0 +1,118 @@
+//! Test of a build with both `runtime-tokio` and `futures` runtime
+//! (`runtime-async-std` or `runtime-futures-io`) enabled.
+
+use std::any;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsConnector;
+
+#[cfg(all(
+    feature = "runtime-tokio",
+    any(feature = "runtime-async-std", feature = "runtime-futures-io")
+))]
+fn test_mixed_runtimes_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    use std::sync::mpsc;
+    use std::thread;
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::net::TcpStream as FuturesTcpStream;
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::block_on as futures_block_on;
+    #[cfg(all(feature = "runtime-futures-io", not(feature = "runtime-async-std")))]
+    use smol::block_on as futures_block_on;
+    #[cfg(all(feature = "runtime-futures-io", not(feature = "runtime-async-std")))]
+    use smol::net::TcpStream as FuturesTcpStream;
+
+    use tls_api::TlsAcceptorBuilder;
+    use tls_api::TlsConnectorBuilder;
+    use tls_api::TlsStreamDyn;
+    use tls_api::TlsStreamWithSocketDyn;
+
+    use crate::new_acceptor;
+    use crate::new_connector_builder_with_root_ca;
+    use crate::BIND_HOST;
+
+    let acceptor: A = t!(new_acceptor::<A>(None).build());
+
+    let (port_tx, port_rx) = mpsc::channel();
+
+    // Server uses tokio and tokio IO traits
+    let j = thread::spawn(move || {
+        use tls_api::runtime::tokio::AsyncReadExt;
+        use tls_api::runtime::tokio::AsyncWriteExt;
+
+        let future = async {
+            let listener = t!(tokio::net::TcpListener::bind((BIND_HOST, 0)).await);
+            port_tx
+                .send(listener.local_addr().expect("local_addr").port())
+                .unwrap();
+            let socket = t!(listener.accept().await).0;
+            let mut socket = t!(acceptor.accept_with_socket(socket).await);
+            assert_eq!(A::info().name, socket.impl_info().name);
+            let mut buf = [0; 5];
+            t!(socket.read_exact(&mut buf).await);
+            assert_eq!(b"hello", &buf);
+            t!(socket.write_all(b"world").await);
+            t!(socket.shutdown().await);
+        };
+        crate::block_on(future)
+    });
+
+    let port = port_rx.recv().expect("port");
+
+    // Client uses async-std or smol and `futures` IO traits
+    let future = async {
+        use tls_api::runtime::futures::AsyncReadExt;
+        use tls_api::runtime::futures::AsyncWriteExt;
+
+        let connector: C = t!(new_connector_builder_with_root_ca::<C>().build());
+        let socket = t!(FuturesTcpStream::connect((BIND_HOST, port)).await);
+        let mut socket = t!(connector.connect_with_socket("localhost", socket).await);
+        assert_eq!(C::info().name, socket.impl_info().name);
+        assert_eq!(port, t!(socket.get_socket_ref().peer_addr()).port());
+        t!(socket.write_all(b"hello").await);
+        let mut buf = vec![];
+        t!(socket.read_to_end(&mut buf).await);
+        assert_eq!(b"world", &buf[..]);
+    };
+    futures_block_on(future);
+
+    j.join().expect("thread join");
+}
+
+/// Tokio server and async-std (or smol) client in a build with both runtimes enabled.
+///
+/// Does nothing unless both `runtime-tokio` and `runtime-async-std` (or `runtime-futures-io`)
+/// are enabled.
+pub fn test_mixed_runtimes<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    #[cfg(all(
+        feature = "runtime-tokio",
+        any(feature = "runtime-async-std", feature = "runtime-futures-io")
+    ))]
+    test_mixed_runtimes_impl::<C, A>();
+
+    #[cfg(not(all(
+        feature = "runtime-tokio",
+        any(feature = "runtime-async-std", feature = "runtime-futures-io")
+    )))]
+    eprintln!("not a build with both tokio and futures runtimes; skipping");
+}
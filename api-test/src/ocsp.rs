@@ -0,0 +1,179 @@
+//! OCSP stapling tests.
+
+use std::any;
+use std::sync::Arc;
+use std::thread;
+
+use tls_api::OcspStapling;
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api::TlsStreamDyn;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Perform a single exchange, return OCSP response observed by the client.
+async fn connect_get_ocsp_response<C, A>(
+    connector: &C,
+    acceptor: Arc<A>,
+) -> anyhow::Result<Option<Vec<u8>>>
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            // Handshake fails on the server side too if client rejects it
+            let mut socket = match acceptor.accept(socket).await {
+                Ok(socket) => socket,
+                Err(_) => return,
+            };
+            t!(socket.write_all(b"hello").await);
+            t!(crate::shutdown(&mut socket).await);
+        };
+        block_on(future)
+    });
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+
+    let client = async {
+        let mut socket = connector.connect("localhost", socket).await?;
+        let mut buf = vec![];
+        socket.read_to_end(&mut buf).await?;
+        assert_eq!(b"hello", &buf[..]);
+        socket.ocsp_response()
+    };
+    let client = client.await;
+
+    j.join().expect("thread join");
+
+    client
+}
+
+fn skip_ocsp<C, A>() -> bool
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return true;
+    }
+
+    if !C::CAPABILITIES.ocsp_stapling || !A::CAPABILITIES.ocsp_stapling {
+        eprintln!(
+            "connector {} or acceptor {} does not support OCSP stapling; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return true;
+    }
+
+    false
+}
+
+fn new_connector<C>(mode: OcspStapling) -> C
+where
+    C: TlsConnector,
+{
+    let mut connector = new_connector_builder_with_root_ca::<C>();
+    t!(connector.set_ocsp_stapling(mode));
+    t!(connector.build())
+}
+
+async fn test_ocsp_stapling_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if skip_ocsp::<C, A>() {
+        return;
+    }
+
+    let keys = test_cert_gen::keys();
+
+    let mut acceptor = new_acceptor::<A>(None);
+    t!(acceptor.set_ocsp_response(&keys.server.ocsp_response));
+    let acceptor: Arc<A> = Arc::new(t!(acceptor.build()));
+
+    let connector: C = new_connector(OcspStapling::Request);
+
+    let ocsp_response = t!(connect_get_ocsp_response(&connector, acceptor.clone()).await);
+    assert_eq!(Some(&keys.server.ocsp_response), ocsp_response.as_ref());
+
+    // Replace the response in already built acceptor
+    t!(acceptor.set_ocsp_response(&keys.server.ocsp_response_next));
+
+    let ocsp_response = t!(connect_get_ocsp_response(&connector, acceptor.clone()).await);
+    assert_eq!(
+        Some(&keys.server.ocsp_response_next),
+        ocsp_response.as_ref()
+    );
+
+    let connector: C = new_connector(OcspStapling::MustStaple);
+    let ocsp_response = t!(connect_get_ocsp_response(&connector, acceptor).await);
+    assert_eq!(
+        Some(&keys.server.ocsp_response_next),
+        ocsp_response.as_ref()
+    );
+}
+
+/// Server staples OCSP response, and it can be replaced after acceptor is built.
+pub fn test_ocsp_stapling<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_ocsp_stapling_impl::<C, A>())
+}
+
+async fn test_ocsp_must_staple_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if skip_ocsp::<C, A>() {
+        return;
+    }
+
+    let acceptor: Arc<A> = Arc::new(t!(new_acceptor::<A>(None).build()));
+
+    let connector: C = new_connector(OcspStapling::Request);
+    let ocsp_response = t!(connect_get_ocsp_response(&connector, acceptor.clone()).await);
+    assert_eq!(None, ocsp_response);
+
+    let connector: C = new_connector(OcspStapling::MustStaple);
+    assert!(connect_get_ocsp_response(&connector, acceptor)
+        .await
+        .is_err());
+}
+
+/// Server does not staple OCSP response.
+pub fn test_ocsp_must_staple<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_ocsp_must_staple_impl::<C, A>())
+}
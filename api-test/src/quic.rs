@@ -0,0 +1,111 @@
+use tls_api::QuicAcceptor;
+use tls_api::QuicConnection;
+use tls_api::QuicConnectionDyn;
+use tls_api::QuicConnector;
+use tls_api::QuicEncryptionLevel;
+use tls_api::QuicEvent;
+use tls_api::QuicSecrets;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnectorBuilder;
+
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+
+/// Pass handshake data from one connection to another, collect secrets of the first one.
+fn transfer(
+    from: &mut QuicConnection,
+    to: &mut QuicConnection,
+    secrets: &mut Vec<QuicSecrets>,
+) -> usize {
+    let mut transferred = 0;
+    while let Some(event) = from.next_event() {
+        match event {
+            QuicEvent::CryptoData { level, data } => {
+                // Data is sent at the level which keys are installed
+                if level != QuicEncryptionLevel::Initial {
+                    assert!(
+                        secrets.iter().any(|s| s.level == level),
+                        "no keys for {:?}",
+                        level
+                    );
+                }
+                t!(to.feed_crypto_data(level, &data));
+                transferred += data.len();
+            }
+            QuicEvent::Secrets(s) => secrets.push(s),
+        }
+    }
+    transferred
+}
+
+pub fn test_quic_connection<C, A>()
+where
+    C: QuicConnector,
+    A: QuicAcceptor,
+{
+    drop(env_logger::try_init());
+
+    let mut acceptor = new_acceptor::<A>(None);
+    let mut connector = new_connector_builder_with_root_ca::<C>();
+    t!(acceptor.set_alpn_protocols(&[b"h3"]));
+    t!(connector.set_alpn_protocols(&[b"h3"]));
+    let acceptor: A = t!(acceptor.build());
+    let connector: C = t!(connector.build());
+
+    // Client hello is sent at initial level
+    let mut server = t!(acceptor.new_quic_connection(b"server params"));
+    assert!(server
+        .feed_crypto_data(QuicEncryptionLevel::Handshake, &[1])
+        .is_err());
+
+    let mut client = t!(connector.new_quic_connection("localhost", b"client params"));
+    let mut server = t!(acceptor.new_quic_connection(b"server params"));
+
+    let mut client_secrets = Vec::new();
+    let mut server_secrets = Vec::new();
+
+    let mut round_trips = 0;
+    while client.is_handshaking() || server.is_handshaking() {
+        let transferred = transfer(&mut client, &mut server, &mut client_secrets)
+            + transfer(&mut server, &mut client, &mut server_secrets);
+        assert!(transferred != 0, "handshake is stuck");
+        round_trips += 1;
+        assert!(round_trips < 10, "too many round trips");
+    }
+    // Session tickets
+    transfer(&mut server, &mut client, &mut server_secrets);
+    transfer(&mut client, &mut server, &mut client_secrets);
+
+    let levels = [QuicEncryptionLevel::Handshake, QuicEncryptionLevel::OneRtt];
+    for secrets in [&client_secrets, &server_secrets] {
+        assert_eq!(
+            &levels[..],
+            &secrets.iter().map(|s| s.level).collect::<Vec<_>>()[..]
+        );
+    }
+    for (c, s) in client_secrets.iter().zip(&server_secrets) {
+        assert_ne!(0, c.cipher_suite);
+        assert_eq!(c.cipher_suite, s.cipher_suite);
+        assert!(!c.local.is_empty());
+        assert_eq!(c.local, s.remote);
+        assert_eq!(c.remote, s.local);
+        assert_ne!(c.local, c.remote);
+    }
+
+    assert_eq!(
+        Some(&b"server params"[..]),
+        client.peer_transport_parameters().as_deref()
+    );
+    assert_eq!(
+        Some(&b"client params"[..]),
+        server.peer_transport_parameters().as_deref()
+    );
+
+    assert_eq!(Some(&b"h3"[..]), t!(client.get_alpn_protocol()).as_deref());
+    assert_eq!(Some(&b"h3"[..]), t!(server.get_alpn_protocol()).as_deref());
+
+    assert_eq!(
+        t!(client.export_keying_material("EXPERIMENTAL test", None, 32)),
+        t!(server.export_keying_material("EXPERIMENTAL test", None, 32)),
+    );
+}
@@ -0,0 +1,294 @@
+//! Certificate revocation list tests.
+
+use std::any;
+use std::thread;
+
+use tls_api::RevocationPolicy;
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Results of client and server sides of the exchange.
+struct Outcome {
+    client: anyhow::Result<()>,
+    server: anyhow::Result<()>,
+}
+
+impl Outcome {
+    fn assert_ok(self) {
+        self.client.expect("client");
+        self.server.expect("server");
+    }
+
+    fn assert_client_failed(self) {
+        assert!(self.client.is_err(), "expecting client error");
+    }
+
+    fn assert_server_failed(self) {
+        assert!(self.server.is_err(), "expecting server error");
+    }
+}
+
+async fn client_server<C, A>(connector: C::Builder, acceptor: A::Builder) -> Outcome
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    let acceptor: A = acceptor.build().expect("acceptor build");
+    let connector: C = connector.build().expect("connector build");
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let server_thread_name = format!("{}-server", thread::current().name().unwrap_or("test"));
+    let j = thread::Builder::new()
+        .name(server_thread_name)
+        .spawn(move || {
+            let future = async {
+                let socket = t!(listener.accept().await).0;
+                let mut socket = acceptor.accept(socket).await?;
+
+                let mut buf = [0; 5];
+                socket.read_exact(&mut buf).await?;
+                assert_eq!(&buf, b"hello");
+
+                socket.write_all(b"world").await?;
+                crate::shutdown(&mut socket).await?;
+                Ok(())
+            };
+            block_on(future)
+        })
+        .unwrap();
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+
+    let client = async {
+        let mut socket = connector.connect("localhost", socket).await?;
+        // With TLS 1.3 client handshake may complete before server verifies client certificate,
+        // so the error is only observed on read.
+        socket.write_all(b"hello").await?;
+        let mut buf = vec![];
+        socket.read_to_end(&mut buf).await?;
+        if buf != b"world" {
+            anyhow::bail!("unexpected response: {:?}", buf);
+        }
+        Ok(())
+    };
+    let client = client.await;
+
+    let server = j.join().expect("thread join");
+
+    Outcome { client, server }
+}
+
+fn skip_crl<C, A>() -> bool
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return true;
+    }
+
+    if !C::CAPABILITIES.crl {
+        eprintln!(
+            "connector {} does not support CRL; skipping",
+            any::type_name::<C>()
+        );
+        return true;
+    }
+
+    false
+}
+
+async fn connect_with_crl<C, A>(crl: &test_cert_gen::Crl, policy: RevocationPolicy) -> Outcome
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    let mut connector = new_connector_builder_with_root_ca::<C>();
+    t!(connector.add_crl_der(crl.get_der()));
+    t!(connector.set_revocation_policy(policy));
+
+    client_server::<C, A>(connector, new_acceptor::<A>(None)).await
+}
+
+async fn test_crl_not_revoked_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if skip_crl::<C, A>() {
+        return;
+    }
+
+    let crls = &test_cert_gen::keys().crls;
+
+    connect_with_crl::<C, A>(&crls.empty, RevocationPolicy::HardFail)
+        .await
+        .assert_ok();
+    connect_with_crl::<C, A>(&crls.empty, RevocationPolicy::LeafOnly)
+        .await
+        .assert_ok();
+}
+
+/// Server certificate is not in CRL.
+pub fn test_crl_not_revoked<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_crl_not_revoked_impl::<C, A>())
+}
+
+async fn test_crl_revoked_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if skip_crl::<C, A>() {
+        return;
+    }
+
+    let crls = &test_cert_gen::keys().crls;
+
+    for policy in [
+        RevocationPolicy::HardFail,
+        RevocationPolicy::SoftFail,
+        RevocationPolicy::LeafOnly,
+    ] {
+        let mut connector = new_connector_builder_with_root_ca::<C>();
+        t!(connector.add_crl_pem(crls.revoked.to_pem().as_bytes()));
+        t!(connector.set_revocation_policy(policy));
+
+        client_server::<C, A>(connector, new_acceptor::<A>(None))
+            .await
+            .assert_client_failed();
+    }
+}
+
+/// Server certificate is revoked.
+pub fn test_crl_revoked<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_crl_revoked_impl::<C, A>())
+}
+
+async fn test_crl_unknown_status_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if skip_crl::<C, A>() {
+        return;
+    }
+
+    let crls = &test_cert_gen::keys().crls;
+
+    connect_with_crl::<C, A>(&crls.foreign, RevocationPolicy::HardFail)
+        .await
+        .assert_client_failed();
+    connect_with_crl::<C, A>(&crls.foreign, RevocationPolicy::LeafOnly)
+        .await
+        .assert_client_failed();
+    connect_with_crl::<C, A>(&crls.foreign, RevocationPolicy::SoftFail)
+        .await
+        .assert_ok();
+}
+
+/// No CRL for the server certificate issuer.
+pub fn test_crl_unknown_status<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_crl_unknown_status_impl::<C, A>())
+}
+
+async fn test_client_auth_crl_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    if !C::CAPABILITIES.client_auth || !A::CAPABILITIES.client_auth || !A::CAPABILITIES.crl {
+        eprintln!(
+            "connector {} or acceptor {} does not support client auth with CRL; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let keys = test_cert_gen::keys();
+
+    let acceptor = |crl: &test_cert_gen::Crl| {
+        let mut acceptor = new_acceptor::<A>(None);
+        t!(acceptor.add_client_auth_root_certificate(keys.client.ca.get_der()));
+        t!(acceptor.add_crl_der(crl.get_der()));
+        acceptor
+    };
+    let connector = |with_cert: bool| {
+        let mut connector = new_connector_builder_with_root_ca::<C>();
+        if with_cert {
+            t!(connector.set_client_certificate(
+                keys.client.cert_and_key.cert.get_der(),
+                keys.client.cert_and_key.key.get_der(),
+            ));
+        }
+        connector
+    };
+
+    client_server::<C, A>(connector(true), acceptor(&keys.crls.empty))
+        .await
+        .assert_ok();
+    client_server::<C, A>(connector(true), acceptor(&keys.crls.revoked))
+        .await
+        .assert_server_failed();
+    client_server::<C, A>(connector(false), acceptor(&keys.crls.empty))
+        .await
+        .assert_server_failed();
+}
+
+/// Client authentication with client certificates checked against CRL.
+pub fn test_client_auth_crl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_client_auth_crl_impl::<C, A>())
+}
@@ -0,0 +1,85 @@
+//! Server name indication test.
+
+use std::any;
+use std::thread;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api::TlsStreamDyn;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_builder_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+/// Check server name if the implementation exposes it.
+fn check_server_name(side: &str, stream: &dyn TlsStreamDyn) {
+    match stream.server_name() {
+        Ok(server_name) => assert_eq!(Some("localhost"), server_name.as_deref(), "{}", side),
+        Err(e) => eprintln!(
+            "{} {} does not expose server name: {}",
+            side,
+            stream.impl_info().name,
+            e
+        ),
+    }
+}
+
+async fn test_server_name_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>(),
+        );
+        return;
+    }
+
+    let acceptor: A = t!(new_acceptor::<A>(None).build());
+
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let j = thread::spawn(move || {
+        let future = async {
+            let socket = t!(listener.accept().await).0;
+            let mut socket = t!(acceptor.accept(socket).await);
+            check_server_name("server", &socket);
+            t!(socket.write_all(b"hello").await);
+            t!(crate::shutdown(&mut socket).await);
+        };
+        block_on(future)
+    });
+
+    let connector: C = t!(new_connector_builder_with_root_ca::<C>().build());
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+    let mut socket = t!(connector.connect("localhost", socket).await);
+    let mut buf = vec![];
+    t!(socket.read_to_end(&mut buf).await);
+    assert_eq!(b"hello", &buf[..]);
+    check_server_name("client", &socket);
+
+    j.join().expect("thread join");
+}
+
+/// Both sides see the name passed to `connect`.
+pub fn test_server_name<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_server_name_impl::<C, A>())
+}
@@ -0,0 +1,75 @@
+use std::any;
+use std::io;
+use std::thread;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnector;
+
+use crate::block_on;
+use crate::new_acceptor;
+use crate::new_connector_with_root_ca;
+use crate::runtime::AsyncReadExt;
+use crate::runtime::AsyncWriteExt;
+use crate::TcpListener;
+use crate::TcpStream;
+use crate::BIND_HOST;
+
+async fn test_truncation_impl<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    drop(env_logger::try_init());
+
+    if !C::CAPABILITIES.implemented || !A::CAPABILITIES.implemented {
+        eprintln!(
+            "connector {} or acceptor {} is not implemented; skipping",
+            any::type_name::<C>(),
+            any::type_name::<A>()
+        );
+        return;
+    }
+
+    let acceptor: A = t!(new_acceptor::<A>(None).build());
+    #[allow(unused_mut)]
+    let mut listener = t!(TcpListener::bind((BIND_HOST, 0)).await);
+    let port = listener.local_addr().expect("local_addr").port();
+
+    let server_thread_name = format!("{}-server", thread::current().name().unwrap_or("test"));
+    let j = thread::Builder::new()
+        .name(server_thread_name)
+        .spawn(move || {
+            let future = async {
+                let socket = t!(listener.accept().await).0;
+                let mut socket = t!(acceptor.accept(socket).await);
+                t!(socket.write_all(b"world").await);
+                t!(socket.flush().await);
+                // Close the socket without sending `close_notify`
+            };
+            block_on(future);
+        })
+        .unwrap();
+
+    let socket = t!(TcpStream::connect((BIND_HOST, port)).await);
+
+    let connector: C = new_connector_with_root_ca();
+    let mut socket = t!(connector.connect("localhost", socket).await);
+
+    let mut buf = vec![];
+    let err = socket.read_to_end(&mut buf).await.unwrap_err();
+    assert_eq!(io::ErrorKind::UnexpectedEof, err.kind(), "{}", err);
+    assert_eq!(b"world", &buf[..]);
+
+    j.join().expect("thread join");
+}
+
+/// Socket closed without `close_notify` is reported as an error
+/// rather than the end of stream, so truncation attack is detected.
+pub fn test_truncation<C, A>()
+where
+    C: TlsConnector,
+    A: TlsAcceptor,
+{
+    block_on(test_truncation_impl::<C, A>())
+}
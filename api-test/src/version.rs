@@ -2,15 +2,18 @@ use std::any;
 
 pub fn test_version<C: tls_api::TlsConnector, A: tls_api::TlsAcceptor>() {
     eprintln!(
-        "connector {} '{}' supports_alpn={}",
-        any::type_name::<A>(),
-        A::info(),
-        A::SUPPORTS_ALPN
-    );
-    eprintln!(
-        "acceptor {} '{}' supports_alpn={}",
+        "connector {} '{}' capabilities: {}",
         any::type_name::<C>(),
         C::info(),
-        C::SUPPORTS_ALPN
+        C::CAPABILITIES
+    );
+    eprintln!(
+        "acceptor {} '{}' capabilities: {}",
+        any::type_name::<A>(),
+        A::info(),
+        A::CAPABILITIES
     );
+    if C::CAPABILITIES.implemented {
+        assert_ne!("unknown", C::info().version);
+    }
 }
@@ -2,15 +2,25 @@ use crate::acceptor_box::TlsAcceptorType;
 use crate::acceptor_box::TlsAcceptorTypeImpl;
 use crate::openssl::der_to_pkcs12;
 use crate::openssl::pkcs12_to_der;
+use crate::revocation::crl_pem_to_der;
 use crate::socket::AsyncSocket;
+use crate::spi::TlsStreamWithUpcastDyn;
 use crate::stream::TlsStream;
 use crate::BoxFuture;
+use crate::Capabilities;
 use crate::ImplInfo;
+use crate::KeyLog;
+use crate::RevocationPolicy;
 use crate::TlsAcceptorBox;
+use crate::TlsConnection;
+use crate::TlsStreamBlocking;
 use crate::TlsStreamDyn;
 use crate::TlsStreamWithSocket;
 use std::fmt;
+use std::future::Future;
 use std::marker;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 /// A builder for `TlsAcceptor`s.
 pub trait TlsAcceptorBuilder: Sized + Sync + Send + 'static {
@@ -27,9 +37,52 @@ pub trait TlsAcceptorBuilder: Sized + Sync + Send + 'static {
     ///
     /// This operation returns an error if the implemenation does not support ALPN.
     ///
-    /// Whether ALPN is supported, can be queried using [`TlsAcceptor::SUPPORTS_ALPN`].
+    /// Whether ALPN is supported, can be queried using [`Capabilities::alpn`].
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
 
+    /// Require clients to present a certificate, and verify it using given root certificate.
+    ///
+    /// Param is DER-encoded X.509 certificate.
+    ///
+    /// This operation fails if not [`Capabilities::client_auth`].
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()>;
+
+    /// Add certificate revocation list used to check revocation status
+    /// of the client certificate chain.
+    ///
+    /// Param is DER-encoded X.509 CRL.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    /// Add certificate revocation lists from a PEM file.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        for crl in crl_pem_to_der(crl)? {
+            self.add_crl_der(&crl)?;
+        }
+        Ok(())
+    }
+
+    /// How added CRLs are checked, [`RevocationPolicy::HardFail`] by default.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()>;
+
+    /// DER-encoded OCSP response stapled to the server certificate.
+    ///
+    /// Response can be replaced later with [`TlsAcceptor::set_ocsp_response`].
+    ///
+    /// This operation fails if not [`Capabilities::ocsp_stapling`].
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()>;
+
+    /// Report TLS secrets of accepted connections to given [`KeyLog`],
+    /// e. g. [`KeyLogFile`](crate::KeyLogFile).
+    ///
+    /// This operation fails if not [`Capabilities::key_log`].
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()>;
+
     /// Get the underlying builder.
     ///
     /// API intentionally exposes the underlying acceptor builder to allow fine tuning
@@ -50,37 +103,22 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
 
     /// `crate::TlsStream<tls_api::AsyncSocketBox>`.
     ///
-    /// In the world of HKT this would be:
-    ///
-    /// ```ignore
-    /// type TlsStream<S: TlsStreamDyn> : TlsStreamWithSocketDyn<S>;
-    /// ```
-    ///
-    /// Note each implementation has `accept_impl` function
-    /// which returns more specific type, providing both access to implementation details
-    /// and the underlying socket.
+    /// Same as [`Stream<AsyncSocketBox>`](Self::Stream).
     type TlsStream: TlsStreamDyn;
 
+    /// `crate::TlsStream<S>`: stream of the implementation over the socket `S`.
+    ///
+    /// Returned by [`accept_static`](Self::accept_static).
+    type Stream<S: AsyncSocket>: TlsStreamWithUpcastDyn<S>;
+
     /// Get the underlying acceptor.
     ///
     /// API intentionally exposes the underlying acceptor builder to allow fine acceptor
     /// not possible in common API.
     fn underlying_mut(&mut self) -> &mut Self::Underlying;
 
-    /// Whether this acceptor type is implemented.
-    ///
-    /// For example, `tls-api-security-framework` is available on Linux,
-    /// but all operations result in error, so `IMPLEMENTED = false`
-    /// for that implementation.
-    const IMPLEMENTED: bool;
-    /// Whether this implementation supports ALPN negotiation.
-    const SUPPORTS_ALPN: bool;
-    /// Whether this implementation supports construction of acceptor using
-    /// a pair of a DER certificate and file pair.
-    const SUPPORTS_DER_KEYS: bool;
-    /// Whether this implementation supports construction of acceptor using
-    /// PKCS #12 file.
-    const SUPPORTS_PKCS12_KEYS: bool;
+    /// Features supported by this implementation.
+    const CAPABILITIES: Capabilities;
 
     /// Dynamic (without type parameter) version of the acceptor.
     ///
@@ -104,9 +142,9 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
     /// `openssl` command is used to convert the certificate.
     fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<Self::Builder> {
         let _ = (cert, key);
-        assert!(!Self::SUPPORTS_DER_KEYS);
+        assert!(!Self::CAPABILITIES.der_keys);
 
-        if !Self::SUPPORTS_PKCS12_KEYS {
+        if !Self::CAPABILITIES.pkcs12_keys {
             Err(crate::CommonError::TlsBuilderFromFromDerOrPkcs12NotSupported(Self::TYPE_DYN))?;
         }
 
@@ -121,9 +159,9 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
     /// `openssl` command is used to convert the certificate.
     fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<Self::Builder> {
         let _ = (pkcs12, passphrase);
-        assert!(!Self::SUPPORTS_PKCS12_KEYS);
+        assert!(!Self::CAPABILITIES.pkcs12_keys);
 
-        if !Self::SUPPORTS_DER_KEYS {
+        if !Self::CAPABILITIES.der_keys {
             Err(crate::CommonError::TlsBuilderFromFromDerOrPkcs12NotSupported(Self::TYPE_DYN))?;
         }
 
@@ -132,6 +170,13 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
         Self::builder_from_der_key(&cert, &key)
     }
 
+    /// Replace DER-encoded OCSP response stapled to the server certificate.
+    ///
+    /// New response is used for connections accepted after this call.
+    ///
+    /// This operation fails if not [`Capabilities::ocsp_stapling`].
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()>;
+
     /// Accept a connection.
     ///
     /// This operation returns a future which is resolved when the negotiation is complete,
@@ -143,7 +188,30 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
         stream: S,
     ) -> BoxFuture<'a, anyhow::Result<TlsStreamWithSocket<S>>>
     where
-        S: AsyncSocket + fmt::Debug + Unpin;
+        S: AsyncSocket + fmt::Debug + Unpin,
+    {
+        BoxFuture::new(async move {
+            self.accept_static(stream)
+                .await
+                .map(TlsStreamWithSocket::new)
+        })
+    }
+
+    /// Accept a connection.
+    ///
+    /// This operation returns a future which is resolved when the negotiation is complete,
+    /// and the stream is ready to send and receive.
+    ///
+    /// Neither the future nor the stream is boxed, so this function
+    /// does not allocate (unless the implementation itself does),
+    /// and the stream calls are statically dispatched.
+    /// Other `accept` functions are implemented on top of this one.
+    fn accept_static<'a, S>(
+        &'a self,
+        stream: S,
+    ) -> impl Future<Output = anyhow::Result<Self::Stream<S>>> + Send + 'a
+    where
+        S: AsyncSocket;
 
     /// Accept a connection.
     ///
@@ -175,23 +243,42 @@ pub trait TlsAcceptor: Sized + Sync + Send + 'static {
     {
         BoxFuture::new(async move { self.accept_with_socket(stream).await.map(TlsStream::new) })
     }
+
+    /// Accept a connection over a blocking socket.
+    ///
+    /// This function returns when the negotiation is complete.
+    /// This function does not need an async runtime,
+    /// and it is available when no runtime feature is enabled.
+    ///
+    /// This operation fails if not [`Capabilities::blocking`].
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking>;
+
+    /// Create a sans-IO server connection.
+    ///
+    /// Handshake is driven by the caller by exchanging ciphertext
+    /// with [`TlsConnection`] functions.
+    ///
+    /// This operation fails if not [`Capabilities::connection`].
+    fn new_connection(&self) -> anyhow::Result<TlsConnection>;
 }
 
 /// Common part of all connectors. Poor man replacement for HKT.
+///
+/// Parameter is the stream type of the implementation over the socket `S`,
+/// e. g. `spi_acceptor_common!(crate::TlsStream<S>)`.
 #[macro_export]
 macro_rules! spi_acceptor_common {
-    () => {
-        fn accept_with_socket<'a, S>(
+    ($stream:ty) => {
+        type Stream<S: $crate::AsyncSocket> = $stream;
+
+        fn accept_static<'a, S>(
             &'a self,
             stream: S,
-        ) -> $crate::BoxFuture<'a, anyhow::Result<$crate::TlsStreamWithSocket<S>>>
+        ) -> impl std::future::Future<Output = anyhow::Result<Self::Stream<S>>> + Send + 'a
         where
             S: $crate::AsyncSocket,
         {
-            $crate::BoxFuture::new(async move {
-                let crate_tls_stream: crate::TlsStream<S> = self.accept_impl(stream).await?;
-                Ok($crate::TlsStreamWithSocket::new(crate_tls_stream))
-            })
+            self.accept_impl(stream)
         }
 
         fn accept_impl_tls_stream<'a, S>(
@@ -1,15 +1,22 @@
 use std::fmt;
 use std::marker;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 use crate::assert_send;
 use crate::assert_sync;
 use crate::AsyncSocket;
 use crate::AsyncSocketBox;
 use crate::BoxFuture;
+use crate::Capabilities;
 use crate::ImplInfo;
+use crate::KeyLog;
+use crate::RevocationPolicy;
 use crate::TlsAcceptor;
 use crate::TlsAcceptorBuilder;
+use crate::TlsConnection;
 use crate::TlsStream;
+use crate::TlsStreamBlocking;
 
 // Type
 
@@ -17,26 +24,14 @@ use crate::TlsStream;
 ///
 /// This can be obtained with [`TlsAcceptor::TYPE_DYN`].
 pub trait TlsAcceptorType: fmt::Debug + fmt::Display + Sync + 'static {
-    /// Whether this acceptor type is implemented.
-    ///
-    /// For example, `tls-api-security-framework` is available on Linux,
-    /// but all operations result in error, so `implemented()` returns `false`
-    /// for that implementation.
-    fn implemented(&self) -> bool;
-    /// Whether this implementation supports ALPN negotiation.
-    fn supports_alpn(&self) -> bool;
-    /// Whether this implementation supports construction of acceptor using
-    /// a pair of a DER certificate and file pair.
-    fn supports_der_keys(&self) -> bool;
-    /// Whether this implementation supports construction of acceptor using
-    /// PKCS #12 file.
-    fn supports_pkcs12_keys(&self) -> bool;
+    /// Features supported by this implementation.
+    fn capabilities(&self) -> Capabilities;
     /// Unspecified version information about this implementation.
     fn info(&self) -> ImplInfo;
 
     /// New builder from given server key.
     ///
-    /// This operation is guaranteed to fail if not [`TlsAcceptorType::supports_der_keys`].
+    /// This operation is guaranteed to fail if not [`Capabilities::der_keys`].
     fn builder_from_der_key(
         &self,
         cert: &[u8],
@@ -45,7 +40,7 @@ pub trait TlsAcceptorType: fmt::Debug + fmt::Display + Sync + 'static {
 
     /// New builder from given server key.
     ///
-    /// This operation is guaranteed to fail if not [`TlsAcceptorType::supports_pkcs12_keys`].
+    /// This operation is guaranteed to fail if not [`Capabilities::pkcs12_keys`].
     fn builder_from_pkcs12(
         &self,
         pkcs12: &[u8],
@@ -68,20 +63,8 @@ impl<A: TlsAcceptor> fmt::Display for TlsAcceptorTypeImpl<A> {
 }
 
 impl<A: TlsAcceptor> TlsAcceptorType for TlsAcceptorTypeImpl<A> {
-    fn implemented(&self) -> bool {
-        A::IMPLEMENTED
-    }
-
-    fn supports_alpn(&self) -> bool {
-        A::SUPPORTS_ALPN
-    }
-
-    fn supports_der_keys(&self) -> bool {
-        A::SUPPORTS_DER_KEYS
-    }
-
-    fn supports_pkcs12_keys(&self) -> bool {
-        A::SUPPORTS_PKCS12_KEYS
+    fn capabilities(&self) -> Capabilities {
+        A::CAPABILITIES
     }
 
     fn info(&self) -> ImplInfo {
@@ -109,11 +92,23 @@ impl<A: TlsAcceptor> TlsAcceptorType for TlsAcceptorTypeImpl<A> {
 
 // Builder
 
-trait TlsAcceptorBuilderDyn: Send + 'static {
+pub(crate) trait TlsAcceptorBuilderDyn: Send + 'static {
     fn type_dyn(&self) -> &'static dyn TlsAcceptorType;
 
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
 
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()>;
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()>;
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()>;
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()>;
+
     fn build(self: Box<Self>) -> anyhow::Result<TlsAcceptorBox>;
 }
 
@@ -126,13 +121,37 @@ impl<A: TlsAcceptorBuilder> TlsAcceptorBuilderDyn for A {
         (*self).set_alpn_protocols(protocols)
     }
 
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        (*self).add_client_auth_root_certificate(cert)
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        (*self).add_crl_der(crl)
+    }
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        (*self).add_crl_pem(crl)
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        (*self).set_revocation_policy(policy)
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        (*self).set_ocsp_response(ocsp_response)
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        (*self).set_key_log(key_log)
+    }
+
     fn build(self: Box<Self>) -> anyhow::Result<TlsAcceptorBox> {
         Ok(TlsAcceptorBox(Box::new((*self).build()?)))
     }
 }
 
 /// Dynamic version of [`TlsAcceptorBuilder`].
-pub struct TlsAcceptorBuilderBox(Box<dyn TlsAcceptorBuilderDyn>);
+pub struct TlsAcceptorBuilderBox(pub(crate) Box<dyn TlsAcceptorBuilderDyn>);
 
 impl TlsAcceptorBuilderBox {
     /// Dynamic (without type parameter) version of the acceptor.
@@ -146,11 +165,51 @@ impl TlsAcceptorBuilderBox {
     ///
     /// This operation returns an error if the implemenation does not support ALPN.
     ///
-    /// Whether ALPN is supported, can be queried using [`TlsAcceptor::SUPPORTS_ALPN`].
+    /// Whether ALPN is supported, can be queried using [`Capabilities::alpn`].
     pub fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
         self.0.set_alpn_protocols(protocols)
     }
 
+    /// Require clients to present a certificate, and verify it using given root certificate.
+    ///
+    /// This operation returns an error if not [`Capabilities::client_auth`].
+    pub fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        self.0.add_client_auth_root_certificate(cert)
+    }
+
+    /// Add DER-encoded certificate revocation list.
+    ///
+    /// This operation returns an error if not [`Capabilities::crl`].
+    pub fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.0.add_crl_der(crl)
+    }
+
+    /// Add certificate revocation lists from a PEM file.
+    ///
+    /// This operation returns an error if not [`Capabilities::crl`].
+    pub fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.0.add_crl_pem(crl)
+    }
+
+    /// How added CRLs are checked.
+    pub fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.0.set_revocation_policy(policy)
+    }
+
+    /// DER-encoded OCSP response stapled to the server certificate.
+    ///
+    /// This operation returns an error if not [`Capabilities::ocsp_stapling`].
+    pub fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.0.set_ocsp_response(ocsp_response)
+    }
+
+    /// Report TLS secrets of accepted connections to given [`KeyLog`].
+    ///
+    /// This operation returns an error if not [`Capabilities::key_log`].
+    pub fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.0.set_key_log(key_log)
+    }
+
     /// Finish the acceptor construction.
     pub fn build(self) -> anyhow::Result<TlsAcceptorBox> {
         self.0.build()
@@ -159,10 +218,16 @@ impl TlsAcceptorBuilderBox {
 
 // Acceptor
 
-trait TlsAcceptorDyn: Send + Sync + 'static {
+pub(crate) trait TlsAcceptorDyn: Send + Sync + 'static {
     fn type_dyn(&self) -> &'static dyn TlsAcceptorType;
 
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()>;
+
     fn accept<'a>(&'a self, socket: AsyncSocketBox) -> BoxFuture<'a, anyhow::Result<TlsStream>>;
+
+    fn accept_blocking(&self, socket: TcpStream) -> anyhow::Result<TlsStreamBlocking>;
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection>;
 }
 
 impl<A: TlsAcceptor> TlsAcceptorDyn for A {
@@ -170,9 +235,21 @@ impl<A: TlsAcceptor> TlsAcceptorDyn for A {
         A::TYPE_DYN
     }
 
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        (*self).set_ocsp_response(ocsp_response)
+    }
+
     fn accept<'a>(&'a self, socket: AsyncSocketBox) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
         self.accept(socket)
     }
+
+    fn accept_blocking(&self, socket: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.accept_blocking(socket)
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.new_connection()
+    }
 }
 
 /// Dynamic version of [`TlsAcceptor`].
@@ -180,7 +257,7 @@ impl<A: TlsAcceptor> TlsAcceptorDyn for A {
 /// This can be constructed either with:
 /// * [`TlsAcceptor::into_dyn`]
 /// * [`TlsAcceptorBuilderBox::build`]
-pub struct TlsAcceptorBox(Box<dyn TlsAcceptorDyn>);
+pub struct TlsAcceptorBox(pub(crate) Box<dyn TlsAcceptorDyn>);
 
 impl TlsAcceptorBox {
     pub(crate) fn new<A: TlsAcceptor>(acceptor: A) -> TlsAcceptorBox {
@@ -194,6 +271,13 @@ impl TlsAcceptorBox {
         self.0.type_dyn()
     }
 
+    /// Replace DER-encoded OCSP response stapled to the server certificate.
+    ///
+    /// This operation returns an error if not [`Capabilities::ocsp_stapling`].
+    pub fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.0.set_ocsp_response(ocsp_response)
+    }
+
     /// Accept a connection.
     ///
     /// This operation returns a future which is resolved when the negotiation is complete,
@@ -204,6 +288,20 @@ impl TlsAcceptorBox {
     ) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
         self.0.accept(AsyncSocketBox::new(socket))
     }
+
+    /// Accept a connection over a blocking socket.
+    ///
+    /// This operation returns an error if not [`Capabilities::blocking`].
+    pub fn accept_blocking(&self, socket: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.0.accept_blocking(socket)
+    }
+
+    /// Create a sans-IO server connection.
+    ///
+    /// This operation returns an error if not [`Capabilities::connection`].
+    pub fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.0.new_connection()
+    }
 }
 
 fn _assert_kinds() {
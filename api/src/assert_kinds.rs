@@ -8,3 +8,12 @@ pub(crate) fn assert_socket<T: AsyncSocket>() {}
 pub(crate) fn assert_send_value<T: Send>(t: T) -> T {
     t
 }
+#[cfg(feature = "runtime-tokio")]
+pub(crate) fn assert_tokio_io<
+    T: crate::runtime::tokio::AsyncRead + crate::runtime::tokio::AsyncWrite,
+>() {
+}
+pub(crate) fn assert_futures_io<
+    T: crate::runtime::futures::AsyncRead + crate::runtime::futures::AsyncWrite,
+>() {
+}
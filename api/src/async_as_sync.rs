@@ -8,15 +8,19 @@ use std::io::Read;
 use std::io::Write;
 use std::marker::PhantomData;
 use std::pin::Pin;
+use std::task::ready;
 use std::task::Context;
 use std::task::Poll;
 
-use crate::runtime::AsyncRead;
-use crate::runtime::AsyncWrite;
+use crate::channel_binding::TLS_EXPORTER_LABEL;
+use crate::channel_binding::TLS_EXPORTER_LEN;
 use crate::spi::restore_context;
 use crate::spi::save_context;
+use crate::spi::AsyncIo;
 use crate::spi::TlsStreamWithUpcastDyn;
+use crate::spi_async_socket_impl;
 use crate::AsyncSocket;
+use crate::ChannelBindingType;
 use crate::ImplInfo;
 use crate::TlsStreamDyn;
 use crate::TlsStreamWithSocketDyn;
@@ -29,8 +33,6 @@ pub struct AsyncIoAsSyncIo<S: Unpin> {
     inner: S,
 }
 
-unsafe impl<S: Unpin + Send> Send for AsyncIoAsSyncIo<S> {}
-
 impl<S: Unpin> AsyncIoAsSyncIo<S> {
     /// Get a mutable reference to a wrapped stream
     pub fn get_inner_mut(&mut self) -> &mut S {
@@ -52,30 +54,19 @@ impl<S: Unpin> AsyncIoAsSyncIo<S> {
     }
 }
 
-impl<S: AsyncRead + Unpin> Read for AsyncIoAsSyncIo<S> {
+impl<S: AsyncSocket> Read for AsyncIoAsSyncIo<S> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        restore_context_poll_to_result(|cx| {
-            #[cfg(feature = "runtime-tokio")]
-            {
-                let mut read_buf = tokio::io::ReadBuf::new(buf);
-                let p = self.get_inner_pin().poll_read(cx, &mut read_buf);
-                p.map_ok(|()| read_buf.filled().len())
-            }
-            #[cfg(feature = "runtime-async-std")]
-            {
-                self.get_inner_pin().poll_read(cx, buf)
-            }
-        })
+        restore_context_poll_to_result(|cx| self.get_inner_pin().poll_socket_read(cx, buf))
     }
 }
 
-impl<S: AsyncWrite + Unpin> Write for AsyncIoAsSyncIo<S> {
+impl<S: AsyncSocket> Write for AsyncIoAsSyncIo<S> {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        restore_context_poll_to_result(|cx| self.get_inner_pin().poll_write(cx, buf))
+        restore_context_poll_to_result(|cx| self.get_inner_pin().poll_socket_write(cx, buf))
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        restore_context_poll_to_result(|cx| self.get_inner_pin().poll_flush(cx))
+        restore_context_poll_to_result(|cx| self.get_inner_pin().poll_socket_flush(cx))
     }
 }
 
@@ -105,6 +96,7 @@ fn poll_to_result<T>(r: Poll<io::Result<T>>) -> io::Result<T> {
     }
 }
 
+/// Outside of poll (no saved context) this returns `WouldBlock`.
 fn restore_context_poll_to_result<R>(
     f: impl FnOnce(&mut Context<'_>) -> Poll<io::Result<R>>,
 ) -> io::Result<R> {
@@ -135,6 +127,26 @@ where
 
     /// Get negotiated ALPN protocol.
     fn get_alpn_protocol(w: &Self::SyncWrapper) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Get server name indication.
+    fn server_name(w: &Self::SyncWrapper) -> anyhow::Result<Option<String>>;
+
+    /// Get OCSP response stapled by the server.
+    fn ocsp_response(w: &Self::SyncWrapper) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Export keying material.
+    fn export_keying_material(
+        w: &Self::SyncWrapper,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Hash of the server certificate for `tls-server-end-point` channel binding.
+    fn tls_server_end_point(w: &Self::SyncWrapper) -> anyhow::Result<Vec<u8>>;
+
+    /// Send `close_notify`.
+    fn shutdown(w: &mut Self::SyncWrapper) -> io::Result<()>;
 }
 
 /// Implementation of `TlsStreamImpl` for APIs using synchronous I/O.
@@ -145,6 +157,7 @@ where
 {
     /// TLS-implementation.
     pub stream: O::SyncWrapper,
+    close_notify_sent: bool,
     _phantom: PhantomData<(A, O)>,
 }
 
@@ -169,6 +182,7 @@ where
     pub fn new(stream: O::SyncWrapper) -> TlsStreamOverSyncIo<A, O> {
         TlsStreamOverSyncIo {
             stream,
+            close_notify_sent: false,
             _phantom: PhantomData,
         }
     }
@@ -183,52 +197,13 @@ where
     {
         result_to_poll(save_context(cx, || f(self)))
     }
-
-    #[cfg(feature = "runtime-tokio")]
-    fn with_context_sync_to_async_tokio<F>(
-        &mut self,
-        cx: &mut Context<'_>,
-        buf: &mut tokio::io::ReadBuf,
-        f: F,
-    ) -> Poll<io::Result<()>>
-    where
-        F: FnOnce(&mut Self, &mut [u8]) -> io::Result<usize>,
-    {
-        self.with_context_sync_to_async(cx, |s| {
-            let unfilled = buf.initialize_unfilled();
-            let read = f(s, unfilled)?;
-            buf.advance(read);
-            Ok(())
-        })
-    }
 }
 
-impl<A, O> AsyncRead for TlsStreamOverSyncIo<A, O>
+impl<A, O> AsyncIo for TlsStreamOverSyncIo<A, O>
 where
     A: Unpin,
     O: AsyncWrapperOps<A>,
 {
-    #[cfg(feature = "runtime-tokio")]
-    fn poll_read(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut tokio::io::ReadBuf,
-    ) -> Poll<io::Result<()>> {
-        self.get_mut()
-            .with_context_sync_to_async_tokio(cx, buf, |s, buf| {
-                let result = s.stream.read(buf);
-                match result {
-                    Ok(r) => Ok(r),
-                    Err(e) if e.kind() == io::ErrorKind::ConnectionAborted => {
-                        // rustls returns `ConnectionAborted` on EOF
-                        Ok(0)
-                    }
-                    Err(e) => Err(e),
-                }
-            })
-    }
-
-    #[cfg(feature = "runtime-async-std")]
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -246,13 +221,7 @@ where
             }
         })
     }
-}
 
-impl<A, O> AsyncWrite for TlsStreamOverSyncIo<A, O>
-where
-    A: Unpin,
-    O: AsyncWrapperOps<A>,
-{
     fn poll_write(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -267,19 +236,19 @@ where
             .with_context_sync_to_async(cx, |stream| stream.stream.flush())
     }
 
-    #[cfg(feature = "runtime-tokio")]
     fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.get_mut()
-            .with_context_sync_to_async(cx, |stream| stream.stream.flush())
-    }
-
-    #[cfg(feature = "runtime-async-std")]
-    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.get_mut()
-            .with_context_sync_to_async(cx, |stream| stream.stream.flush())
+        let self_mut = self.get_mut();
+        // Shutdown is not repeated: second call may wait for peer `close_notify`
+        if !self_mut.close_notify_sent {
+            ready!(self_mut.with_context_sync_to_async(cx, |s| O::shutdown(&mut s.stream)))?;
+            self_mut.close_notify_sent = true;
+        }
+        self_mut.with_context_sync_to_async(cx, |stream| stream.stream.flush())
     }
 }
 
+spi_async_socket_impl!([A: AsyncSocket, O: AsyncWrapperOps<A>] TlsStreamOverSyncIo<A, O>);
+
 impl<A, O> TlsStreamDyn for TlsStreamOverSyncIo<A, O>
 where
     A: AsyncSocket,
@@ -293,6 +262,35 @@ where
         O::get_alpn_protocol(&self.stream)
     }
 
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        O::server_name(&self.stream)
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        O::ocsp_response(&self.stream)
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        O::export_keying_material(&self.stream, label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => O::tls_server_end_point(&self.stream),
+            ChannelBindingType::TlsExporter => O::export_keying_material(
+                &self.stream,
+                TLS_EXPORTER_LABEL,
+                Some(&[]),
+                TLS_EXPORTER_LEN,
+            ),
+        }
+    }
+
     fn get_socket_dyn_mut(&mut self) -> &mut dyn AsyncSocket {
         O::get_mut(&mut self.stream).get_inner_mut()
     }
@@ -362,6 +360,30 @@ macro_rules! spi_tls_stream_over_sync_io_wrapper {
                 self.0.get_alpn_protocol()
             }
 
+            fn server_name(&self) -> anyhow::Result<Option<String>> {
+                self.0.server_name()
+            }
+
+            fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+                self.0.ocsp_response()
+            }
+
+            fn export_keying_material(
+                &self,
+                label: &str,
+                context: Option<&[u8]>,
+                len: usize,
+            ) -> anyhow::Result<Vec<u8>> {
+                self.0.export_keying_material(label, context, len)
+            }
+
+            fn channel_binding(
+                &self,
+                kind: tls_api::ChannelBindingType,
+            ) -> anyhow::Result<Vec<u8>> {
+                self.0.channel_binding(kind)
+            }
+
             fn impl_info(&self) -> ImplInfo {
                 self.0.impl_info()
             }
@@ -392,3 +414,94 @@ macro_rules! spi_tls_stream_over_sync_io_wrapper {
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use std::io;
+    use std::io::Read;
+    use std::io::Write;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::task::Wake;
+    use std::task::Waker;
+
+    use super::AsyncIoAsSyncIo;
+    use crate::spi::save_context;
+    use crate::spi::AsyncIo;
+    use crate::spi_async_socket_impl;
+
+    /// Socket reading from a slice and writing into a vec.
+    #[derive(Debug, Default)]
+    struct MemSocket {
+        read: &'static [u8],
+        written: Vec<u8>,
+    }
+
+    impl AsyncIo for MemSocket {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(self.get_mut().read.read(buf))
+        }
+
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            Poll::Ready(self.get_mut().written.write(buf))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    spi_async_socket_impl!(MemSocket);
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn with_context<R>(f: impl FnOnce() -> R) -> R {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        save_context(&Context::from_waker(&waker), f)
+    }
+
+    #[test]
+    fn read_outside_of_poll() {
+        let mut socket = AsyncIoAsSyncIo::new(MemSocket {
+            read: b"abc",
+            ..MemSocket::default()
+        });
+        let mut buf = [0; 3];
+        let err = socket.read(&mut buf).unwrap_err();
+        assert_eq!(io::ErrorKind::WouldBlock, err.kind());
+        // Nothing is consumed
+        assert_eq!(3, with_context(|| socket.read(&mut buf)).unwrap());
+        assert_eq!(b"abc", &buf);
+    }
+
+    #[test]
+    fn write_outside_of_poll() {
+        let mut socket = AsyncIoAsSyncIo::new(MemSocket::default());
+        let err = socket.write(b"abc").unwrap_err();
+        assert_eq!(io::ErrorKind::WouldBlock, err.kind());
+        assert_eq!(
+            io::ErrorKind::WouldBlock,
+            socket.flush().unwrap_err().kind()
+        );
+        assert_eq!(3, with_context(|| socket.write(b"abc")).unwrap());
+        assert_eq!(b"abc", &socket.get_inner_ref().written[..]);
+    }
+}
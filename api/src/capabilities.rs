@@ -0,0 +1,189 @@
+use std::fmt;
+
+use crate::assert_send;
+use crate::assert_sync;
+
+/// Version of TLS or DTLS protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    /// TLS 1.0.
+    Tls10,
+    /// TLS 1.1.
+    Tls11,
+    /// TLS 1.2.
+    Tls12,
+    /// TLS 1.3.
+    Tls13,
+    /// DTLS 1.0.
+    Dtls10,
+    /// DTLS 1.2.
+    Dtls12,
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ProtocolVersion::Tls10 => "TLSv1.0",
+            ProtocolVersion::Tls11 => "TLSv1.1",
+            ProtocolVersion::Tls12 => "TLSv1.2",
+            ProtocolVersion::Tls13 => "TLSv1.3",
+            ProtocolVersion::Dtls10 => "DTLSv1.0",
+            ProtocolVersion::Dtls12 => "DTLSv1.2",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Features supported by a connector or an acceptor implementation.
+///
+/// Available statically as e. g. [`TlsConnector::CAPABILITIES`](crate::TlsConnector::CAPABILITIES)
+/// and dynamically as e. g. [`TlsConnectorType::capabilities`](crate::TlsConnectorType::capabilities).
+///
+/// Implementations list supported features and fill the rest from [`Capabilities::NONE`],
+/// so features added later are unsupported by default:
+///
+/// ```
+/// # use tls_api::Capabilities;
+/// const CAPABILITIES: Capabilities = Capabilities {
+///     implemented: true,
+///     alpn: true,
+///     ..Capabilities::NONE
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Is it implemented? When `false` all operations return an error.
+    ///
+    /// At the moment of writing, there are two crates which return `false` here:
+    /// * `tls-api-stub`, dummy implementation is not meant to be instantiated
+    /// * `tls-api-security-framework`, `true` only on macOS and iOS, `false` elsewhere
+    pub implemented: bool,
+    /// ALPN negotiation.
+    pub alpn: bool,
+    /// Construction of acceptor from a pair of DER certificate and key.
+    pub der_keys: bool,
+    /// Construction of acceptor from PKCS #12 file.
+    pub pkcs12_keys: bool,
+    /// Presenting a client certificate (connector)
+    /// or verifying client certificate (acceptor).
+    pub client_auth: bool,
+    /// Checking certificate revocation lists of the peer certificate chain.
+    pub crl: bool,
+    /// Requesting (connector) or stapling (acceptor) OCSP response.
+    pub ocsp_stapling: bool,
+    /// Reporting TLS secrets to [`KeyLog`](crate::KeyLog).
+    pub key_log: bool,
+    /// Blocking `connect_blocking` and `accept_blocking` operations.
+    pub blocking: bool,
+    /// Sans-IO [`TlsConnection`](crate::TlsConnection).
+    pub connection: bool,
+    /// Protocol versions enabled by default, from oldest to newest.
+    pub protocol_versions: &'static [ProtocolVersion],
+    /// Exporting keying material (RFC 5705) with
+    /// [`export_keying_material`](crate::TlsStreamDyn::export_keying_material)
+    /// and `tls-exporter` channel binding.
+    pub keying_material_exporter: bool,
+    /// Sending and accepting TLS 1.3 early data (0-RTT).
+    pub early_data: bool,
+}
+
+fn _assert_kinds() {
+    assert_send::<Capabilities>();
+    assert_sync::<Capabilities>();
+}
+
+impl Capabilities {
+    /// Nothing is supported, the implementation is not implemented.
+    pub const NONE: Capabilities = Capabilities {
+        implemented: false,
+        alpn: false,
+        der_keys: false,
+        pkcs12_keys: false,
+        client_auth: false,
+        crl: false,
+        ocsp_stapling: false,
+        key_log: false,
+        blocking: false,
+        connection: false,
+        protocol_versions: &[],
+        keying_material_exporter: false,
+        early_data: false,
+    };
+
+    /// Whether given protocol version is enabled by default.
+    pub fn supports_protocol_version(&self, version: ProtocolVersion) -> bool {
+        self.protocol_versions.contains(&version)
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Capabilities {
+        Capabilities::NONE
+    }
+}
+
+impl fmt::Display for Capabilities {
+    /// Comma-separated supported features and protocol versions.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.implemented {
+            return write!(f, "not implemented");
+        }
+        let features = [
+            (self.alpn, "alpn"),
+            (self.der_keys, "der-keys"),
+            (self.pkcs12_keys, "pkcs12-keys"),
+            (self.client_auth, "client-auth"),
+            (self.crl, "crl"),
+            (self.ocsp_stapling, "ocsp-stapling"),
+            (self.key_log, "key-log"),
+            (self.blocking, "blocking"),
+            (self.connection, "connection"),
+            (self.keying_material_exporter, "exporter"),
+            (self.early_data, "early-data"),
+        ];
+        let mut items: Vec<String> = self
+            .protocol_versions
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        items.extend(
+            features
+                .iter()
+                .filter(|(supported, _)| *supported)
+                .map(|(_, name)| (*name).to_owned()),
+        );
+        if items.is_empty() {
+            write!(f, "-")
+        } else {
+            write!(f, "{}", items.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("not implemented", Capabilities::NONE.to_string());
+        let capabilities = Capabilities {
+            implemented: true,
+            alpn: true,
+            key_log: true,
+            protocol_versions: &[ProtocolVersion::Tls12, ProtocolVersion::Tls13],
+            ..Capabilities::NONE
+        };
+        assert_eq!("TLSv1.2, TLSv1.3, alpn, key-log", capabilities.to_string());
+        assert!(capabilities.supports_protocol_version(ProtocolVersion::Tls13));
+        assert!(!capabilities.supports_protocol_version(ProtocolVersion::Tls10));
+        assert_eq!(
+            "-",
+            Capabilities {
+                implemented: true,
+                ..Capabilities::NONE
+            }
+            .to_string()
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// Channel binding types.
+///
+/// See [`TlsStreamDyn::channel_binding`](crate::TlsStreamDyn::channel_binding).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ChannelBindingType {
+    /// `tls-server-end-point` ([RFC 5929](https://www.rfc-editor.org/rfc/rfc5929)):
+    /// hash of the server certificate.
+    TlsServerEndPoint,
+    /// `tls-exporter` ([RFC 9266](https://www.rfc-editor.org/rfc/rfc9266)):
+    /// keying material exported with `EXPORTER-Channel-Binding` label.
+    ///
+    /// Should only be used with TLS 1.3, or TLS 1.2 with extended master secret.
+    TlsExporter,
+}
+
+impl ChannelBindingType {
+    /// Name of channel binding type as registered with IANA.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChannelBindingType::TlsServerEndPoint => "tls-server-end-point",
+            ChannelBindingType::TlsExporter => "tls-exporter",
+        }
+    }
+}
+
+impl fmt::Display for ChannelBindingType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// `tls-exporter` label.
+pub const TLS_EXPORTER_LABEL: &str = "EXPORTER-Channel-Binding";
+/// `tls-exporter` length.
+pub const TLS_EXPORTER_LEN: usize = 32;
+
+/// Error returned when keying material or channel binding cannot be provided.
+///
+/// Other errors (e. g. I/O errors of the implementation) are returned as is.
+#[derive(Debug, thiserror::Error)]
+pub enum ChannelBindingError {
+    /// Implementation cannot export keying material.
+    #[error("{} does not support exporting keying material", _0)]
+    ExportNotSupported(&'static str),
+    /// Implementation cannot provide given channel binding.
+    #[error("{} does not support {} channel binding", _1, _0)]
+    NotSupported(ChannelBindingType, &'static str),
+    /// Server certificate is not available, e. g. handshake is not complete.
+    #[error("server certificate is not available")]
+    NoServerCertificate,
+    /// `tls-server-end-point` is undefined for the server certificate signature algorithm.
+    #[error(
+        "tls-server-end-point is not defined for certificate signature algorithm {}",
+        _0
+    )]
+    UnsupportedSignatureAlgorithm(String),
+}
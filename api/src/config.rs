@@ -0,0 +1,529 @@
+//! Connector and acceptor configuration loadable with serde.
+
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use crate::Capabilities;
+use crate::CommonError;
+use crate::ImplInfo;
+use crate::KeyLogFile;
+use crate::OcspStapling;
+use crate::RevocationPolicy;
+use crate::TlsAcceptorBox;
+use crate::TlsAcceptorBuilderBox;
+use crate::TlsAcceptorType;
+use crate::TlsConnectorBox;
+use crate::TlsConnectorBuilderBox;
+use crate::TlsConnectorType;
+
+/// Client certificate file and its private key file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CertificateKeyConfig {
+    /// Certificate file, PEM or DER.
+    pub certificate: PathBuf,
+    /// Private key file, PEM or DER.
+    pub key: PathBuf,
+}
+
+/// Declarative configuration of a connector.
+///
+/// Fields correspond to [`TlsConnectorBuilderBox`] operations.
+/// Certificates, keys and CRLs are read from files, which can be PEM or DER.
+/// Omitted fields keep builder defaults:
+///
+/// ```toml
+/// alpn_protocols = ["h2", "http/1.1"]
+/// root_certificates = ["/etc/service/ca.pem"]
+/// client_certificate = { certificate = "client.pem", key = "client.key" }
+/// ocsp_stapling = "request"
+/// ```
+///
+/// Configuration is validated against the implementation [`Capabilities`],
+/// and errors name the offending field.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsConnectorConfig {
+    /// ALPN protocols to negotiate.
+    pub alpn_protocols: Vec<String>,
+    /// Should hostname verification be performed, `true` by default.
+    pub verify_hostname: bool,
+    /// Trusted root certificate files, in addition to the global trusted roots.
+    pub root_certificates: Vec<PathBuf>,
+    /// Certificate revocation list files.
+    pub crls: Vec<PathBuf>,
+    /// How CRLs are checked.
+    pub revocation_policy: Option<RevocationPolicy>,
+    /// Certificate presented when server requests client authentication.
+    pub client_certificate: Option<CertificateKeyConfig>,
+    /// Whether to request stapled OCSP response.
+    pub ocsp_stapling: OcspStapling,
+    /// File to append TLS secrets to, in `SSLKEYLOGFILE` format.
+    pub key_log_file: Option<PathBuf>,
+}
+
+impl Default for TlsConnectorConfig {
+    fn default() -> TlsConnectorConfig {
+        TlsConnectorConfig {
+            alpn_protocols: Vec::new(),
+            verify_hostname: true,
+            root_certificates: Vec::new(),
+            crls: Vec::new(),
+            revocation_policy: None,
+            client_certificate: None,
+            ocsp_stapling: OcspStapling::Disabled,
+            key_log_file: None,
+        }
+    }
+}
+
+/// Declarative configuration of an acceptor.
+///
+/// Server key is either `certificate` and `key` files, or `pkcs12` file.
+/// Other fields correspond to [`TlsAcceptorBuilderBox`] operations:
+///
+/// ```toml
+/// certificate = "/etc/service/server.pem"
+/// key = "/etc/service/server.key"
+/// alpn_protocols = ["h2"]
+/// client_auth_root_certificates = ["/etc/service/clients-ca.pem"]
+/// ```
+///
+/// Configuration is validated against the implementation [`Capabilities`],
+/// and errors name the offending field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TlsAcceptorConfig {
+    /// Server certificate file, PEM or DER.
+    pub certificate: Option<PathBuf>,
+    /// Server private key file, PEM or DER.
+    pub key: Option<PathBuf>,
+    /// Server certificate and key in PKCS #12 file.
+    pub pkcs12: Option<PathBuf>,
+    /// Passphrase of `pkcs12` file.
+    pub pkcs12_passphrase: String,
+    /// ALPN protocols to negotiate.
+    pub alpn_protocols: Vec<String>,
+    /// Require client certificate issued by one of these root certificates.
+    pub client_auth_root_certificates: Vec<PathBuf>,
+    /// Certificate revocation list files to check client certificates.
+    pub crls: Vec<PathBuf>,
+    /// How CRLs are checked.
+    pub revocation_policy: Option<RevocationPolicy>,
+    /// DER-encoded OCSP response file to staple.
+    pub ocsp_response: Option<PathBuf>,
+    /// File to append TLS secrets to, in `SSLKEYLOGFILE` format.
+    pub key_log_file: Option<PathBuf>,
+}
+
+/// Fail if the field is set, but the feature is not supported.
+fn require(
+    set: bool,
+    supported: bool,
+    field: &'static str,
+    feature: &'static str,
+    info: &ImplInfo,
+) -> anyhow::Result<()> {
+    if set && !supported {
+        Err(CommonError::ConfigUnsupported(field, info.name, feature).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn read(path: &Path) -> anyhow::Result<Vec<u8>> {
+    fs::read(path).with_context(|| format!("reading {}", path.display()))
+}
+
+fn is_pem(data: &[u8]) -> bool {
+    data.starts_with(b"-----BEGIN")
+}
+
+/// DER-encoded certificates from a PEM or DER file.
+fn read_certificates(path: &Path) -> anyhow::Result<Vec<Vec<u8>>> {
+    let data = read(path)?;
+    if !is_pem(&data) {
+        return Ok(vec![data]);
+    }
+    let certs: Vec<Vec<u8>> = pem::parse_many(&data)
+        .into_iter()
+        .filter(|p| p.tag == "CERTIFICATE")
+        .map(|p| p.contents)
+        .collect();
+    if certs.is_empty() {
+        Err(CommonError::NoCertificatesInPem.into())
+    } else {
+        Ok(certs)
+    }
+}
+
+/// Single DER-encoded certificate from a PEM or DER file.
+fn read_certificate(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let mut certs = read_certificates(path)?;
+    match certs.len() {
+        1 => Ok(certs.remove(0)),
+        n => Err(CommonError::PemNotSingleCertificate(n).into()),
+    }
+}
+
+/// DER-encoded private key from a PEM or DER file.
+fn read_key(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let data = read(path)?;
+    if !is_pem(&data) {
+        return Ok(data);
+    }
+    pem::parse_many(&data)
+        .into_iter()
+        .find(|p| p.tag.ends_with("PRIVATE KEY"))
+        .map(|p| p.contents)
+        .ok_or_else(|| CommonError::NoPrivateKeyInPem.into())
+}
+
+fn key_log_file(path: &Path) -> anyhow::Result<Arc<KeyLogFile>> {
+    let file = KeyLogFile::open(path).with_context(|| format!("opening {}", path.display()))?;
+    Ok(Arc::new(file))
+}
+
+fn alpn_protocols(protocols: &[String]) -> Vec<&[u8]> {
+    protocols.iter().map(|p| p.as_bytes()).collect()
+}
+
+/// Error context naming the config field.
+fn field(name: &str) -> String {
+    format!("config field `{}`", name)
+}
+
+fn field_index(name: &str, index: usize) -> String {
+    format!("config field `{}[{}]`", name, index)
+}
+
+impl TlsConnectorConfig {
+    /// Check that the implementation supports all configured features.
+    ///
+    /// Files are not read.
+    pub fn validate(&self, connector: &dyn TlsConnectorType) -> anyhow::Result<()> {
+        let c: Capabilities = connector.capabilities();
+        let info = connector.info();
+        require(
+            !self.alpn_protocols.is_empty(),
+            c.alpn,
+            "alpn_protocols",
+            "ALPN",
+            &info,
+        )?;
+        require(!self.crls.is_empty(), c.crl, "crls", "CRL", &info)?;
+        require(
+            self.revocation_policy.is_some(),
+            c.crl,
+            "revocation_policy",
+            "CRL",
+            &info,
+        )?;
+        require(
+            self.client_certificate.is_some(),
+            c.client_auth,
+            "client_certificate",
+            "client authentication",
+            &info,
+        )?;
+        require(
+            self.ocsp_stapling != OcspStapling::Disabled,
+            c.ocsp_stapling,
+            "ocsp_stapling",
+            "OCSP stapling",
+            &info,
+        )?;
+        require(
+            self.key_log_file.is_some(),
+            c.key_log,
+            "key_log_file",
+            "key log",
+            &info,
+        )?;
+        Ok(())
+    }
+
+    /// Validate the configuration, and create a configured builder.
+    ///
+    /// Builder can be used to set options not available in configuration.
+    pub fn builder(
+        &self,
+        connector: &dyn TlsConnectorType,
+    ) -> anyhow::Result<TlsConnectorBuilderBox> {
+        self.validate(connector)?;
+
+        let mut builder = connector.builder()?;
+        if !self.alpn_protocols.is_empty() {
+            builder
+                .set_alpn_protocols(&alpn_protocols(&self.alpn_protocols))
+                .with_context(|| field("alpn_protocols"))?;
+        }
+        if !self.verify_hostname {
+            builder
+                .set_verify_hostname(false)
+                .with_context(|| field("verify_hostname"))?;
+        }
+        for (i, path) in self.root_certificates.iter().enumerate() {
+            (|| {
+                for cert in read_certificates(path)? {
+                    builder.add_root_certificate(&cert)?;
+                }
+                Ok::<_, anyhow::Error>(())
+            })()
+            .with_context(|| field_index("root_certificates", i))?;
+        }
+        for (i, path) in self.crls.iter().enumerate() {
+            (|| {
+                let crl = read(path)?;
+                if is_pem(&crl) {
+                    builder.add_crl_pem(&crl)
+                } else {
+                    builder.add_crl_der(&crl)
+                }
+            })()
+            .with_context(|| field_index("crls", i))?;
+        }
+        if let Some(policy) = self.revocation_policy {
+            builder
+                .set_revocation_policy(policy)
+                .with_context(|| field("revocation_policy"))?;
+        }
+        if let Some(client) = &self.client_certificate {
+            let cert = read_certificate(&client.certificate)
+                .with_context(|| field("client_certificate.certificate"))?;
+            let key = read_key(&client.key).with_context(|| field("client_certificate.key"))?;
+            builder
+                .set_client_certificate(&cert, &key)
+                .with_context(|| field("client_certificate"))?;
+        }
+        if self.ocsp_stapling != OcspStapling::Disabled {
+            builder
+                .set_ocsp_stapling(self.ocsp_stapling)
+                .with_context(|| field("ocsp_stapling"))?;
+        }
+        if let Some(path) = &self.key_log_file {
+            (|| builder.set_key_log(key_log_file(path)?))()
+                .with_context(|| field("key_log_file"))?;
+        }
+        Ok(builder)
+    }
+
+    /// Validate the configuration, and build a connector.
+    pub fn build(&self, connector: &dyn TlsConnectorType) -> anyhow::Result<TlsConnectorBox> {
+        self.builder(connector)?.build()
+    }
+}
+
+impl TlsAcceptorConfig {
+    /// Check that the server key is specified,
+    /// and the implementation supports all configured features.
+    ///
+    /// Files are not read.
+    pub fn validate(&self, acceptor: &dyn TlsAcceptorType) -> anyhow::Result<()> {
+        match (&self.certificate, &self.key, &self.pkcs12) {
+            (Some(_), Some(_), None) | (None, None, Some(_)) => {}
+            (Some(_), None, None) => {
+                return Err(CommonError::ConfigMissing("key", "certificate").into())
+            }
+            (None, Some(_), None) => {
+                return Err(CommonError::ConfigMissing("certificate", "key").into())
+            }
+            (None, None, None) => return Err(CommonError::ConfigNoServerKey.into()),
+            (certificate, _, Some(_)) => {
+                let other = match certificate {
+                    Some(_) => "certificate",
+                    None => "key",
+                };
+                return Err(CommonError::ConfigConflict("pkcs12", other).into());
+            }
+        }
+
+        let c: Capabilities = acceptor.capabilities();
+        let info = acceptor.info();
+        // Keys are converted with `openssl` command when only other format is supported
+        require(
+            true,
+            c.der_keys || c.pkcs12_keys,
+            match self.pkcs12 {
+                Some(_) => "pkcs12",
+                None => "certificate",
+            },
+            "DER or PKCS #12 keys",
+            &info,
+        )?;
+        require(
+            !self.alpn_protocols.is_empty(),
+            c.alpn,
+            "alpn_protocols",
+            "ALPN",
+            &info,
+        )?;
+        require(
+            !self.client_auth_root_certificates.is_empty(),
+            c.client_auth,
+            "client_auth_root_certificates",
+            "client authentication",
+            &info,
+        )?;
+        require(!self.crls.is_empty(), c.crl, "crls", "CRL", &info)?;
+        require(
+            self.revocation_policy.is_some(),
+            c.crl,
+            "revocation_policy",
+            "CRL",
+            &info,
+        )?;
+        require(
+            self.ocsp_response.is_some(),
+            c.ocsp_stapling,
+            "ocsp_response",
+            "OCSP stapling",
+            &info,
+        )?;
+        require(
+            self.key_log_file.is_some(),
+            c.key_log,
+            "key_log_file",
+            "key log",
+            &info,
+        )?;
+        Ok(())
+    }
+
+    fn new_builder(&self, acceptor: &dyn TlsAcceptorType) -> anyhow::Result<TlsAcceptorBuilderBox> {
+        match (&self.certificate, &self.key, &self.pkcs12) {
+            (Some(certificate), Some(key), _) => {
+                let cert = read_certificate(certificate).with_context(|| field("certificate"))?;
+                let key = read_key(key).with_context(|| field("key"))?;
+                acceptor
+                    .builder_from_der_key(&cert, &key)
+                    .with_context(|| field("certificate"))
+            }
+            (_, _, Some(pkcs12)) => {
+                let data = read(pkcs12).with_context(|| field("pkcs12"))?;
+                acceptor
+                    .builder_from_pkcs12(&data, &self.pkcs12_passphrase)
+                    .with_context(|| field("pkcs12"))
+            }
+            _ => unreachable!("checked in validate"),
+        }
+    }
+
+    /// Validate the configuration, and create a configured builder.
+    ///
+    /// Builder can be used to set options not available in configuration.
+    pub fn builder(&self, acceptor: &dyn TlsAcceptorType) -> anyhow::Result<TlsAcceptorBuilderBox> {
+        self.validate(acceptor)?;
+
+        let mut builder = self.new_builder(acceptor)?;
+        if !self.alpn_protocols.is_empty() {
+            builder
+                .set_alpn_protocols(&alpn_protocols(&self.alpn_protocols))
+                .with_context(|| field("alpn_protocols"))?;
+        }
+        for (i, path) in self.client_auth_root_certificates.iter().enumerate() {
+            (|| {
+                for cert in read_certificates(path)? {
+                    builder.add_client_auth_root_certificate(&cert)?;
+                }
+                Ok::<_, anyhow::Error>(())
+            })()
+            .with_context(|| field_index("client_auth_root_certificates", i))?;
+        }
+        for (i, path) in self.crls.iter().enumerate() {
+            (|| {
+                let crl = read(path)?;
+                if is_pem(&crl) {
+                    builder.add_crl_pem(&crl)
+                } else {
+                    builder.add_crl_der(&crl)
+                }
+            })()
+            .with_context(|| field_index("crls", i))?;
+        }
+        if let Some(policy) = self.revocation_policy {
+            builder
+                .set_revocation_policy(policy)
+                .with_context(|| field("revocation_policy"))?;
+        }
+        if let Some(path) = &self.ocsp_response {
+            (|| builder.set_ocsp_response(&read(path)?))()
+                .with_context(|| field("ocsp_response"))?;
+        }
+        if let Some(path) = &self.key_log_file {
+            (|| builder.set_key_log(key_log_file(path)?))()
+                .with_context(|| field("key_log_file"))?;
+        }
+        Ok(builder)
+    }
+
+    /// Validate the configuration, and build an acceptor.
+    pub fn build(&self, acceptor: &dyn TlsAcceptorType) -> anyhow::Result<TlsAcceptorBox> {
+        self.builder(acceptor)?.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn connector_from_toml() {
+        let config: TlsConnectorConfig = toml::from_str(
+            r#"
+            alpn_protocols = ["h2", "http/1.1"]
+            root_certificates = ["ca.pem"]
+            revocation_policy = "soft-fail"
+            client_certificate = { certificate = "client.pem", key = "client.key" }
+            ocsp_stapling = "must-staple"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            TlsConnectorConfig {
+                alpn_protocols: vec!["h2".to_owned(), "http/1.1".to_owned()],
+                root_certificates: vec![PathBuf::from("ca.pem")],
+                revocation_policy: Some(RevocationPolicy::SoftFail),
+                client_certificate: Some(CertificateKeyConfig {
+                    certificate: PathBuf::from("client.pem"),
+                    key: PathBuf::from("client.key"),
+                }),
+                ocsp_stapling: OcspStapling::MustStaple,
+                ..TlsConnectorConfig::default()
+            },
+            config
+        );
+        assert!(config.verify_hostname);
+    }
+
+    #[test]
+    fn acceptor_from_toml() {
+        let config: TlsAcceptorConfig = toml::from_str(
+            r#"
+            pkcs12 = "server.p12"
+            pkcs12_passphrase = "secret"
+            crls = ["crl.pem"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            TlsAcceptorConfig {
+                pkcs12: Some(PathBuf::from("server.p12")),
+                pkcs12_passphrase: "secret".to_owned(),
+                crls: vec![PathBuf::from("crl.pem")],
+                ..TlsAcceptorConfig::default()
+            },
+            config
+        );
+    }
+
+    #[test]
+    fn unknown_field() {
+        let err = toml::from_str::<TlsConnectorConfig>("alpn = [\"h2\"]").unwrap_err();
+        assert!(err.to_string().contains("unknown field `alpn`"), "{}", err);
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::io;
+
+use crate::assert_send;
+use crate::ChannelBindingType;
+use crate::ImplInfo;
+
+/// State of the handshake of a [`TlsConnection`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsHandshakeState {
+    /// Handshake is not complete yet: exchange ciphertext with the peer
+    /// until the state changes.
+    InProgress,
+    /// Handshake is complete, plaintext can be exchanged.
+    Complete,
+}
+
+/// Trait implemented by all sans-IO TLS connection objects.
+///
+/// Connection does not perform any I/O: the caller feeds ciphertext
+/// received from the peer with [`feed_ciphertext`](Self::feed_ciphertext),
+/// pulls ciphertext to be sent to the peer with
+/// [`take_ciphertext`](Self::take_ciphertext),
+/// and reads and writes plaintext.
+///
+/// See [`TlsStreamDyn`](crate::TlsStreamDyn) for documentation of the introspection functions.
+pub trait TlsConnectionDyn: fmt::Debug + Send + 'static {
+    /// Current handshake state.
+    fn handshake_state(&self) -> TlsHandshakeState;
+
+    /// Process ciphertext received from the peer.
+    ///
+    /// Returns the number of bytes consumed, which can be less than the length
+    /// of `data` when internal buffers are full; the rest must be fed again
+    /// after reading plaintext.
+    ///
+    /// Fails if the peer sent invalid data or the handshake failed;
+    /// in that case there might be an alert to [take](Self::take_ciphertext) and send.
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize>;
+
+    /// Is there ciphertext to be sent to the peer?
+    fn wants_write(&self) -> bool;
+
+    /// Take ciphertext to be sent to the peer.
+    ///
+    /// Returns the number of bytes written to `buf`, `0` if there is nothing to send.
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Read received plaintext.
+    ///
+    /// Returns `0` when the peer sent `close_notify`,
+    /// and fails with [`io::ErrorKind::WouldBlock`] when more ciphertext is needed.
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Write plaintext to be encrypted.
+    ///
+    /// Returns the number of bytes accepted, which can be less than the length
+    /// of `buf` (even zero) when internal buffers are full;
+    /// the rest must be written again after taking ciphertext.
+    ///
+    /// Fails with [`io::ErrorKind::WouldBlock`] when more ciphertext is needed
+    /// before plaintext can be written (e. g. during renegotiation).
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize>;
+
+    /// Queue `close_notify` alert to be sent to the peer.
+    fn send_close_notify(&mut self) -> io::Result<()>;
+
+    /// Must the peer send `close_notify` before closing the socket?
+    ///
+    /// When `true`, socket EOF without `close_notify` is reported
+    /// as [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) error to detect truncation attacks.
+    /// Connections which do not frame data, like `tls-api-not-tls`, return `false`.
+    fn requires_close_notify(&self) -> bool {
+        true
+    }
+
+    /// Get negotiated ALPN protocol negotiated.
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Server name indication (SNI).
+    fn server_name(&self) -> anyhow::Result<Option<String>>;
+
+    /// DER-encoded OCSP response stapled by the server.
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Export keying material.
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Channel binding data of this connection.
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>>;
+
+    /// Implementation info for this connection (e. g. which crate provides it).
+    fn impl_info(&self) -> ImplInfo;
+}
+
+/// Sans-IO TLS connection.
+///
+/// Returned by [`TlsConnector::new_connection`](crate::TlsConnector::new_connection)
+/// and [`TlsAcceptor::new_connection`](crate::TlsAcceptor::new_connection).
+#[derive(Debug)]
+pub struct TlsConnection(Box<dyn TlsConnectionDyn>);
+
+fn _assert_kinds() {
+    assert_send::<TlsConnection>();
+}
+
+impl TlsConnection {
+    /// Wrap.
+    pub fn new<C: TlsConnectionDyn>(conn: C) -> TlsConnection {
+        TlsConnection(Box::new(conn))
+    }
+
+    /// Is handshake complete?
+    pub fn is_handshaking(&self) -> bool {
+        self.0.handshake_state() == TlsHandshakeState::InProgress
+    }
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        self.0.handshake_state()
+    }
+
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.feed_ciphertext(data)
+    }
+
+    fn wants_write(&self) -> bool {
+        self.0.wants_write()
+    }
+
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.take_ciphertext(buf)
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read_plaintext(buf)
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write_plaintext(buf)
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        self.0.send_close_notify()
+    }
+
+    fn requires_close_notify(&self) -> bool {
+        self.0.requires_close_notify()
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.get_alpn_protocol()
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.0.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.0.channel_binding(kind)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.0.impl_info()
+    }
+}
@@ -1,12 +1,24 @@
+use std::future::Future;
 use std::marker;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 use crate::connector_box::TlsConnectorBox;
 use crate::connector_box::TlsConnectorTypeImpl;
+use crate::revocation::crl_pem_to_der;
 use crate::socket::AsyncSocket;
+use crate::spi::TlsStreamWithUpcastDyn;
 use crate::stream::TlsStream;
 use crate::BoxFuture;
+use crate::Capabilities;
 use crate::ImplInfo;
+use crate::KeyLog;
+use crate::OcspStapling;
+use crate::RetryableError;
+use crate::RevocationPolicy;
+use crate::TlsConnection;
 use crate::TlsConnectorType;
+use crate::TlsStreamBlocking;
 use crate::TlsStreamDyn;
 use crate::TlsStreamWithSocket;
 
@@ -36,7 +48,7 @@ pub trait TlsConnectorBuilder: Sized + Sync + Send + 'static {
 
     /// Set ALPN-protocols to negotiate.
     ///
-    /// This operations fails is not [`TlsConnector::SUPPORTS_ALPN`].
+    /// This operations fails is not [`Capabilities::alpn`].
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
 
     /// Should hostname verification be performed?
@@ -49,6 +61,50 @@ pub trait TlsConnectorBuilder: Sized + Sync + Send + 'static {
     /// Param is DER-encoded X.509 certificate.
     fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()>;
 
+    /// Add certificate revocation list used to check revocation status
+    /// of the server certificate chain.
+    ///
+    /// Param is DER-encoded X.509 CRL.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    /// Add certificate revocation lists from a PEM file.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        for crl in crl_pem_to_der(crl)? {
+            self.add_crl_der(&crl)?;
+        }
+        Ok(())
+    }
+
+    /// How added CRLs are checked, [`RevocationPolicy::HardFail`] by default.
+    ///
+    /// This operation fails if not [`Capabilities::crl`].
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()>;
+
+    /// Certificate presented to the server when server requests client authentication.
+    ///
+    /// Params are DER-encoded X.509 certificate and corresponding private key.
+    ///
+    /// This operation fails if not [`Capabilities::client_auth`].
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()>;
+
+    /// Whether to request stapled OCSP response from the server,
+    /// [`OcspStapling::Disabled`] by default.
+    ///
+    /// Received response is available with [`TlsStreamDyn::ocsp_response`].
+    ///
+    /// This operation fails if not [`Capabilities::ocsp_stapling`].
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()>;
+
+    /// Report TLS secrets of established connections to given [`KeyLog`],
+    /// e. g. [`KeyLogFile`](crate::KeyLogFile).
+    ///
+    /// This operation fails if not [`Capabilities::key_log`].
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()>;
+
     /// Finish the acceptor construction.
     fn build(self) -> anyhow::Result<Self::Connector>;
 }
@@ -63,28 +119,22 @@ pub trait TlsConnector: Sized + Sync + Send + 'static {
 
     /// `crate::TlsStream<tls_api::AsyncSocketBox>`.
     ///
-    /// In the world of HKT this would be:
-    ///
-    /// ```ignore
-    /// type TlsStream<S: TlsStreamDyn> : TlsStreamWithSocketDyn<S>;
-    /// ```
+    /// Same as [`Stream<AsyncSocketBox>`](Self::Stream).
     type TlsStream: TlsStreamDyn;
 
+    /// `crate::TlsStream<S>`: stream of the implementation over the socket `S`.
+    ///
+    /// Returned by [`connect_static`](Self::connect_static).
+    type Stream<S: AsyncSocket>: TlsStreamWithUpcastDyn<S>;
+
     /// Get the underlying builder.
     ///
     /// API intentionally exposes the underlying acceptor builder to allow fine tuning
     /// not possible in common API.
     fn underlying_mut(&mut self) -> &mut Self::Underlying;
 
-    /// Is it implemented? When `false` all operations return an error.
-    ///
-    /// At the moment of writing, there are two crates which return `false` here:
-    /// * `tls-api-stub`, dummy implementation is not meant to be instantiated
-    /// * `tls-api-security-framework`, `true` only on macOS and iOS, `false` elsewhere
-    const IMPLEMENTED: bool;
-
-    /// Whether this implementation supports ALPN negotiation.
-    const SUPPORTS_ALPN: bool;
+    /// Features supported by this implementation.
+    const CAPABILITIES: Capabilities;
 
     /// Implementation info.
     fn info() -> ImplInfo;
@@ -92,6 +142,16 @@ pub trait TlsConnector: Sized + Sync + Send + 'static {
     /// New builder for the acceptor.
     fn builder() -> anyhow::Result<Self::Builder>;
 
+    /// Check if connect error is a handshake failure another implementation may not have.
+    ///
+    /// Used by [`FallbackConnectorBuilder`](crate::FallbackConnectorBuilder).
+    /// Implementations look for their own error types in the error chain
+    /// with [`spi::find_error`](crate::spi::find_error).
+    /// Default implementation does not recognize any errors.
+    fn classify_error(_error: &anyhow::Error) -> Option<RetryableError> {
+        None
+    }
+
     /// Dynamic (without type parameter) version of the connector.
     ///
     /// This function returns a connector type, which can be used to constructor connectors.
@@ -153,6 +213,30 @@ pub trait TlsConnector: Sized + Sync + Send + 'static {
         domain: &'a str,
         stream: S,
     ) -> BoxFuture<'a, anyhow::Result<TlsStreamWithSocket<S>>>
+    where
+        S: AsyncSocket,
+    {
+        BoxFuture::new(async move {
+            self.connect_static(domain, stream)
+                .await
+                .map(TlsStreamWithSocket::new)
+        })
+    }
+
+    /// Connect.
+    ///
+    /// Returned future is resolved when the TLS-negotiation completes,
+    /// and the stream is ready to send and receive.
+    ///
+    /// Neither the future nor the stream is boxed, so this function
+    /// does not allocate (unless the implementation itself does),
+    /// and the stream calls are statically dispatched.
+    /// Other `connect` functions are implemented on top of this one.
+    fn connect_static<'a, S>(
+        &'a self,
+        domain: &'a str,
+        stream: S,
+    ) -> impl Future<Output = anyhow::Result<Self::Stream<S>>> + Send + 'a
     where
         S: AsyncSocket;
 
@@ -172,25 +256,47 @@ pub trait TlsConnector: Sized + Sync + Send + 'static {
     ) -> BoxFuture<'a, anyhow::Result<Self::TlsStream>>
     where
         S: AsyncSocket;
+
+    /// Connect over a blocking socket.
+    ///
+    /// This function returns when the TLS-negotiation completes.
+    /// This function does not need an async runtime,
+    /// and it is available when no runtime feature is enabled.
+    ///
+    /// This operation fails if not [`Capabilities::blocking`].
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking>;
+
+    /// Create a sans-IO client connection.
+    ///
+    /// Handshake is driven by the caller by exchanging ciphertext
+    /// with [`TlsConnection`] functions.
+    ///
+    /// This operation fails if not [`Capabilities::connection`].
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection>;
 }
 
 /// Common part of all connectors. Poor man replacement for HKT.
+///
+/// Parameter is the stream type of the implementation over the socket `S`,
+/// e. g. `spi_connector_common!(crate::TlsStream<S>)`.
 #[macro_export]
 macro_rules! spi_connector_common {
-    () => {
-        fn connect_with_socket<'a, S>(
+    ($stream:ty) => {
+        type Stream<S: $crate::AsyncSocket> = $stream;
+
+        fn connect_static<'a, S>(
             &'a self,
             domain: &'a str,
             stream: S,
-        ) -> $crate::BoxFuture<'a, anyhow::Result<$crate::TlsStreamWithSocket<S>>>
+        ) -> impl std::future::Future<Output = anyhow::Result<Self::Stream<S>>> + Send + 'a
         where
             S: $crate::AsyncSocket,
         {
-            $crate::BoxFuture::new(async move {
-                let crate_tls_stream: crate::TlsStream<S> =
-                    self.connect_impl(domain, stream).await?;
-                Ok($crate::TlsStreamWithSocket::new(crate_tls_stream))
-            })
+            self.connect_impl(domain, stream)
         }
 
         fn connect_impl_tls_stream<'a, S>(
@@ -1,39 +1,44 @@
 use std::fmt;
 use std::marker;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 use crate::assert_send;
 use crate::assert_sync;
 use crate::AsyncSocket;
 use crate::AsyncSocketBox;
 use crate::BoxFuture;
+use crate::Capabilities;
 use crate::ImplInfo;
+use crate::KeyLog;
+use crate::OcspStapling;
+use crate::RetryableError;
+use crate::RevocationPolicy;
+use crate::TlsConnection;
 use crate::TlsConnector;
 use crate::TlsConnectorBuilder;
 use crate::TlsStream;
+use crate::TlsStreamBlocking;
 
 // Connector type.
 
 /// Similar to [`TlsConnector`], but it is dynamic, does not require type parameter.
 ///
 /// This can be obtained with [`TlsConnector::TYPE_DYN`].
-pub trait TlsConnectorType: fmt::Display + fmt::Debug + 'static {
+pub trait TlsConnectorType: fmt::Display + fmt::Debug + Sync + 'static {
     /// Constructor a builder dynamically.
     fn builder(&self) -> anyhow::Result<TlsConnectorBuilderBox>;
 
-    /// It this connector implemented?
-    ///
-    /// When not implemented, all operations return error.
-    ///
-    /// For example, `tls-api-security-framework` is available on Linux,
-    /// but all operations result in error, so `implemented()` returns `false`
-    /// for that implementation.
-    fn implemented(&self) -> bool;
-
-    /// Is this implementation ALPN negotation?
-    fn supports_alpn(&self) -> bool;
+    /// Features supported by this implementation.
+    fn capabilities(&self) -> Capabilities;
 
     /// Implementation version.
     fn info(&self) -> ImplInfo;
+
+    /// Check if connect error is a handshake failure another implementation may not have.
+    ///
+    /// See [`TlsConnector::classify_error`].
+    fn classify_error(&self, error: &anyhow::Error) -> Option<RetryableError>;
 }
 
 pub(crate) struct TlsConnectorTypeImpl<C: TlsConnector>(pub marker::PhantomData<C>);
@@ -55,22 +60,22 @@ impl<C: TlsConnector> TlsConnectorType for TlsConnectorTypeImpl<C> {
         Ok(TlsConnectorBuilderBox(Box::new(C::builder()?)))
     }
 
-    fn implemented(&self) -> bool {
-        C::IMPLEMENTED
-    }
-
-    fn supports_alpn(&self) -> bool {
-        C::SUPPORTS_ALPN
+    fn capabilities(&self) -> Capabilities {
+        C::CAPABILITIES
     }
 
     fn info(&self) -> ImplInfo {
         C::info()
     }
+
+    fn classify_error(&self, error: &anyhow::Error) -> Option<RetryableError> {
+        C::classify_error(error)
+    }
 }
 
 // Connector builder.
 
-trait TlsConnectorBuilderDyn: Send + 'static {
+pub(crate) trait TlsConnectorBuilderDyn: Send + 'static {
     fn type_dyn(&self) -> &'static dyn TlsConnectorType;
 
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
@@ -79,6 +84,18 @@ trait TlsConnectorBuilderDyn: Send + 'static {
 
     fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()>;
 
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()>;
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()>;
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()>;
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()>;
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()>;
+
     fn build(self: Box<Self>) -> anyhow::Result<TlsConnectorBox>;
 }
 
@@ -99,6 +116,30 @@ impl<C: TlsConnectorBuilder> TlsConnectorBuilderDyn for C {
         self.add_root_certificate(cert)
     }
 
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.add_crl_der(crl)
+    }
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.add_crl_pem(crl)
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.set_revocation_policy(policy)
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        self.set_client_certificate(cert, key)
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.set_ocsp_stapling(mode)
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.set_key_log(key_log)
+    }
+
     fn build(self: Box<Self>) -> anyhow::Result<TlsConnectorBox> {
         let connector = (*self).build()?;
         Ok(TlsConnectorBox(Box::new(connector)))
@@ -108,7 +149,7 @@ impl<C: TlsConnectorBuilder> TlsConnectorBuilderDyn for C {
 /// [`TlsConnector`] without type parameter.
 ///
 /// Implementation can be switched without parameterizing every function.
-pub struct TlsConnectorBuilderBox(Box<dyn TlsConnectorBuilderDyn>);
+pub struct TlsConnectorBuilderBox(pub(crate) Box<dyn TlsConnectorBuilderDyn>);
 
 impl TlsConnectorBuilderBox {
     /// Build a connector.
@@ -118,7 +159,7 @@ impl TlsConnectorBuilderBox {
 
     /// Set ALPN-protocols to negotiate.
     ///
-    /// This operations fails is not [`TlsConnector::SUPPORTS_ALPN`].
+    /// This operations fails is not [`Capabilities::alpn`].
     pub fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
         self.0.set_alpn_protocols(protocols)
     }
@@ -133,18 +174,71 @@ impl TlsConnectorBuilderBox {
     pub fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
         self.0.add_root_certificate(cert)
     }
+
+    /// Add DER-encoded certificate revocation list.
+    ///
+    /// This operations fails is not [`Capabilities::crl`].
+    pub fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.0.add_crl_der(crl)
+    }
+
+    /// Add certificate revocation lists from a PEM file.
+    ///
+    /// This operations fails is not [`Capabilities::crl`].
+    pub fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.0.add_crl_pem(crl)
+    }
+
+    /// How added CRLs are checked.
+    pub fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.0.set_revocation_policy(policy)
+    }
+
+    /// Certificate presented to the server when server requests client authentication.
+    ///
+    /// This operations fails is not [`Capabilities::client_auth`].
+    pub fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        self.0.set_client_certificate(cert, key)
+    }
+
+    /// Whether to request stapled OCSP response from the server.
+    ///
+    /// This operations fails is not [`Capabilities::ocsp_stapling`].
+    pub fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.0.set_ocsp_stapling(mode)
+    }
+
+    /// Report TLS secrets of established connections to given [`KeyLog`].
+    ///
+    /// This operations fails is not [`Capabilities::key_log`].
+    pub fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.0.set_key_log(key_log)
+    }
 }
 
 // Connector.
 
-trait TlsConnectorDyn: Send + Sync + 'static {
+pub(crate) trait TlsConnectorDyn: Send + Sync + 'static {
     fn type_dyn(&self) -> &'static dyn TlsConnectorType;
 
+    /// Classify a connect error with the implementation which returned it.
+    fn classify_error(&self, error: &anyhow::Error) -> Option<RetryableError> {
+        self.type_dyn().classify_error(error)
+    }
+
     fn connect<'a>(
         &'a self,
         domain: &'a str,
         stream: AsyncSocketBox,
     ) -> BoxFuture<'a, anyhow::Result<TlsStream>>;
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking>;
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection>;
 }
 
 impl<C: TlsConnector> TlsConnectorDyn for C {
@@ -159,6 +253,18 @@ impl<C: TlsConnector> TlsConnectorDyn for C {
     ) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
         self.connect(domain, stream)
     }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.connect_blocking(domain, stream)
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.new_connection(domain)
+    }
 }
 
 /// Configured connector. This is a dynamic version of [`TlsConnector`].
@@ -166,7 +272,7 @@ impl<C: TlsConnector> TlsConnectorDyn for C {
 /// This can be constructed either with:
 /// * [`TlsConnector::into_dyn`]
 /// * [`TlsConnectorBuilderBox::build`]
-pub struct TlsConnectorBox(Box<dyn TlsConnectorDyn>);
+pub struct TlsConnectorBox(pub(crate) Box<dyn TlsConnectorDyn>);
 
 impl TlsConnectorBox {
     pub(crate) fn new<C: TlsConnector>(connector: C) -> TlsConnectorBox {
@@ -192,6 +298,24 @@ impl TlsConnectorBox {
     ) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
         self.connect_dyn(domain, AsyncSocketBox::new(stream))
     }
+
+    /// Connect over a blocking socket.
+    ///
+    /// This operations fails is not [`Capabilities::blocking`].
+    pub fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.0.connect_blocking(domain, stream)
+    }
+
+    /// Create a sans-IO client connection.
+    ///
+    /// This operations fails is not [`Capabilities::connection`].
+    pub fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.0.new_connection(domain)
+    }
 }
 
 fn _assert_kinds() {
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+/// Datagram socket connected to a single peer, e. g. UDP socket.
+///
+/// This is what DTLS streams are created over.
+/// Implemented for `tokio::net::UdpSocket` when `runtime-tokio` feature is enabled;
+/// other runtimes do not provide poll functions for UDP sockets,
+/// so users implement this trait for their sockets.
+pub trait AsyncDatagramSocket: fmt::Debug + Unpin + Send + 'static {
+    /// Receive a single datagram.
+    ///
+    /// Datagram is truncated if it does not fit into the buffer.
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Send the buffer as a single datagram.
+    fn poll_send(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8])
+        -> Poll<io::Result<usize>>;
+
+    /// Address of the peer.
+    fn peer_addr(&self) -> io::Result<SocketAddr>;
+}
+
+impl<S: AsyncDatagramSocket + ?Sized> AsyncDatagramSocket for Box<S> {
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_recv(cx, buf)
+    }
+
+    fn poll_send(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_send(cx, buf)
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        (**self).peer_addr()
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl AsyncDatagramSocket for tokio::net::UdpSocket {
+    fn poll_recv(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut read_buf = tokio::io::ReadBuf::new(buf);
+        match tokio::net::UdpSocket::poll_recv(&self, cx, &mut read_buf) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(read_buf.filled().len())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_send(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        tokio::net::UdpSocket::poll_send(&self, cx, buf)
+    }
+
+    fn peer_addr(&self) -> io::Result<SocketAddr> {
+        tokio::net::UdpSocket::peer_addr(self)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::AsyncDatagramSocket;
+use crate::BoxFuture;
+use crate::Capabilities;
+use crate::DtlsStream;
+use crate::ImplInfo;
+
+/// A builder for `DtlsAcceptor`s.
+///
+/// DTLS counterpart of [`TlsAcceptorBuilder`](crate::TlsAcceptorBuilder).
+pub trait DtlsAcceptorBuilder: Sized + Sync + Send + 'static {
+    /// Type of acceptor produced by this builder.
+    type Acceptor: DtlsAcceptor;
+
+    /// Type of the underlying builder.
+    type Underlying;
+
+    /// Get the underlying builder.
+    ///
+    /// API intentionally exposes the underlying builder to allow fine tuning
+    /// not possible in common API.
+    fn underlying_mut(&mut self) -> &mut Self::Underlying;
+
+    /// Specify ALPN protocols for negotiation.
+    ///
+    /// This operation fails if not [`Capabilities::alpn`].
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
+
+    /// Maximum size of datagrams sent, including DTLS overhead.
+    fn set_mtu(&mut self, mtu: u32) -> anyhow::Result<()>;
+
+    /// Finish the acceptor construction.
+    fn build(self) -> anyhow::Result<Self::Acceptor>;
+}
+
+/// A builder for server-side DTLS connections.
+///
+/// DTLS counterpart of [`TlsAcceptor`](crate::TlsAcceptor).
+///
+/// Server verifies that the client can receive datagrams at its address
+/// with a cookie exchange before doing expensive handshake operations.
+pub trait DtlsAcceptor: Sized + Sync + Send + 'static {
+    /// Type of the builder for this acceptor.
+    type Builder: DtlsAcceptorBuilder<Acceptor = Self>;
+
+    /// Features supported by this implementation.
+    const CAPABILITIES: Capabilities;
+
+    /// Implementation info.
+    fn info() -> ImplInfo;
+
+    /// New builder from given server key.
+    ///
+    /// Parameters are DER-encoded (binary) X509 cert and corresponding private key.
+    fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<Self::Builder>;
+
+    /// Accept a connection.
+    ///
+    /// Socket must be connected to the client address.
+    /// Returned future is resolved when the DTLS-negotiation completes,
+    /// lost handshake messages are retransmitted.
+    fn accept<'a, S>(&'a self, socket: S) -> BoxFuture<'a, anyhow::Result<DtlsStream>>
+    where
+        S: AsyncDatagramSocket;
+}
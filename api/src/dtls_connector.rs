@@ -0,0 +1,72 @@
+use crate::AsyncDatagramSocket;
+use crate::BoxFuture;
+use crate::Capabilities;
+use crate::DtlsStream;
+use crate::ImplInfo;
+
+/// A builder for `DtlsConnector`s.
+///
+/// DTLS counterpart of [`TlsConnectorBuilder`](crate::TlsConnectorBuilder).
+pub trait DtlsConnectorBuilder: Sized + Sync + Send + 'static {
+    /// Result of connector to be build.
+    type Connector: DtlsConnector;
+
+    /// Type of the underlying builder.
+    type Underlying;
+
+    /// Get the underlying builder.
+    ///
+    /// API intentionally exposes the underlying builder to allow fine tuning
+    /// not possible in common API.
+    fn underlying_mut(&mut self) -> &mut Self::Underlying;
+
+    /// Set ALPN-protocols to negotiate.
+    ///
+    /// This operations fails is not [`Capabilities::alpn`].
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()>;
+
+    /// Should hostname verification be performed?
+    /// Use carefully, it opens the door to MITM attacks.
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()>;
+
+    /// Add trusted root certificate. By default connector supports only
+    /// global trusted root.
+    ///
+    /// Param is DER-encoded X.509 certificate.
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()>;
+
+    /// Maximum size of datagrams sent, including DTLS overhead.
+    fn set_mtu(&mut self, mtu: u32) -> anyhow::Result<()>;
+
+    /// Finish the connector construction.
+    fn build(self) -> anyhow::Result<Self::Connector>;
+}
+
+/// A builder for client-side DTLS connections.
+///
+/// DTLS counterpart of [`TlsConnector`](crate::TlsConnector).
+pub trait DtlsConnector: Sized + Sync + Send + 'static {
+    /// Type of the builder for this connector.
+    type Builder: DtlsConnectorBuilder<Connector = Self>;
+
+    /// Features supported by this implementation.
+    const CAPABILITIES: Capabilities;
+
+    /// Implementation info.
+    fn info() -> ImplInfo;
+
+    /// New builder for the connector.
+    fn builder() -> anyhow::Result<Self::Builder>;
+
+    /// Connect.
+    ///
+    /// Returned future is resolved when the DTLS-negotiation completes,
+    /// lost handshake messages are retransmitted.
+    fn connect<'a, S>(
+        &'a self,
+        domain: &'a str,
+        socket: S,
+    ) -> BoxFuture<'a, anyhow::Result<DtlsStream>>
+    where
+        S: AsyncDatagramSocket;
+}
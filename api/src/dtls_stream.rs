@@ -0,0 +1,78 @@
+use std::fmt;
+use std::future;
+use std::io;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::assert_send;
+use crate::ImplInfo;
+
+/// Trait implemented by all DTLS stream objects.
+///
+/// Unlike TLS streams, DTLS stream preserves message boundaries:
+/// each send is delivered to the peer as a single message (or lost).
+pub trait DtlsStreamDyn: fmt::Debug + Send + 'static {
+    /// Receive a single message.
+    ///
+    /// Returns `0` when the peer sent `close_notify`.
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+
+    /// Send the buffer as a single message.
+    ///
+    /// Message must fit into a single datagram with the configured MTU.
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>>;
+
+    /// Get negotiated ALPN protocol negotiated.
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Implementation info for this stream (e. g. which crate provides it).
+    fn impl_info(&self) -> ImplInfo;
+}
+
+/// DTLS stream over [`AsyncDatagramSocket`](crate::AsyncDatagramSocket).
+///
+/// Returned by [`DtlsConnector::connect`](crate::DtlsConnector::connect)
+/// and [`DtlsAcceptor::accept`](crate::DtlsAcceptor::accept).
+#[derive(Debug)]
+pub struct DtlsStream(Box<dyn DtlsStreamDyn>);
+
+fn _assert_kinds() {
+    assert_send::<DtlsStream>();
+}
+
+impl DtlsStream {
+    /// Wrap.
+    pub fn new<S: DtlsStreamDyn>(stream: S) -> DtlsStream {
+        DtlsStream(Box::new(stream))
+    }
+
+    /// Receive a single message.
+    ///
+    /// Returns `0` when the peer sent `close_notify`.
+    pub async fn recv(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        future::poll_fn(|cx| self.0.poll_recv(cx, buf)).await
+    }
+
+    /// Send the buffer as a single message.
+    pub async fn send(&mut self, buf: &[u8]) -> io::Result<usize> {
+        future::poll_fn(|cx| self.0.poll_send(cx, buf)).await
+    }
+}
+
+impl DtlsStreamDyn for DtlsStream {
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_recv(cx, buf)
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_send(cx, buf)
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.get_alpn_protocol()
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.0.impl_info()
+    }
+}
@@ -15,4 +15,39 @@ pub(crate) enum CommonError {
     OpensslCommandFailedToConvert,
     #[error("PEM file created from PKCS #12 is expected to contain a single certificate and key, it actually contains {:?}", _0)]
     PemFromPkcs12ContainsNotSingleCertKeyPair(Vec<String>),
+    #[error("PEM file does not contain X509 CRL entries")]
+    NoCrlsInPem,
+    #[error("handshake failed by fault injection")]
+    FaultInjectedHandshakeFailure,
+    #[error("fallback connector requires at least one connector")]
+    FallbackNoConnectors,
+    #[error("fallback connector only connects async sockets")]
+    FallbackAsyncOnly,
+    #[error("unknown TLS implementation `{}`; available: {}", _0, _1)]
+    UnknownImpl(String, String),
+    #[error("TLS implementation `{}` is not available on this platform; available: {}", _0, _1)]
+    ImplNotImplemented(&'static str, String),
+    #[error("no TLS implementation is available on this platform")]
+    NoImplAvailable,
+    #[cfg(feature = "serde")]
+    #[error("config field `{}`: {} does not support {}", _0, _1, _2)]
+    ConfigUnsupported(&'static str, &'static str, &'static str),
+    #[cfg(feature = "serde")]
+    #[error("config field `{}` is required with `{}`", _0, _1)]
+    ConfigMissing(&'static str, &'static str),
+    #[cfg(feature = "serde")]
+    #[error("config fields `{}` and `{}` cannot be used together", _0, _1)]
+    ConfigConflict(&'static str, &'static str),
+    #[cfg(feature = "serde")]
+    #[error("config requires server key: `certificate` and `key`, or `pkcs12`")]
+    ConfigNoServerKey,
+    #[cfg(feature = "serde")]
+    #[error("PEM file does not contain certificates")]
+    NoCertificatesInPem,
+    #[cfg(feature = "serde")]
+    #[error("expecting a single certificate, file contains {}", _0)]
+    PemNotSingleCertificate(usize),
+    #[cfg(feature = "serde")]
+    #[error("PEM file does not contain a private key")]
+    NoPrivateKeyInPem,
 }
@@ -0,0 +1,303 @@
+//! Connector which falls back to other implementations.
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use crate::connector_box::TlsConnectorDyn;
+use crate::AsyncSocket;
+use crate::AsyncSocketBox;
+use crate::BoxFuture;
+use crate::TlsConnection;
+use crate::TlsConnectorBox;
+use crate::TlsConnectorType;
+use crate::TlsStream;
+use crate::TlsStreamBlocking;
+
+/// Handshake failure which another implementation may not have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryableError {
+    /// No protocol version supported by both sides.
+    ProtocolVersion,
+    /// No cipher suite supported by both sides.
+    CipherSuite,
+    /// Signature algorithm of the handshake or of the certificate is not supported.
+    SignatureAlgorithm,
+    /// Peer is incompatible for unspecified reason (e. g. `handshake_failure` alert).
+    HandshakeFailure,
+}
+
+impl fmt::Display for RetryableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            RetryableError::ProtocolVersion => "protocol version",
+            RetryableError::CipherSuite => "cipher suite",
+            RetryableError::SignatureAlgorithm => "signature algorithm",
+            RetryableError::HandshakeFailure => "handshake failure",
+        };
+        f.write_str(s)
+    }
+}
+
+impl RetryableError {
+    /// Classify a connect error of the connector of given type.
+    ///
+    /// Errors recognized by [`TlsConnector::classify_error`](crate::TlsConnector::classify_error)
+    /// of the implementation are classified first, and
+    /// [`classify_message`](Self::classify_message) is the last resort.
+    /// `None` means the error is not related to the implementation
+    /// (e. g. IO error or certificate validation failure), and it should not be retried.
+    pub fn classify(
+        connector: &dyn TlsConnectorType,
+        error: &anyhow::Error,
+    ) -> Option<RetryableError> {
+        connector
+            .classify_error(error)
+            .or_else(|| RetryableError::classify_message(error))
+    }
+
+    /// Classify a connect error by messages in the error chain.
+    ///
+    /// Last resort for implementations which do not classify their errors,
+    /// or which report them as text (e. g. errors passed through FFI).
+    /// Matches well-known messages of supported implementations,
+    /// so it may break when an implementation changes the wording.
+    pub fn classify_message(error: &anyhow::Error) -> Option<RetryableError> {
+        // Messages differ in case and separators:
+        // `ProtocolVersion`, `protocol_version`, `tlsv1 alert protocol version`
+        let normalized: String = error
+            .chain()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        const PATTERNS: &[(&str, RetryableError)] = &[
+            ("protocolversion", RetryableError::ProtocolVersion),
+            ("unsupportedprotocol", RetryableError::ProtocolVersion),
+            ("wrongversionnumber", RetryableError::ProtocolVersion),
+            ("nosharedcipher", RetryableError::CipherSuite),
+            ("ciphersuite", RetryableError::CipherSuite),
+            ("signaturealgorithm", RetryableError::SignatureAlgorithm),
+            ("signaturescheme", RetryableError::SignatureAlgorithm),
+            ("sigalg", RetryableError::SignatureAlgorithm),
+            ("handshakefailure", RetryableError::HandshakeFailure),
+            ("peerisincompatible", RetryableError::HandshakeFailure),
+        ];
+        PATTERNS
+            .iter()
+            .find(|(pattern, _)| normalized.contains(pattern))
+            .map(|(_, e)| *e)
+    }
+}
+
+/// Find an error of type `E` in the error chain.
+///
+/// Unlike [`anyhow::Error::chain`], also looks at errors wrapped in [`io::Error`],
+/// which does not report the wrapped error as a source.
+pub fn find_error<E: std::error::Error + 'static>(error: &anyhow::Error) -> Option<&E> {
+    error.chain().find_map(|e| {
+        e.downcast_ref::<E>().or_else(|| {
+            e.downcast_ref::<io::Error>()
+                .and_then(|e| e.get_ref())
+                .and_then(|e| e.downcast_ref::<E>())
+        })
+    })
+}
+
+type Reconnect = dyn Fn(&str) -> BoxFuture<'static, io::Result<AsyncSocketBox>> + Send + Sync;
+
+/// Builder of a connector which tries implementations in order.
+///
+/// When a handshake fails with a [`RetryableError`], the connection is reopened
+/// with the reconnect function, and the next implementation is tried.
+/// Error of the last attempt is returned when all implementations fail.
+///
+/// [`TlsStream::impl_info`](crate::TlsStreamDyn::impl_info) of the returned stream
+/// reports the implementation which completed the handshake.
+///
+/// # Limitations
+///
+/// Only [`TlsConnectorBox::connect`] falls back: reconnect function is async,
+/// so [`connect_blocking`](TlsConnectorBox::connect_blocking) and
+/// [`new_connection`](TlsConnectorBox::new_connection) of the built connector fail.
+///
+/// The built connector can be added to another fallback connector: its errors
+/// are classified by each of its implementations, but log messages of the outer
+/// connector describe it by [`ImplInfo`](crate::ImplInfo) of its first implementation.
+pub struct FallbackConnectorBuilder {
+    connectors: Vec<TlsConnectorBox>,
+    reconnect: Arc<Reconnect>,
+}
+
+impl FallbackConnectorBuilder {
+    /// Create a builder with a function which opens a new socket to the domain.
+    pub fn new<F, R, S>(reconnect: F) -> FallbackConnectorBuilder
+    where
+        F: Fn(&str) -> R + Send + Sync + 'static,
+        R: Future<Output = io::Result<S>> + Send + 'static,
+        S: AsyncSocket,
+    {
+        FallbackConnectorBuilder {
+            connectors: Vec::new(),
+            reconnect: Arc::new(move |domain| {
+                let socket = reconnect(domain);
+                BoxFuture::new(async move { socket.await.map(AsyncSocketBox::new) })
+            }),
+        }
+    }
+
+    /// Add a configured connector to try after the previously added ones.
+    pub fn add_connector(&mut self, connector: TlsConnectorBox) {
+        self.connectors.push(connector);
+    }
+
+    /// Build a connector.
+    ///
+    /// Fails if no connectors were added.
+    pub fn build(self) -> anyhow::Result<TlsConnectorBox> {
+        if self.connectors.is_empty() {
+            return Err(crate::CommonError::FallbackNoConnectors.into());
+        }
+        Ok(TlsConnectorBox(Box::new(FallbackConnector {
+            connectors: self.connectors,
+            reconnect: self.reconnect,
+        })))
+    }
+}
+
+struct FallbackConnector {
+    /// Not empty.
+    connectors: Vec<TlsConnectorBox>,
+    reconnect: Arc<Reconnect>,
+}
+
+impl TlsConnectorDyn for FallbackConnector {
+    /// There is no type for a set of implementations, report the first one.
+    fn type_dyn(&self) -> &'static dyn TlsConnectorType {
+        self.connectors[0].0.type_dyn()
+    }
+
+    fn classify_error(&self, error: &anyhow::Error) -> Option<RetryableError> {
+        self.connectors
+            .iter()
+            .find_map(|connector| connector.0.classify_error(error))
+    }
+
+    fn connect<'a>(
+        &'a self,
+        domain: &'a str,
+        stream: AsyncSocketBox,
+    ) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
+        BoxFuture::new(async move {
+            let (last, connectors) = self.connectors.split_last().unwrap();
+            let mut stream = stream;
+            for connector in connectors {
+                let e = match connector.connect_dyn(domain, stream).await {
+                    Ok(stream) => return Ok(stream),
+                    Err(e) => e,
+                };
+                let reason = connector
+                    .0
+                    .classify_error(&e)
+                    .or_else(|| RetryableError::classify_message(&e));
+                match reason {
+                    Some(reason) => log::debug!(
+                        "{} failed to connect to {} ({}), trying next implementation: {}",
+                        connector.0.type_dyn().info(),
+                        domain,
+                        reason,
+                        e
+                    ),
+                    None => return Err(e),
+                }
+                stream = (self.reconnect)(domain).await?;
+            }
+            last.connect_dyn(domain, stream).await
+        })
+    }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        let _ = (domain, stream);
+        Err(crate::CommonError::FallbackAsyncOnly.into())
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        let _ = domain;
+        Err(crate::CommonError::FallbackAsyncOnly.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn classify(message: &str) -> Option<RetryableError> {
+        RetryableError::classify_message(&anyhow::anyhow!("{}", message))
+    }
+
+    #[test]
+    fn classify_messages() {
+        // rustls
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            classify("received fatal alert: ProtocolVersion")
+        );
+        assert_eq!(
+            Some(RetryableError::SignatureAlgorithm),
+            classify("invalid peer certificate: UnsupportedSignatureAlgorithm")
+        );
+        assert_eq!(
+            Some(RetryableError::HandshakeFailure),
+            classify("peer is incompatible: UncompressedEcPointsRequired")
+        );
+        // openssl
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            classify("error:0A00010B:SSL routines:ssl3_get_record:wrong version number")
+        );
+        assert_eq!(
+            Some(RetryableError::CipherSuite),
+            classify("error:0A0000C1:SSL routines:tls_post_process_client_hello:no shared cipher")
+        );
+        assert_eq!(
+            Some(RetryableError::HandshakeFailure),
+            classify("error:0A000410:SSL routines:ssl3_read_bytes:sslv3 alert handshake failure")
+        );
+        // Not retryable
+        assert_eq!(None, classify("invalid peer certificate: UnknownIssuer"));
+        assert_eq!(None, classify("Connection reset by peer (os error 104)"));
+    }
+
+    #[test]
+    fn classify_chain() {
+        let e = anyhow::anyhow!("tlsv1 alert protocol version").context("connect");
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            RetryableError::classify_message(&e)
+        );
+    }
+
+    #[test]
+    fn find_error_in_io_error() {
+        let e = anyhow::Error::new(io::Error::new(
+            io::ErrorKind::InvalidData,
+            crate::CommonError::FallbackNoConnectors,
+        ))
+        .context("connect");
+        assert!(matches!(
+            find_error::<crate::CommonError>(&e),
+            Some(crate::CommonError::FallbackNoConnectors)
+        ));
+        assert!(find_error::<fmt::Error>(&e).is_none());
+    }
+}
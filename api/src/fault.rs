@@ -0,0 +1,565 @@
+//! Fault injection for resilience tests.
+
+use std::future::Future;
+use std::io;
+use std::net::TcpStream;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::acceptor_box::TlsAcceptorBuilderDyn;
+use crate::acceptor_box::TlsAcceptorDyn;
+use crate::connector_box::TlsConnectorBuilderDyn;
+use crate::connector_box::TlsConnectorDyn;
+use crate::spi::AsyncIo;
+use crate::spi::Timer;
+use crate::spi_async_socket_impl;
+use crate::AsyncSocket;
+use crate::AsyncSocketBox;
+use crate::BoxFuture;
+use crate::KeyLog;
+use crate::OcspStapling;
+use crate::RetryableError;
+use crate::RevocationPolicy;
+use crate::TlsAcceptorBox;
+use crate::TlsAcceptorBuilderBox;
+use crate::TlsAcceptorType;
+use crate::TlsConnection;
+use crate::TlsConnectorBox;
+use crate::TlsConnectorBuilderBox;
+use crate::TlsConnectorType;
+use crate::TlsStream;
+use crate::TlsStreamBlocking;
+
+/// Faults injected by a wrapped connector or acceptor.
+///
+/// Random decisions are made by an RNG seeded with [`seed`](Self::seed),
+/// separately for each built connector or acceptor, so the same sequence
+/// of connections gets the same faults.
+///
+/// Record faults apply to the bytes received from the peer by async streams,
+/// before they reach the TLS implementation. Blocking and sans-IO connections
+/// only get handshake faults.
+///
+/// ```
+/// # use std::time::Duration;
+/// # use tls_api::FaultConfig;
+/// let faults = FaultConfig {
+///     seed: 17,
+///     handshake_latency: Duration::from_millis(50),
+///     handshake_failure_probability: 0.1,
+///     ..FaultConfig::default()
+/// };
+/// # drop(faults);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FaultConfig {
+    /// RNG seed.
+    pub seed: u64,
+    /// Delay before each handshake.
+    pub handshake_latency: Duration,
+    /// Maximum random delay added to [`handshake_latency`](Self::handshake_latency).
+    pub handshake_latency_jitter: Duration,
+    /// Fraction of handshakes which fail without touching the socket, from `0.0` to `1.0`.
+    pub handshake_failure_probability: f64,
+    /// Flip random bits of the received byte at this offset.
+    pub corrupt_after: Option<u64>,
+    /// Report EOF after this many received bytes.
+    pub truncate_after: Option<u64>,
+    /// Stop returning received data after this many bytes.
+    pub stall_reads_after: Option<u64>,
+    /// How long reads stall, forever if `None`.
+    pub stall_duration: Option<Duration>,
+}
+
+impl FaultConfig {
+    /// Wrap a connector builder of given type.
+    pub fn connector_builder(
+        &self,
+        connector: &'static dyn TlsConnectorType,
+    ) -> anyhow::Result<TlsConnectorBuilderBox> {
+        Ok(TlsConnectorBuilderBox(Box::new(FaultConnectorBuilder {
+            inner: connector.builder()?,
+            config: self.clone(),
+        })))
+    }
+
+    /// Wrap an acceptor builder of given type constructed from DER certificate and key.
+    pub fn acceptor_builder_from_der_key(
+        &self,
+        acceptor: &'static dyn TlsAcceptorType,
+        cert: &[u8],
+        key: &[u8],
+    ) -> anyhow::Result<TlsAcceptorBuilderBox> {
+        Ok(TlsAcceptorBuilderBox(Box::new(FaultAcceptorBuilder {
+            inner: acceptor.builder_from_der_key(cert, key)?,
+            config: self.clone(),
+        })))
+    }
+
+    /// Wrap an acceptor builder of given type constructed from PKCS #12 archive.
+    pub fn acceptor_builder_from_pkcs12(
+        &self,
+        acceptor: &'static dyn TlsAcceptorType,
+        pkcs12: &[u8],
+        passphrase: &str,
+    ) -> anyhow::Result<TlsAcceptorBuilderBox> {
+        Ok(TlsAcceptorBuilderBox(Box::new(FaultAcceptorBuilder {
+            inner: acceptor.builder_from_pkcs12(pkcs12, passphrase)?,
+            config: self.clone(),
+        })))
+    }
+
+    /// Wrap a configured connector.
+    pub fn wrap_connector(&self, connector: TlsConnectorBox) -> TlsConnectorBox {
+        TlsConnectorBox(Box::new(FaultConnector {
+            inner: connector,
+            injector: FaultInjector::new(self.clone()),
+        }))
+    }
+
+    /// Wrap a configured acceptor.
+    pub fn wrap_acceptor(&self, acceptor: TlsAcceptorBox) -> TlsAcceptorBox {
+        TlsAcceptorBox(Box::new(FaultAcceptor {
+            inner: acceptor,
+            injector: FaultInjector::new(self.clone()),
+        }))
+    }
+}
+
+/// SplitMix64.
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Faults chosen for one connection.
+#[derive(Debug, Clone, PartialEq)]
+struct ConnectionFaults {
+    latency: Duration,
+    fail_handshake: bool,
+    /// Non-zero.
+    corrupt_mask: u8,
+}
+
+#[derive(Debug)]
+struct FaultInjector {
+    config: FaultConfig,
+    rng: Mutex<Rng>,
+}
+
+impl FaultInjector {
+    fn new(config: FaultConfig) -> FaultInjector {
+        FaultInjector {
+            rng: Mutex::new(Rng(config.seed)),
+            config,
+        }
+    }
+
+    fn next_connection(&self) -> ConnectionFaults {
+        let mut rng = self.rng.lock().unwrap();
+        // Always draw the same number of values, so decisions do not shift
+        // when the configuration changes
+        let jitter = rng.next_f64();
+        let fail = rng.next_f64();
+        let mask = rng.next_u64();
+        ConnectionFaults {
+            latency: self.config.handshake_latency
+                + self.config.handshake_latency_jitter.mul_f64(jitter),
+            fail_handshake: fail < self.config.handshake_failure_probability,
+            corrupt_mask: (mask as u8).max(1),
+        }
+    }
+
+    async fn handshake(&self) -> anyhow::Result<ConnectionFaults> {
+        let faults = self.next_connection();
+        if faults.latency != Duration::ZERO {
+            Delay::new(faults.latency).await;
+        }
+        if faults.fail_handshake {
+            return Err(crate::CommonError::FaultInjectedHandshakeFailure.into());
+        }
+        Ok(faults)
+    }
+
+    fn handshake_blocking(&self) -> anyhow::Result<()> {
+        let faults = self.next_connection();
+        thread::sleep(faults.latency);
+        if faults.fail_handshake {
+            return Err(crate::CommonError::FaultInjectedHandshakeFailure.into());
+        }
+        Ok(())
+    }
+
+    fn handshake_connection(&self) -> anyhow::Result<()> {
+        if self.next_connection().fail_handshake {
+            return Err(crate::CommonError::FaultInjectedHandshakeFailure.into());
+        }
+        Ok(())
+    }
+
+    fn wrap_socket(&self, socket: AsyncSocketBox, faults: &ConnectionFaults) -> AsyncSocketBox {
+        AsyncSocketBox::new(FaultSocket {
+            inner: socket,
+            corrupt_after: self.config.corrupt_after,
+            corrupt_mask: faults.corrupt_mask,
+            truncate_after: self.config.truncate_after,
+            stall_reads_after: self.config.stall_reads_after,
+            stall_duration: self.config.stall_duration,
+            stall: Stall::NotYet,
+            received: 0,
+        })
+    }
+}
+
+/// Runtime-neutral delay.
+#[derive(Debug)]
+struct Delay {
+    deadline: Instant,
+    timer: Timer,
+}
+
+impl Delay {
+    fn new(duration: Duration) -> Delay {
+        Delay {
+            deadline: Instant::now() + duration,
+            timer: Timer::new(),
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+        self.timer.wake_at(self.deadline, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[derive(Debug)]
+enum Stall {
+    NotYet,
+    /// Stalling until the delay, if any, is complete.
+    Stalling(Option<Delay>),
+    Done,
+}
+
+/// Socket which damages received data.
+#[derive(Debug)]
+struct FaultSocket {
+    inner: AsyncSocketBox,
+    corrupt_after: Option<u64>,
+    corrupt_mask: u8,
+    truncate_after: Option<u64>,
+    stall_reads_after: Option<u64>,
+    stall_duration: Option<Duration>,
+    stall: Stall,
+    /// Bytes received so far.
+    received: u64,
+}
+
+impl AsyncIo for FaultSocket {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if let Stall::NotYet = this.stall {
+            if matches!(this.stall_reads_after, Some(after) if this.received >= after) {
+                this.stall = Stall::Stalling(this.stall_duration.map(Delay::new));
+            }
+        }
+        if let Stall::Stalling(delay) = &mut this.stall {
+            match delay {
+                Some(delay) => std::task::ready!(Pin::new(delay).poll(cx)),
+                // Never woken
+                None => return Poll::Pending,
+            }
+            this.stall = Stall::Done;
+        }
+
+        // Stop exactly at the offsets where faults start
+        let mut limit = buf.len() as u64;
+        if let Some(truncate_after) = this.truncate_after {
+            if this.received >= truncate_after {
+                return Poll::Ready(Ok(0));
+            }
+            limit = limit.min(truncate_after - this.received);
+        }
+        if let (Stall::NotYet, Some(after)) = (&this.stall, this.stall_reads_after) {
+            limit = limit.min(after - this.received);
+        }
+        let buf = &mut buf[..limit as usize];
+
+        let read = std::task::ready!(Pin::new(&mut this.inner).poll_socket_read(cx, buf))?;
+        if let Some(corrupt_after) = this.corrupt_after {
+            if corrupt_after >= this.received && corrupt_after < this.received + read as u64 {
+                buf[(corrupt_after - this.received) as usize] ^= this.corrupt_mask;
+            }
+        }
+        this.received += read as u64;
+        Poll::Ready(Ok(read))
+    }
+
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_socket_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_socket_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_socket_shutdown(cx)
+    }
+}
+
+spi_async_socket_impl!(FaultSocket);
+
+struct FaultConnectorBuilder {
+    inner: TlsConnectorBuilderBox,
+    config: FaultConfig,
+}
+
+impl TlsConnectorBuilderDyn for FaultConnectorBuilder {
+    fn type_dyn(&self) -> &'static dyn TlsConnectorType {
+        self.inner.0.type_dyn()
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.inner.set_alpn_protocols(protocols)
+    }
+
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
+        self.inner.set_verify_hostname(verify)
+    }
+
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_root_certificate(cert)
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_crl_der(crl)
+    }
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_crl_pem(crl)
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.inner.set_revocation_policy(policy)
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        self.inner.set_client_certificate(cert, key)
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.inner.set_ocsp_stapling(mode)
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.inner.set_key_log(key_log)
+    }
+
+    fn build(self: Box<Self>) -> anyhow::Result<TlsConnectorBox> {
+        Ok(self.config.wrap_connector(self.inner.build()?))
+    }
+}
+
+struct FaultConnector {
+    inner: TlsConnectorBox,
+    injector: FaultInjector,
+}
+
+impl TlsConnectorDyn for FaultConnector {
+    fn type_dyn(&self) -> &'static dyn TlsConnectorType {
+        self.inner.0.type_dyn()
+    }
+
+    fn classify_error(&self, error: &anyhow::Error) -> Option<RetryableError> {
+        self.inner.0.classify_error(error)
+    }
+
+    fn connect<'a>(
+        &'a self,
+        domain: &'a str,
+        stream: AsyncSocketBox,
+    ) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
+        BoxFuture::new(async move {
+            let faults = self.injector.handshake().await?;
+            let stream = self.injector.wrap_socket(stream, &faults);
+            self.inner.connect_dyn(domain, stream).await
+        })
+    }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.injector.handshake_blocking()?;
+        self.inner.connect_blocking(domain, stream)
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.injector.handshake_connection()?;
+        self.inner.new_connection(domain)
+    }
+}
+
+struct FaultAcceptorBuilder {
+    inner: TlsAcceptorBuilderBox,
+    config: FaultConfig,
+}
+
+impl TlsAcceptorBuilderDyn for FaultAcceptorBuilder {
+    fn type_dyn(&self) -> &'static dyn TlsAcceptorType {
+        self.inner.type_dyn()
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.inner.set_alpn_protocols(protocols)
+    }
+
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_client_auth_root_certificate(cert)
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_crl_der(crl)
+    }
+
+    fn add_crl_pem(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.inner.add_crl_pem(crl)
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.inner.set_revocation_policy(policy)
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.inner.set_ocsp_response(ocsp_response)
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.inner.set_key_log(key_log)
+    }
+
+    fn build(self: Box<Self>) -> anyhow::Result<TlsAcceptorBox> {
+        Ok(self.config.wrap_acceptor(self.inner.build()?))
+    }
+}
+
+struct FaultAcceptor {
+    inner: TlsAcceptorBox,
+    injector: FaultInjector,
+}
+
+impl TlsAcceptorDyn for FaultAcceptor {
+    fn type_dyn(&self) -> &'static dyn TlsAcceptorType {
+        self.inner.type_dyn()
+    }
+
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.inner.set_ocsp_response(ocsp_response)
+    }
+
+    fn accept<'a>(&'a self, socket: AsyncSocketBox) -> BoxFuture<'a, anyhow::Result<TlsStream>> {
+        BoxFuture::new(async move {
+            let faults = self.injector.handshake().await?;
+            let socket = self.injector.wrap_socket(socket, &faults);
+            self.inner.accept(socket).await
+        })
+    }
+
+    fn accept_blocking(&self, socket: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.injector.handshake_blocking()?;
+        self.inner.accept_blocking(socket)
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.injector.handshake_connection()?;
+        self.inner.new_connection()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn failures(config: &FaultConfig) -> Vec<bool> {
+        let injector = FaultInjector::new(config.clone());
+        (0..64)
+            .map(|_| injector.next_connection().fail_handshake)
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_faults() {
+        let config = FaultConfig {
+            seed: 42,
+            handshake_latency_jitter: Duration::from_secs(1),
+            handshake_failure_probability: 0.5,
+            ..FaultConfig::default()
+        };
+        let a = FaultInjector::new(config.clone());
+        let b = FaultInjector::new(config);
+        for _ in 0..64 {
+            assert_eq!(a.next_connection(), b.next_connection());
+        }
+    }
+
+    #[test]
+    fn failure_probability() {
+        let mut config = FaultConfig::default();
+        assert!(failures(&config).iter().all(|f| !f));
+        config.handshake_failure_probability = 1.0;
+        assert!(failures(&config).iter().all(|f| *f));
+        config.handshake_failure_probability = 0.5;
+        let failed = failures(&config).iter().filter(|f| **f).count();
+        assert!(failed > 0 && failed < 64, "{}", failed);
+    }
+
+    #[test]
+    fn latency_jitter() {
+        let injector = FaultInjector::new(FaultConfig {
+            handshake_latency: Duration::from_millis(10),
+            handshake_latency_jitter: Duration::from_millis(5),
+            ..FaultConfig::default()
+        });
+        for _ in 0..64 {
+            let latency = injector.next_connection().latency;
+            assert!(latency >= Duration::from_millis(10), "{:?}", latency);
+            assert!(latency < Duration::from_millis(15), "{:?}", latency);
+        }
+    }
+}
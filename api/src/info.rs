@@ -7,8 +7,15 @@ use std::fmt;
 pub struct ImplInfo {
     /// Implementation name (usually the name of crate the underlying implementation).
     pub name: &'static str,
-    /// Some unspecified version number (e. g. openssl library version for openssl implementation).
+    /// Version of the underlying library, when it is available at runtime
+    /// (e. g. `OpenSSL 3.0.13 30 Jan 2024` for openssl implementation),
+    /// otherwise version requirement of the library crate (e. g. `^0.23.29` for rustls).
     pub version: &'static str,
+    /// Cryptography provider, when the implementation allows choosing one
+    /// (e. g. `ring` or `aws-lc-rs` for rustls).
+    pub crypto_provider: Option<&'static str>,
+    /// Whether cryptography is performed by a FIPS validated module.
+    pub fips: bool,
 }
 
 fn _assert_kinds() {
@@ -18,6 +25,12 @@ fn _assert_kinds() {
 
 impl fmt::Display for ImplInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}={}", self.name, self.version)
+        write!(f, "{}={}", self.name, self.version)?;
+        match (self.crypto_provider, self.fips) {
+            (Some(provider), true) => write!(f, " ({}, FIPS)", provider),
+            (Some(provider), false) => write!(f, " ({})", provider),
+            (None, true) => write!(f, " (FIPS)"),
+            (None, false) => Ok(()),
+        }
     }
 }
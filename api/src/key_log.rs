@@ -0,0 +1,130 @@
+use std::env;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Receiver of TLS session secrets.
+///
+/// Secrets are reported in the terms of
+/// [NSS key log format](https://developer.mozilla.org/en-US/docs/Mozilla/Projects/NSS/Key_Log_Format),
+/// which is understood by Wireshark.
+///
+/// Installed with [`TlsConnectorBuilder::set_key_log`](crate::TlsConnectorBuilder::set_key_log)
+/// or [`TlsAcceptorBuilder::set_key_log`](crate::TlsAcceptorBuilder::set_key_log).
+/// This is a debugging facility: anyone with access to the secrets
+/// can decrypt the traffic.
+pub trait KeyLog: fmt::Debug + Send + Sync + 'static {
+    /// Log a secret.
+    ///
+    /// `label` is for example `CLIENT_RANDOM` (TLS 1.2 master secret)
+    /// or `CLIENT_TRAFFIC_SECRET_0` (TLS 1.3),
+    /// `client_random` identifies the session.
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]);
+}
+
+/// Format a line of NSS key log file (without trailing newline).
+pub(crate) fn key_log_line(label: &str, client_random: &[u8], secret: &[u8]) -> String {
+    let mut line =
+        String::with_capacity(label.len() + 2 + 2 * (client_random.len() + secret.len()));
+    line.push_str(label);
+    line.push(' ');
+    for b in client_random {
+        write!(line, "{:02x}", b).unwrap();
+    }
+    line.push(' ');
+    for b in secret {
+        write!(line, "{:02x}", b).unwrap();
+    }
+    line
+}
+
+/// [`KeyLog`] which appends secrets to a file, like browsers do.
+///
+/// Write errors are ignored.
+pub struct KeyLogFile {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl fmt::Debug for KeyLogFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyLogFile")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl KeyLogFile {
+    /// Name of environment variable with the key log file path.
+    pub const ENV_VAR: &'static str = "SSLKEYLOGFILE";
+
+    /// Open the file for appending, create if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<KeyLogFile> {
+        let path = path.as_ref().to_owned();
+        let file = OpenOptions::new().append(true).create(true).open(&path)?;
+        Ok(KeyLogFile {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Open the file specified by `SSLKEYLOGFILE` environment variable.
+    ///
+    /// Return `None` if the variable is not set or empty.
+    pub fn from_env() -> io::Result<Option<KeyLogFile>> {
+        match env::var_os(Self::ENV_VAR) {
+            Some(path) if !path.is_empty() => KeyLogFile::open(path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl KeyLog for KeyLogFile {
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        let mut line = key_log_line(label, client_random, secret);
+        line.push('\n');
+        // Single write to avoid interleaving with other processes
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        drop(file.write_all(line.as_bytes()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn line() {
+        assert_eq!(
+            "CLIENT_RANDOM 0a0b ff00",
+            key_log_line("CLIENT_RANDOM", &[10, 11], &[255, 0])
+        );
+    }
+
+    #[test]
+    fn file_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("keys.log");
+        fs::write(&path, "# existing\n").unwrap();
+        let key_log = KeyLogFile::open(&path).unwrap();
+        key_log.log("CLIENT_TRAFFIC_SECRET_0", &[1], &[2, 3]);
+        key_log.log("SERVER_TRAFFIC_SECRET_0", &[1], &[4]);
+        assert_eq!(
+            "# existing\nCLIENT_TRAFFIC_SECRET_0 01 0203\nSERVER_TRAFFIC_SECRET_0 01 04\n",
+            fs::read_to_string(&path).unwrap()
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! Kernel TLS (kTLS) on Linux.
+
+use std::cmp;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::mem;
+use std::net::Shutdown;
+use std::net::TcpStream;
+use std::ops::Range;
+use std::os::raw::c_int;
+use std::os::raw::c_void;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::io::RawFd;
+
+use crate::ImplInfo;
+use crate::TlsAcceptor;
+use crate::TlsConnector;
+use crate::TlsStreamBlocking;
+use crate::TlsStreamBlockingDyn;
+
+// From `linux/tls.h`, not exposed by `libc`
+const TLS_TX: c_int = 1;
+const TLS_RX: c_int = 2;
+const TLS_SET_RECORD_TYPE: c_int = 1;
+const TLS_GET_RECORD_TYPE: c_int = 2;
+const TLS_CIPHER_AES_GCM_128: u16 = 51;
+const TLS_CIPHER_AES_GCM_256: u16 = 52;
+const TLS_CIPHER_CHACHA20_POLY1305: u16 = 54;
+
+// TLS record content types
+const RECORD_ALERT: u8 = 21;
+const RECORD_HANDSHAKE: u8 = 22;
+const RECORD_APPLICATION_DATA: u8 = 23;
+
+const ALERT_CLOSE_NOTIFY: u8 = 0;
+const HANDSHAKE_KEY_UPDATE: u8 = 24;
+
+/// Maximum plaintext of a TLS record plus the record header.
+const MAX_RECORD_LEN: usize = 16 * 1024 + 5;
+
+/// AEAD of the negotiated cipher suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KtlsCipher {
+    /// AES-128-GCM.
+    Aes128Gcm,
+    /// AES-256-GCM.
+    Aes256Gcm,
+    /// ChaCha20-Poly1305.
+    Chacha20Poly1305,
+}
+
+/// Keys of one direction of the connection, passed by implementations to the kernel.
+pub struct KtlsCryptoInfo {
+    /// Protocol version, `0x0303` for TLS 1.2 or `0x0304` for TLS 1.3.
+    pub version: u16,
+    /// AEAD.
+    pub cipher: KtlsCipher,
+    /// AEAD key.
+    pub key: Vec<u8>,
+    /// 12 bytes IV (TLS 1.2 salt followed by explicit nonce).
+    pub iv: Vec<u8>,
+    /// Sequence number of the next record.
+    pub seq: u64,
+}
+
+impl fmt::Debug for KtlsCryptoInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not print the key
+        f.debug_struct("KtlsCryptoInfo")
+            .field("version", &self.version)
+            .field("cipher", &self.cipher)
+            .field("seq", &self.seq)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KtlsCryptoInfo {
+    /// Serialize as `struct tls12_crypto_info_*`.
+    fn to_sockopt(&self) -> io::Result<Vec<u8>> {
+        let (cipher_type, key_len, salt_len) = match self.cipher {
+            KtlsCipher::Aes128Gcm => (TLS_CIPHER_AES_GCM_128, 16, 4),
+            KtlsCipher::Aes256Gcm => (TLS_CIPHER_AES_GCM_256, 32, 4),
+            KtlsCipher::Chacha20Poly1305 => (TLS_CIPHER_CHACHA20_POLY1305, 32, 0),
+        };
+        if self.key.len() != key_len || self.iv.len() != 12 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "wrong kTLS key or IV length",
+            ));
+        }
+        let (salt, iv) = self.iv.split_at(salt_len);
+        let mut r = Vec::new();
+        r.extend_from_slice(&self.version.to_ne_bytes());
+        r.extend_from_slice(&cipher_type.to_ne_bytes());
+        r.extend_from_slice(iv);
+        r.extend_from_slice(&self.key);
+        r.extend_from_slice(salt);
+        r.extend_from_slice(&self.seq.to_be_bytes());
+        Ok(r)
+    }
+}
+
+fn setsockopt(socket: &TcpStream, level: c_int, name: c_int, value: &[u8]) -> io::Result<()> {
+    let r = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            level,
+            name,
+            value.as_ptr() as *const c_void,
+            value.len() as libc::socklen_t,
+        )
+    };
+    if r != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Attach the `tls` upper layer protocol to the socket.
+///
+/// Fails if the `tls` kernel module is not available; then the socket
+/// is unchanged and can still be used for userspace TLS.
+pub fn enable_tls_ulp(socket: &TcpStream) -> io::Result<()> {
+    setsockopt(socket, libc::SOL_TCP, libc::TCP_ULP, b"tls")
+}
+
+/// Configure kernel encryption and decryption on the socket
+/// with `tls` upper layer protocol attached.
+pub fn configure_ktls(
+    socket: &TcpStream,
+    tx: &KtlsCryptoInfo,
+    rx: &KtlsCryptoInfo,
+) -> io::Result<()> {
+    setsockopt(socket, libc::SOL_TLS, TLS_TX, &tx.to_sockopt()?)?;
+    setsockopt(socket, libc::SOL_TLS, TLS_RX, &rx.to_sockopt()?)?;
+    Ok(())
+}
+
+/// TLS stream with record encryption done by the kernel.
+///
+/// Application data is read and written directly on the socket,
+/// so the socket can be used with `sendfile` and `splice`.
+pub struct KtlsStream {
+    socket: TcpStream,
+    alpn_protocol: Option<Vec<u8>>,
+    impl_info: ImplInfo,
+    eof: bool,
+    /// Records are received here when the caller's buffer cannot hold a whole record:
+    /// alerts and handshake messages must be received whole to be parsed.
+    record: Vec<u8>,
+    /// Application data in `record` not yet returned to the caller.
+    pending: Range<usize>,
+}
+
+impl fmt::Debug for KtlsStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KtlsStream")
+            .field("socket", &self.socket)
+            .field("alpn_protocol", &self.alpn_protocol)
+            .field("impl_info", &self.impl_info)
+            .field("eof", &self.eof)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
+
+impl KtlsStream {
+    /// Wrap a socket with kTLS configured in both directions.
+    pub fn new(
+        socket: TcpStream,
+        alpn_protocol: Option<Vec<u8>>,
+        impl_info: ImplInfo,
+    ) -> KtlsStream {
+        KtlsStream {
+            socket,
+            alpn_protocol,
+            impl_info,
+            eof: false,
+            record: Vec::new(),
+            pending: 0..0,
+        }
+    }
+
+    /// Get negotiated ALPN protocol.
+    pub fn get_alpn_protocol(&self) -> Option<&[u8]> {
+        self.alpn_protocol.as_deref()
+    }
+
+    /// Implementation which performed the handshake.
+    pub fn impl_info(&self) -> ImplInfo {
+        self.impl_info.clone()
+    }
+
+    /// Get the underlying socket.
+    pub fn get_socket_ref(&self) -> &TcpStream {
+        &self.socket
+    }
+
+    /// Send `close_notify` and shut down the write side of the socket.
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        self.send_record(RECORD_ALERT, &[1, ALERT_CLOSE_NOTIFY])?;
+        self.socket.shutdown(Shutdown::Write)
+    }
+
+    /// Send a record of the given type.
+    fn send_record(&mut self, record_type: u8, data: &[u8]) -> io::Result<()> {
+        let mut cmsg_buf = [0u64; 4];
+        let mut iov = libc::iovec {
+            iov_base: data.as_ptr() as *mut c_void,
+            iov_len: data.len(),
+        };
+        unsafe {
+            let mut msg: libc::msghdr = mem::zeroed();
+            msg.msg_iov = &mut iov;
+            msg.msg_iovlen = 1;
+            msg.msg_control = cmsg_buf.as_mut_ptr() as *mut c_void;
+            msg.msg_controllen = libc::CMSG_SPACE(1) as _;
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_TLS;
+            (*cmsg).cmsg_type = TLS_SET_RECORD_TYPE;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(1) as _;
+            *libc::CMSG_DATA(cmsg) = record_type;
+            if libc::sendmsg(self.socket.as_raw_fd(), &msg, 0) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Receive a single record (or its part), return its content type.
+///
+/// Records other than application data are returned one per call.
+fn recv_record(socket: &TcpStream, buf: &mut [u8]) -> io::Result<(u8, usize)> {
+    let mut cmsg_buf = [0u64; 4];
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut c_void,
+        iov_len: buf.len(),
+    };
+    unsafe {
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = cmsg_buf.as_mut_ptr() as *mut c_void;
+        msg.msg_controllen = mem::size_of_val(&cmsg_buf) as _;
+        let read = libc::recvmsg(socket.as_raw_fd(), &mut msg, 0);
+        if read < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // Control message is present only for records other than application data
+        let mut record_type = RECORD_APPLICATION_DATA;
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_TLS && (*cmsg).cmsg_type == TLS_GET_RECORD_TYPE {
+                record_type = *libc::CMSG_DATA(cmsg);
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+        Ok((record_type, read as usize))
+    }
+}
+
+/// Check a record other than application data, `Ok(true)` on `close_notify`.
+fn control_record(record_type: u8, data: &[u8]) -> io::Result<bool> {
+    match record_type {
+        RECORD_ALERT => match data {
+            [_, ALERT_CLOSE_NOTIFY] => Ok(true),
+            [_, description] => Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                format!("received TLS alert {}", description),
+            )),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "malformed TLS alert record",
+            )),
+        },
+        RECORD_HANDSHAKE => {
+            // Session tickets are not used after the handshake,
+            // a record may contain several handshake messages
+            let mut messages = data;
+            while let [message_type, l0, l1, l2, rest @ ..] = messages {
+                if *message_type == HANDSHAKE_KEY_UPDATE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "TLS key update is not supported with kTLS",
+                    ));
+                }
+                let len = u32::from_be_bytes([0, *l0, *l1, *l2]) as usize;
+                messages = rest.get(len..).unwrap_or_default();
+            }
+            Ok(false)
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unexpected TLS record type {}", record_type),
+        )),
+    }
+}
+
+impl Read for KtlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if !self.pending.is_empty() {
+                let len = cmp::min(buf.len(), self.pending.len());
+                buf[..len].copy_from_slice(&self.record[self.pending.start..][..len]);
+                self.pending.start += len;
+                return Ok(len);
+            }
+            if self.eof || buf.is_empty() {
+                return Ok(0);
+            }
+            // Large buffer holds any record, so application data is received in place
+            let direct = buf.len() >= MAX_RECORD_LEN;
+            if !direct && self.record.is_empty() {
+                self.record = vec![0; MAX_RECORD_LEN];
+            }
+            let (record_type, read) = if direct {
+                recv_record(&self.socket, buf)?
+            } else {
+                recv_record(&self.socket, &mut self.record)?
+            };
+            if record_type != RECORD_APPLICATION_DATA {
+                let data = if direct {
+                    &buf[..read]
+                } else {
+                    &self.record[..read]
+                };
+                if control_record(record_type, data)? {
+                    self.eof = true;
+                }
+            } else if read == 0 {
+                // Socket EOF without `close_notify`
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "peer closed connection without sending TLS close_notify",
+                ));
+            } else if direct {
+                return Ok(read);
+            } else {
+                self.pending = 0..read;
+            }
+        }
+    }
+}
+
+impl Write for KtlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.socket.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.socket.flush()
+    }
+}
+
+impl AsRawFd for KtlsStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
+    }
+}
+
+/// Stream returned by [`KtlsConnector::connect_ktls`] and [`KtlsAcceptor::accept_ktls`]:
+/// kTLS stream or, if kernel TLS is not available, regular TLS stream.
+#[derive(Debug)]
+pub enum MaybeKtlsStream {
+    /// Kernel does the record encryption.
+    Ktls(KtlsStream),
+    /// Kernel TLS is not available for this connection, TLS is done in userspace.
+    Userspace(TlsStreamBlocking),
+}
+
+impl MaybeKtlsStream {
+    /// Is kernel TLS used?
+    pub fn is_ktls(&self) -> bool {
+        matches!(self, MaybeKtlsStream::Ktls(_))
+    }
+
+    /// Get negotiated ALPN protocol.
+    pub fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        match self {
+            MaybeKtlsStream::Ktls(s) => Ok(s.get_alpn_protocol().map(Vec::from)),
+            MaybeKtlsStream::Userspace(s) => s.get_alpn_protocol(),
+        }
+    }
+
+    /// Implementation which established the connection.
+    pub fn impl_info(&self) -> ImplInfo {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.impl_info(),
+            MaybeKtlsStream::Userspace(s) => s.impl_info(),
+        }
+    }
+
+    /// Get the underlying socket.
+    pub fn get_socket_ref(&self) -> &TcpStream {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.get_socket_ref(),
+            MaybeKtlsStream::Userspace(s) => s.get_socket_ref(),
+        }
+    }
+
+    /// Send `close_notify` and shutdown the write side of the socket.
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.shutdown(),
+            MaybeKtlsStream::Userspace(s) => s.shutdown(),
+        }
+    }
+}
+
+impl Read for MaybeKtlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.read(buf),
+            MaybeKtlsStream::Userspace(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for MaybeKtlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.write(buf),
+            MaybeKtlsStream::Userspace(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            MaybeKtlsStream::Ktls(s) => s.flush(),
+            MaybeKtlsStream::Userspace(s) => s.flush(),
+        }
+    }
+}
+
+/// TLS connector which can offload record encryption to the Linux kernel.
+pub trait KtlsConnector: TlsConnector {
+    /// Perform the handshake over blocking socket, then configure kTLS on the socket.
+    ///
+    /// Falls back to [`MaybeKtlsStream::Userspace`] when kTLS is not available
+    /// (e. g. `tls` kernel module is not loaded or the cipher suite is not supported).
+    /// Failure of the kernel to accept the keys is an error:
+    /// by then the connection state is already taken from the TLS library.
+    fn connect_ktls(&self, domain: &str, stream: TcpStream) -> anyhow::Result<MaybeKtlsStream>;
+}
+
+/// TLS acceptor which can offload record encryption to the Linux kernel.
+pub trait KtlsAcceptor: TlsAcceptor {
+    /// Perform the handshake over blocking socket, then configure kTLS on the socket.
+    ///
+    /// Falls back to [`MaybeKtlsStream::Userspace`] when kTLS is not available
+    /// (e. g. `tls` kernel module is not loaded or the cipher suite is not supported).
+    /// Failure of the kernel to accept the keys is an error:
+    /// by then the connection state is already taken from the TLS library.
+    fn accept_ktls(&self, stream: TcpStream) -> anyhow::Result<MaybeKtlsStream>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn alert_record() {
+        assert!(control_record(RECORD_ALERT, &[1, ALERT_CLOSE_NOTIFY]).unwrap());
+        let e = control_record(RECORD_ALERT, &[2, 40]).unwrap_err();
+        assert_eq!(io::ErrorKind::ConnectionAborted, e.kind());
+        let e = control_record(RECORD_ALERT, &[1]).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, e.kind());
+    }
+
+    #[test]
+    fn handshake_record() {
+        // Two `NewSessionTicket` messages, the second one with the body of key update type
+        let tickets = [
+            4,
+            0,
+            0,
+            2,
+            HANDSHAKE_KEY_UPDATE,
+            0,
+            4,
+            0,
+            0,
+            1,
+            HANDSHAKE_KEY_UPDATE,
+        ];
+        assert!(!control_record(RECORD_HANDSHAKE, &tickets).unwrap());
+        let mut key_update = tickets.to_vec();
+        key_update.extend_from_slice(&[HANDSHAKE_KEY_UPDATE, 0, 0, 1, 0]);
+        let e = control_record(RECORD_HANDSHAKE, &key_update).unwrap_err();
+        assert_eq!(io::ErrorKind::Unsupported, e.kind());
+    }
+}
@@ -1,8 +1,9 @@
 //! # One TLS API to rule them all
 //!
-//! Support both:
+//! Support:
 //! * `tokio`
 //! * `async-std`
+//! * other executors using `futures` IO traits, like `smol`
 //!
 //! and four TLS implementations:
 //! * `tls-api-openssl`, wraps `openssl` crate
@@ -18,8 +19,8 @@
 //! use tls_api::{TlsConnector, TlsConnectorBuilder};
 //! // or async_std::net::TcpStream;
 //! use tokio::net::TcpStream;
-//! # use tls_api::runtime::AsyncWriteExt;
-//! # use tls_api::runtime::AsyncReadExt;
+//! # use tls_api::runtime::tokio::AsyncWriteExt;
+//! # use tls_api::runtime::tokio::AsyncReadExt;
 //!
 //! async fn download_rust_lang_org<C: TlsConnector>() -> anyhow::Result<Vec<u8>> {
 //!     let stream = TcpStream::connect(("rust-lang.org", 443)).await?;
@@ -39,8 +40,8 @@
 //! use tls_api::TlsConnectorType;
 //! // or async_std::net::TcpStream;
 //! use tokio::net::TcpStream;
-//! # use tls_api::runtime::AsyncWriteExt;
-//! # use tls_api::runtime::AsyncReadExt;
+//! # use tls_api::runtime::tokio::AsyncWriteExt;
+//! # use tls_api::runtime::tokio::AsyncReadExt;
 //!
 //! async fn download_rust_lang_org(connector_type: &dyn TlsConnectorType) -> anyhow::Result<Vec<u8>> {
 //!     let stream = TcpStream::connect(("rust-lang.org", 443)).await?;
@@ -64,8 +65,29 @@
 //! Crate features:
 //! * `runtime-tokio` enables the implementation over tokio
 //! * `runtime-async-std` enables the implementation over async-std
+//! * `runtime-futures-io` enables the implementation over `futures` IO traits,
+//!   for `smol`, `async-executor` and similar executors (implied by `runtime-async-std`)
 //!
-//! Currently the features are mutually exclusive.
+//! The features can be enabled together (e. g. when different crates in a workspace
+//! use different runtimes); then TLS streams implement both tokio and `futures` IO traits,
+//! see [`runtime`] module for details.
+//!
+//! Blocking API ([`TlsConnector::connect_blocking`], [`TlsAcceptor::accept_blocking`])
+//! over [`std::net::TcpStream`] does not need any runtime feature.
+//!
+//! Sans-IO API ([`TlsConnector::new_connection`], [`TlsAcceptor::new_connection`])
+//! returns a [`TlsConnection`] which does not perform any I/O:
+//! the caller moves ciphertext between the connection and the transport.
+//!
+//! DTLS API ([`DtlsConnector`], [`DtlsAcceptor`]) works over [`AsyncDatagramSocket`],
+//! e. g. UDP socket.
+//!
+//! QUIC stacks use TLS handshake directly, without TLS records:
+//! [`QuicConnector`] and [`QuicAcceptor`] create [`QuicConnection`]s
+//! which take CRYPTO frame data and output handshake data and secrets per encryption level.
+//!
+//! On Linux, [`KtlsConnector`] and [`KtlsAcceptor`] can hand record encryption
+//! to the kernel after the handshake, so `sendfile` can be used on the socket.
 
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(missing_docs)]
@@ -75,16 +97,63 @@ pub use acceptor::TlsAcceptorBuilder;
 pub use acceptor_box::TlsAcceptorBox;
 pub use acceptor_box::TlsAcceptorBuilderBox;
 pub use acceptor_box::TlsAcceptorType;
+pub use capabilities::Capabilities;
+pub use capabilities::ProtocolVersion;
+pub use channel_binding::ChannelBindingError;
+pub use channel_binding::ChannelBindingType;
+#[cfg(feature = "serde")]
+pub use config::CertificateKeyConfig;
+#[cfg(feature = "serde")]
+pub use config::TlsAcceptorConfig;
+#[cfg(feature = "serde")]
+pub use config::TlsConnectorConfig;
+pub use connection::TlsConnection;
+pub use connection::TlsConnectionDyn;
+pub use connection::TlsHandshakeState;
 pub use connector::TlsConnector;
 pub use connector::TlsConnectorBuilder;
 pub use connector_box::TlsConnectorBox;
 pub use connector_box::TlsConnectorBuilderBox;
 pub use connector_box::TlsConnectorType;
+pub use datagram::AsyncDatagramSocket;
+pub use dtls_acceptor::DtlsAcceptor;
+pub use dtls_acceptor::DtlsAcceptorBuilder;
+pub use dtls_connector::DtlsConnector;
+pub use dtls_connector::DtlsConnectorBuilder;
+pub use dtls_stream::DtlsStream;
+pub use dtls_stream::DtlsStreamDyn;
+pub use fallback::FallbackConnectorBuilder;
+pub use fallback::RetryableError;
+pub use fault::FaultConfig;
 pub use future::BoxFuture;
 pub use info::ImplInfo;
+pub use key_log::KeyLog;
+pub use key_log::KeyLogFile;
+#[cfg(target_os = "linux")]
+pub use ktls::KtlsAcceptor;
+#[cfg(target_os = "linux")]
+pub use ktls::KtlsConnector;
+#[cfg(target_os = "linux")]
+pub use ktls::KtlsStream;
+#[cfg(target_os = "linux")]
+pub use ktls::MaybeKtlsStream;
+pub use ocsp::OcspStapling;
+pub use quic::QuicAcceptor;
+pub use quic::QuicConnection;
+pub use quic::QuicConnectionDyn;
+pub use quic::QuicConnector;
+pub use quic::QuicEncryptionLevel;
+pub use quic::QuicEvent;
+pub use quic::QuicSecrets;
+pub use registry::RegisteredImpl;
+pub use registry::TlsImplRegistry;
+pub use registry::TLS_API_IMPL_ENV;
+pub use revocation::RevocationPolicy;
 pub use socket::AsyncSocket;
 pub use socket_box::AsyncSocketBox;
 pub use stream::TlsStream;
+pub use stream_blocking::TlsStreamBlocking;
+pub use stream_blocking::TlsStreamBlockingDyn;
 pub use stream_dyn::TlsStreamDyn;
 pub use stream_dyn::TlsStreamWithSocketDyn;
 pub use stream_with_socket::TlsStreamWithSocket;
@@ -99,27 +168,65 @@ pub mod runtime;
 /// Interfaces needed by API implementor (like `tls-api-rustls`),
 /// and not needed by the users of API.
 pub mod spi {
+    pub use crate::channel_binding::TLS_EXPORTER_LABEL;
+    pub use crate::channel_binding::TLS_EXPORTER_LEN;
+    pub use crate::fallback::find_error;
+    #[cfg(target_os = "linux")]
+    pub use crate::ktls::configure_ktls;
+    #[cfg(target_os = "linux")]
+    pub use crate::ktls::enable_tls_ulp;
+    #[cfg(target_os = "linux")]
+    pub use crate::ktls::KtlsCipher;
+    #[cfg(target_os = "linux")]
+    pub use crate::ktls::KtlsCryptoInfo;
+    pub use crate::socket::AsyncIo;
     pub use crate::stream_dyn::TlsStreamWithUpcastDyn;
+    pub use crate::stream_over_connection::TlsStreamOverConnection;
     pub use crate::thread_local_context::restore_context;
     pub use crate::thread_local_context::save_context;
+    pub use crate::timer::Timer;
 }
 
 mod acceptor;
 mod acceptor_box;
 mod assert_kinds;
 pub mod async_as_sync;
+mod capabilities;
+mod channel_binding;
+#[cfg(feature = "serde")]
+mod config;
+mod connection;
 mod connector;
 mod connector_box;
+mod datagram;
+mod dtls_acceptor;
+mod dtls_connector;
+mod dtls_stream;
 mod error;
+mod fallback;
+mod fault;
 mod future;
 mod info;
+mod key_log;
+#[cfg(target_os = "linux")]
+mod ktls;
+mod ocsp;
 mod openssl;
+mod quic;
+mod registry;
+mod revocation;
 mod socket;
 mod socket_box;
+mod socket_futures_io;
+#[cfg(feature = "runtime-tokio")]
+mod socket_tokio_io;
 mod stream;
+mod stream_blocking;
 mod stream_dyn;
+mod stream_over_connection;
 mod stream_with_socket;
 mod thread_local_context;
+mod timer;
 
 fn _assert_kinds() {
     fn connect_future_is_send<C, S>(c: &C, s: S)
@@ -0,0 +1,21 @@
+/// Whether client requests a stapled OCSP response from the server.
+///
+/// Stapled response is exposed with [`TlsStreamDyn::ocsp_response`](crate::TlsStreamDyn::ocsp_response),
+/// but its content is not validated: that is left to the user.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum OcspStapling {
+    /// Do not care about stapled OCSP response.
+    ///
+    /// Note some implementations request stapling anyway.
+    #[default]
+    Disabled,
+    /// Request stapled OCSP response, but accept servers which do not send it.
+    Request,
+    /// Request stapled OCSP response, and fail the handshake if server does not send it.
+    MustStaple,
+}
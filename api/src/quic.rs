@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::assert_send;
+use crate::ImplInfo;
+
+/// QUIC encryption level (packet number space).
+///
+/// Initial keys are derived by the QUIC stack from the destination connection id,
+/// so [`QuicSecrets`] are only reported for later levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuicEncryptionLevel {
+    /// Initial packets.
+    Initial,
+    /// Handshake packets.
+    Handshake,
+    /// 1-RTT (application data) packets.
+    OneRtt,
+}
+
+/// TLS 1.3 traffic secrets for an encryption level.
+///
+/// QUIC stack derives packet protection and header protection keys
+/// from these secrets (RFC 9001, section 5.1).
+#[derive(Clone, PartialEq, Eq)]
+pub struct QuicSecrets {
+    /// Encryption level these secrets are for.
+    pub level: QuicEncryptionLevel,
+    /// Negotiated TLS 1.3 cipher suite IANA identifier, e. g. `0x1301`.
+    pub cipher_suite: u16,
+    /// Secret to protect packets sent by this side.
+    pub local: Vec<u8>,
+    /// Secret to unprotect packets sent by the peer.
+    pub remote: Vec<u8>,
+}
+
+impl fmt::Debug for QuicSecrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Do not print the secrets
+        f.debug_struct("QuicSecrets")
+            .field("level", &self.level)
+            .field("cipher_suite", &self.cipher_suite)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Output of [`QuicConnection`] to be handled by the QUIC stack, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuicEvent {
+    /// Handshake data to be sent to the peer in CRYPTO frames.
+    CryptoData {
+        /// Encryption level of packets the data must be sent in.
+        level: QuicEncryptionLevel,
+        /// Handshake data.
+        data: Vec<u8>,
+    },
+    /// Keys for a new encryption level are available.
+    Secrets(QuicSecrets),
+}
+
+/// Trait implemented by all QUIC TLS connection objects.
+///
+/// Connection does not perform any I/O: the QUIC stack feeds the contents of
+/// received CRYPTO frames with [`feed_crypto_data`](Self::feed_crypto_data)
+/// and handles [events](Self::next_event): sends handshake data and installs
+/// packet protection keys.
+pub trait QuicConnectionDyn: fmt::Debug + Send + 'static {
+    /// Process contents of CRYPTO frames received from the peer at the given level.
+    ///
+    /// Data must be fed in order. Fails if the handshake failed,
+    /// then [`alert`](Self::alert) should be sent to the peer in `CONNECTION_CLOSE`.
+    fn feed_crypto_data(&mut self, level: QuicEncryptionLevel, data: &[u8]) -> anyhow::Result<()>;
+
+    /// Next output to be handled by the QUIC stack, `None` if there is nothing to do
+    /// until more data is fed.
+    fn next_event(&mut self) -> Option<QuicEvent>;
+
+    /// Is handshake still in progress?
+    fn is_handshaking(&self) -> bool;
+
+    /// QUIC transport parameters sent by the peer, available after peer's first flight.
+    fn peer_transport_parameters(&self) -> Option<Vec<u8>>;
+
+    /// TLS alert to be sent to the peer after handshake failure.
+    fn alert(&self) -> Option<u8>;
+
+    /// Get negotiated ALPN protocol negotiated.
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Export keying material.
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Implementation info for this connection (e. g. which crate provides it).
+    fn impl_info(&self) -> ImplInfo;
+}
+
+/// TLS connection for QUIC.
+///
+/// Returned by [`QuicConnector::new_quic_connection`](crate::QuicConnector::new_quic_connection)
+/// and [`QuicAcceptor::new_quic_connection`](crate::QuicAcceptor::new_quic_connection).
+#[derive(Debug)]
+pub struct QuicConnection(Box<dyn QuicConnectionDyn>);
+
+fn _assert_kinds() {
+    assert_send::<QuicConnection>();
+}
+
+impl QuicConnection {
+    /// Wrap.
+    pub fn new<C: QuicConnectionDyn>(conn: C) -> QuicConnection {
+        QuicConnection(Box::new(conn))
+    }
+}
+
+impl QuicConnectionDyn for QuicConnection {
+    fn feed_crypto_data(&mut self, level: QuicEncryptionLevel, data: &[u8]) -> anyhow::Result<()> {
+        self.0.feed_crypto_data(level, data)
+    }
+
+    fn next_event(&mut self) -> Option<QuicEvent> {
+        self.0.next_event()
+    }
+
+    fn is_handshaking(&self) -> bool {
+        self.0.is_handshaking()
+    }
+
+    fn peer_transport_parameters(&self) -> Option<Vec<u8>> {
+        self.0.peer_transport_parameters()
+    }
+
+    fn alert(&self) -> Option<u8> {
+        self.0.alert()
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.get_alpn_protocol()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.0.impl_info()
+    }
+}
+
+/// TLS connector which can create connections for QUIC.
+///
+/// Connector configuration (root certificates, ALPN protocols etc.) is used for QUIC
+/// connections, but QUIC requires TLS 1.3.
+pub trait QuicConnector: crate::TlsConnector {
+    /// Create a client connection for QUIC version 1.
+    ///
+    /// `transport_parameters` are encoded QUIC transport parameters sent to the server.
+    fn new_quic_connection(
+        &self,
+        domain: &str,
+        transport_parameters: &[u8],
+    ) -> anyhow::Result<QuicConnection>;
+}
+
+/// TLS acceptor which can create connections for QUIC.
+///
+/// Acceptor configuration (server key, ALPN protocols etc.) is used for QUIC
+/// connections, but QUIC requires TLS 1.3.
+pub trait QuicAcceptor: crate::TlsAcceptor {
+    /// Create a server connection for QUIC version 1.
+    ///
+    /// `transport_parameters` are encoded QUIC transport parameters sent to the client.
+    fn new_quic_connection(&self, transport_parameters: &[u8]) -> anyhow::Result<QuicConnection>;
+}
@@ -0,0 +1,173 @@
+//! Selection of implementation by name at runtime.
+
+use std::env;
+use std::fmt;
+
+use crate::TlsAcceptor;
+use crate::TlsAcceptorType;
+use crate::TlsConnector;
+use crate::TlsConnectorType;
+
+/// Environment variable read by [`TlsImplRegistry::get_from_env`].
+pub const TLS_API_IMPL_ENV: &str = "TLS_API_IMPL";
+
+/// Connector and acceptor types of one implementation.
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredImpl {
+    /// Connector type.
+    pub connector: &'static dyn TlsConnectorType,
+    /// Acceptor type.
+    pub acceptor: &'static dyn TlsAcceptorType,
+}
+
+impl RegisteredImpl {
+    /// Implementation name, e. g. `rustls`.
+    pub fn name(&self) -> &'static str {
+        self.connector.info().name
+    }
+
+    /// Whether the implementation is available on this platform.
+    pub fn implemented(&self) -> bool {
+        self.connector.capabilities().implemented && self.acceptor.capabilities().implemented
+    }
+}
+
+impl fmt::Display for RegisteredImpl {
+    /// Implementation info and capabilities.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.implemented() {
+            return write!(f, "{}: not implemented", self.name());
+        }
+        write!(
+            f,
+            "{}; connector: {}; acceptor: {}",
+            self.connector.info(),
+            self.connector.capabilities(),
+            self.acceptor.capabilities(),
+        )
+    }
+}
+
+/// Implementations linked into the program, looked up by name.
+///
+/// Implementations are registered by the program (or by a facade crate),
+/// in the order of preference:
+///
+/// ```ignore
+/// let mut registry = TlsImplRegistry::new();
+/// registry.register::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>();
+/// registry.register::<tls_api_openssl::TlsConnector, tls_api_openssl::TlsAcceptor>();
+/// // `rustls` unless overridden with `TLS_API_IMPL=openssl`
+/// let connector = registry.get_from_env()?.connector.builder()?.build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TlsImplRegistry {
+    impls: Vec<RegisteredImpl>,
+}
+
+impl TlsImplRegistry {
+    /// Empty registry.
+    pub fn new() -> TlsImplRegistry {
+        TlsImplRegistry::default()
+    }
+
+    /// Register an implementation.
+    pub fn register<C: TlsConnector, A: TlsAcceptor>(&mut self) {
+        self.register_dyn(C::TYPE_DYN, A::TYPE_DYN);
+    }
+
+    /// Register an implementation by its connector and acceptor types.
+    pub fn register_dyn(
+        &mut self,
+        connector: &'static dyn TlsConnectorType,
+        acceptor: &'static dyn TlsAcceptorType,
+    ) {
+        self.impls.push(RegisteredImpl {
+            connector,
+            acceptor,
+        });
+    }
+
+    /// All registered implementations, including not implemented on this platform.
+    pub fn all(&self) -> &[RegisteredImpl] {
+        &self.impls
+    }
+
+    /// Implementations available on this platform.
+    pub fn available(&self) -> impl Iterator<Item = RegisteredImpl> + '_ {
+        self.impls
+            .iter()
+            .copied()
+            .filter(RegisteredImpl::implemented)
+    }
+
+    fn available_names(&self) -> String {
+        let names: Vec<&str> = self.available().map(|i| i.name()).collect();
+        if names.is_empty() {
+            "none".to_owned()
+        } else {
+            names.join(", ")
+        }
+    }
+
+    /// Find an available implementation by name.
+    ///
+    /// Name is matched ignoring case, and `_` matches `-`,
+    /// so `native_tls` finds `native-tls`.
+    pub fn get(&self, name: &str) -> anyhow::Result<RegisteredImpl> {
+        let normalize = |s: &str| s.to_ascii_lowercase().replace('_', "-");
+        let normalized = normalize(name);
+        match self
+            .impls
+            .iter()
+            .find(|i| normalize(i.name()) == normalized)
+        {
+            Some(i) if i.implemented() => Ok(*i),
+            Some(i) => {
+                Err(crate::CommonError::ImplNotImplemented(i.name(), self.available_names()).into())
+            }
+            None => {
+                Err(crate::CommonError::UnknownImpl(name.to_owned(), self.available_names()).into())
+            }
+        }
+    }
+
+    /// First available implementation.
+    pub fn default_impl(&self) -> anyhow::Result<RegisteredImpl> {
+        match self.available().next() {
+            Some(i) => Ok(i),
+            None => Err(crate::CommonError::NoImplAvailable.into()),
+        }
+    }
+
+    /// Implementation named by [`TLS_API_IMPL`](TLS_API_IMPL_ENV) environment variable,
+    /// or [`default_impl`](Self::default_impl) if the variable is not set or empty.
+    pub fn get_from_env(&self) -> anyhow::Result<RegisteredImpl> {
+        self.get_from_env_var(TLS_API_IMPL_ENV)
+    }
+
+    /// Implementation named by given environment variable,
+    /// or [`default_impl`](Self::default_impl) if the variable is not set or empty.
+    pub fn get_from_env_var(&self, var: &str) -> anyhow::Result<RegisteredImpl> {
+        match env::var(var) {
+            Ok(name) if !name.is_empty() => self.get(&name),
+            Ok(_) | Err(env::VarError::NotPresent) => self.default_impl(),
+            Err(e) => Err(anyhow::Error::new(e).context(format!("reading {}", var))),
+        }
+    }
+}
+
+fn _assert_kinds() {
+    crate::assert_send::<TlsImplRegistry>();
+    crate::assert_sync::<TlsImplRegistry>();
+}
+
+impl fmt::Display for TlsImplRegistry {
+    /// One line per implementation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in &self.impls {
+            writeln!(f, "{}", i)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+/// How certificate revocation lists are used to verify the peer certificate chain.
+///
+/// Policy has no effect if no CRLs were added to the builder:
+/// revocation is not checked at all in that case.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum RevocationPolicy {
+    /// Check every certificate in the chain (except trust anchor),
+    /// fail if a certificate is revoked or if there's no CRL for the certificate issuer.
+    #[default]
+    HardFail,
+    /// Check every certificate in the chain (except trust anchor),
+    /// fail if a certificate is revoked, but accept certificates
+    /// whose revocation status is unknown.
+    SoftFail,
+    /// Check only the end-entity certificate,
+    /// fail if it is revoked or its revocation status is unknown.
+    LeafOnly,
+}
+
+/// Split PEM file into DER-encoded CRLs.
+pub(crate) fn crl_pem_to_der(crl_pem: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let crls: Vec<Vec<u8>> = pem::parse_many(crl_pem)
+        .into_iter()
+        .filter(|p| p.tag == "X509 CRL")
+        .map(|p| p.contents)
+        .collect();
+    if crls.is_empty() {
+        return Err(crate::CommonError::NoCrlsInPem.into());
+    }
+    Ok(crls)
+}
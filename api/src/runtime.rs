@@ -1,24 +1,62 @@
-//! Tokio or async-std type reexports.
+//! Async IO traits of supported runtimes.
 //!
-//! Note rustdoc will likely show tokio types here (because tokio is default),
-//! but for async-std, async-std types are imported here.
-
-#[cfg(feature = "runtime-async-std")]
-pub use futures_util::io::AsyncRead;
-#[cfg(feature = "runtime-async-std")]
-pub use futures_util::io::AsyncReadExt;
-#[cfg(feature = "runtime-async-std")]
-pub use futures_util::io::AsyncWrite;
-#[cfg(feature = "runtime-async-std")]
-pub use futures_util::io::AsyncWriteExt;
+//! TLS streams implement [`tokio`] traits with `runtime-tokio` feature,
+//! and [`futures`] traits (used by async-std, smol and similar executors)
+//! with `runtime-futures-io` or `runtime-async-std` feature,
+//! or when no runtime feature is enabled.
+//!
+//! Runtime features are additive: enabling one runtime does not change
+//! traits or sockets of another runtime, so crates using different runtimes
+//! can be used in the same build. Import traits from the module
+//! of the runtime used, not depending on which features are enabled.
+//!
+//! Sockets of supported runtimes implement [`AsyncSocket`](crate::AsyncSocket)
+//! directly, other sockets can be wrapped into [`TokioIo`] or [`FuturesIo`].
 
+pub use crate::socket_futures_io::FuturesIo;
 #[cfg(feature = "runtime-tokio")]
-pub use tokio::io::AsyncRead;
-#[cfg(feature = "runtime-tokio")]
-pub use tokio::io::AsyncReadExt;
-#[cfg(feature = "runtime-tokio")]
-pub use tokio::io::AsyncWrite;
-#[cfg(feature = "runtime-tokio")]
-pub use tokio::io::AsyncWriteExt;
+pub use crate::socket_tokio_io::TokioIo;
+
+/// `tokio` IO traits.
 #[cfg(feature = "runtime-tokio")]
-pub use tokio::io::ReadBuf;
+pub mod tokio {
+    pub use ::tokio::io::AsyncRead;
+    pub use ::tokio::io::AsyncReadExt;
+    pub use ::tokio::io::AsyncWrite;
+    pub use ::tokio::io::AsyncWriteExt;
+    pub use ::tokio::io::ReadBuf;
+}
+
+/// `futures` IO traits.
+pub mod futures {
+    pub use futures_util::io::AsyncRead;
+    pub use futures_util::io::AsyncReadExt;
+    pub use futures_util::io::AsyncWrite;
+    pub use futures_util::io::AsyncWriteExt;
+}
+
+/// Declare [`RuntimeIo`] with the given supertraits.
+macro_rules! runtime_io {
+    ( $($bound:path),* ) => {
+        /// IO traits of all enabled runtimes.
+        ///
+        /// Supertrait of [`TlsStreamDyn`](crate::TlsStreamDyn), so TLS streams
+        /// work with IO utilities of each enabled runtime.
+        /// Implemented with [`spi_async_socket_impl!`](crate::spi_async_socket_impl).
+        pub trait RuntimeIo: $($bound +)* {}
+
+        impl<T: $($bound +)* ?Sized> RuntimeIo for T {}
+    };
+}
+
+#[cfg(all(feature = "runtime-tokio", feature = "runtime-futures-io"))]
+runtime_io!(
+    tokio::AsyncRead,
+    tokio::AsyncWrite,
+    futures::AsyncRead,
+    futures::AsyncWrite
+);
+#[cfg(all(feature = "runtime-tokio", not(feature = "runtime-futures-io")))]
+runtime_io!(tokio::AsyncRead, tokio::AsyncWrite);
+#[cfg(not(feature = "runtime-tokio"))]
+runtime_io!(futures::AsyncRead, futures::AsyncWrite);
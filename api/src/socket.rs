@@ -1,150 +1,536 @@
-use crate::runtime::AsyncRead;
-use crate::runtime::AsyncWrite;
 use std::fmt;
+use std::io;
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
 
-/// Type alias for necessary socket async traits.
+/// Socket which can be used by TLS API.
 ///
-/// Type alias exists to avoid repetition of traits in function signatures.
+/// Implemented for sockets of enabled runtimes:
+/// * with `runtime-tokio`: `tokio::net::TcpStream`, `tokio::net::UnixStream`
+///   and `tokio::io::DuplexStream`
+/// * with `runtime-async-std`: `async_std::net::TcpStream`
+///   and `async_std::os::unix::net::UnixStream`
+/// * with `runtime-futures-io`: `async_net::TcpStream` (same as `smol::net::TcpStream`)
+///   and `async_net::unix::UnixStream`
 ///
-/// This type cannot be implemented directly, and there's no need to.
-pub trait AsyncSocket: AsyncRead + AsyncWrite + fmt::Debug + Unpin + Send + 'static {}
-
-/// Auto-implement for all socket types.
-impl<A: AsyncRead + AsyncWrite + fmt::Debug + Unpin + Send + 'static> AsyncSocket for A {}
-
-/// Delegate [`AsyncSocket`] implementation to the underlying socket.
+/// and for TLS streams. Other sockets can be wrapped into
+/// [`TokioIo`](crate::runtime::TokioIo) or [`FuturesIo`](crate::runtime::FuturesIo).
 ///
-/// This is meant to be used only by API implementations.
+/// Implementations for one runtime do not depend on other runtime features,
+/// so crates using different runtimes can be linked together.
 ///
-/// # See also
-/// * [PR in tokio](https://github.com/tokio-rs/tokio/pull/3540)
-/// * [PR in futures](https://github.com/rust-lang/futures-rs/pull/2352)
+/// `poll_socket_*` functions are runtime-neutral socket operations
+/// used by API implementations.
+///
+/// API implementations implement [`AsyncIo`](crate::spi::AsyncIo) instead
+/// of this trait directly.
+pub trait AsyncSocket: fmt::Debug + Unpin + Send + 'static {
+    /// Attempt to read from the socket into `buf`.
+    fn poll_socket_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempt to write `buf` into the socket.
+    fn poll_socket_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempt to write `bufs` into the socket.
+    fn poll_socket_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Whether the socket has an efficient [`poll_socket_write_vectored`](Self::poll_socket_write_vectored).
+    fn is_socket_write_vectored(&self) -> bool;
+
+    /// Attempt to flush the socket.
+    fn poll_socket_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempt to shut down the write side of the socket.
+    fn poll_socket_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+impl<S: AsyncSocket + ?Sized> AsyncSocket for Box<S> {
+    fn poll_socket_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_socket_read(cx, buf)
+    }
+
+    fn poll_socket_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_socket_write(cx, buf)
+    }
+
+    fn poll_socket_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut **self.get_mut()).poll_socket_write_vectored(cx, bufs)
+    }
+
+    fn is_socket_write_vectored(&self) -> bool {
+        (**self).is_socket_write_vectored()
+    }
+
+    fn poll_socket_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self.get_mut()).poll_socket_flush(cx)
+    }
+
+    fn poll_socket_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut **self.get_mut()).poll_socket_shutdown(cx)
+    }
+}
+
+/// Implement [`AsyncSocket`] for a socket implementing `tokio` IO traits.
 #[cfg(feature = "runtime-tokio")]
-#[macro_export]
-macro_rules! spi_async_socket_impl_delegate {
-    ( "AsyncRead" ) => {
-        fn poll_read(
-            self: std::pin::Pin<&mut Self>,
-            cx: &mut std::task::Context<'_>,
-            buf: &mut $crate::runtime::ReadBuf,
-        ) -> std::task::Poll<std::io::Result<()>> {
-            self.deref_pin_mut_for_impl_socket().poll_read(cx, buf)
+macro_rules! async_socket_impl_tokio {
+    ( [$($g:tt)*] $t:ty ) => {
+        impl<$($g)*> AsyncSocket for $t {
+            fn poll_socket_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                let mut read_buf = ::tokio::io::ReadBuf::new(buf);
+                std::task::ready!(::tokio::io::AsyncRead::poll_read(self, cx, &mut read_buf))?;
+                Poll::Ready(Ok(read_buf.filled().len()))
+            }
+
+            fn poll_socket_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                ::tokio::io::AsyncWrite::poll_write(self, cx, buf)
+            }
+
+            fn poll_socket_write_vectored(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                bufs: &[IoSlice<'_>],
+            ) -> Poll<io::Result<usize>> {
+                ::tokio::io::AsyncWrite::poll_write_vectored(self, cx, bufs)
+            }
+
+            fn is_socket_write_vectored(&self) -> bool {
+                ::tokio::io::AsyncWrite::is_write_vectored(self)
+            }
+
+            fn poll_socket_flush(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                ::tokio::io::AsyncWrite::poll_flush(self, cx)
+            }
+
+            fn poll_socket_shutdown(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                ::tokio::io::AsyncWrite::poll_shutdown(self, cx)
+            }
         }
     };
-    ( "AsyncWrite" ) => {
-        fn poll_write(
-            self: std::pin::Pin<&mut Self>,
-            cx: &mut std::task::Context<'_>,
-            buf: &[u8],
-        ) -> std::task::Poll<std::io::Result<usize>> {
-            self.deref_pin_mut_for_impl_socket().poll_write(cx, buf)
-        }
+}
 
-        fn poll_write_vectored(
-            self: std::pin::Pin<&mut Self>,
-            cx: &mut std::task::Context<'_>,
-            bufs: &[std::io::IoSlice<'_>],
-        ) -> std::task::Poll<std::result::Result<usize, std::io::Error>> {
-            self.deref_pin_mut_for_impl_socket()
-                .poll_write_vectored(cx, bufs)
-        }
+/// Implement [`AsyncSocket`] for a socket implementing `futures` IO traits.
+#[cfg(any(feature = "runtime-async-std", feature = "runtime-futures-io"))]
+macro_rules! async_socket_impl_futures {
+    ( [$($g:tt)*] $t:ty ) => {
+        impl<$($g)*> AsyncSocket for $t {
+            fn poll_socket_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                futures_util::io::AsyncRead::poll_read(self, cx, buf)
+            }
 
-        fn is_write_vectored(&self) -> bool {
-            self.deref_for_impl_socket().is_write_vectored()
-        }
+            fn poll_socket_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                futures_util::io::AsyncWrite::poll_write(self, cx, buf)
+            }
 
-        fn poll_flush(
-            self: std::pin::Pin<&mut Self>,
-            cx: &mut std::task::Context<'_>,
-        ) -> std::task::Poll<std::io::Result<()>> {
-            self.deref_pin_mut_for_impl_socket().poll_flush(cx)
+            fn poll_socket_write_vectored(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                bufs: &[IoSlice<'_>],
+            ) -> Poll<io::Result<usize>> {
+                futures_util::io::AsyncWrite::poll_write_vectored(self, cx, bufs)
+            }
+
+            fn is_socket_write_vectored(&self) -> bool {
+                // `futures` IO traits do not provide this information.
+                false
+            }
+
+            fn poll_socket_flush(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                futures_util::io::AsyncWrite::poll_flush(self, cx)
+            }
+
+            fn poll_socket_shutdown(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<io::Result<()>> {
+                futures_util::io::AsyncWrite::poll_close(self, cx)
+            }
         }
+    };
+}
 
-        fn poll_shutdown(
-            self: std::pin::Pin<&mut Self>,
-            ctx: &mut std::task::Context<'_>,
-        ) -> std::task::Poll<std::io::Result<()>> {
-            self.deref_pin_mut_for_impl_socket().poll_shutdown(ctx)
+#[cfg(feature = "runtime-tokio")]
+async_socket_impl_tokio!([] tokio::net::TcpStream);
+#[cfg(all(feature = "runtime-tokio", unix))]
+async_socket_impl_tokio!([] tokio::net::UnixStream);
+#[cfg(feature = "runtime-tokio")]
+async_socket_impl_tokio!([] tokio::io::DuplexStream);
+
+#[cfg(feature = "runtime-async-std")]
+async_socket_impl_futures!([] async_std::net::TcpStream);
+#[cfg(all(feature = "runtime-async-std", unix))]
+async_socket_impl_futures!([] async_std::os::unix::net::UnixStream);
+
+#[cfg(feature = "runtime-futures-io")]
+async_socket_impl_futures!([] async_net::TcpStream);
+#[cfg(all(feature = "runtime-futures-io", unix))]
+async_socket_impl_futures!([] async_net::unix::UnixStream);
+
+/// Runtime-neutral async IO implemented by API implementations.
+///
+/// Runtime IO traits (`tokio` and/or `futures`, depending on enabled features)
+/// and [`AsyncSocket`] are derived from this trait
+/// with [`spi_async_socket_impl!`](crate::spi_async_socket_impl).
+///
+/// This is meant to be used only by API implementations.
+pub trait AsyncIo {
+    /// Attempt to read into `buf`.
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempt to write `buf`.
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>>;
+
+    /// Attempt to write `bufs`.
+    ///
+    /// Default implementation writes the first non-empty buffer.
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let buf = bufs
+            .iter()
+            .find(|b| !b.is_empty())
+            .map_or(&[][..], |b| &**b);
+        self.poll_write(cx, buf)
+    }
+
+    /// Whether [`poll_write_vectored`](Self::poll_write_vectored) is efficient.
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
+    /// Attempt to flush.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+
+    /// Attempt to shut down the write side.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+/// Implement runtime IO traits and [`AsyncSocket`] for a type implementing [`AsyncIo`](crate::spi::AsyncIo).
+///
+/// `tokio` traits are implemented when `tls-api` is compiled with `runtime-tokio` feature,
+/// `futures` traits are implemented when `tls-api` is compiled with `runtime-futures-io`
+/// (implied by `runtime-async-std`) or without `runtime-tokio`.
+/// Both are implemented when both features are enabled.
+///
+/// Forms:
+/// * `spi_async_socket_impl!(MyStream<S>)` for a type parameterized by an [`AsyncSocket`]
+/// * `spi_async_socket_impl!([A: Bound, B] MyStream<A, B>)` for arbitrary generics
+/// * `spi_async_socket_impl!(MyStream)` for a type without generics
+///
+/// This is meant to be used only by API implementations.
+#[macro_export]
+macro_rules! spi_async_socket_impl {
+    ( [$($g:tt)*] $t:ty ) => {
+        $crate::spi_async_socket_impl_tokio!([$($g)*] $t);
+        $crate::spi_async_socket_impl_futures!([$($g)*] $t);
+
+        impl<$($g)*> $crate::AsyncSocket for $t {
+            fn poll_socket_read(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &mut [u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_read(self, cx, buf)
+            }
+
+            fn poll_socket_write(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &[u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write(self, cx, buf)
+            }
+
+            fn poll_socket_write_vectored(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                bufs: &[std::io::IoSlice<'_>],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write_vectored(self, cx, bufs)
+            }
+
+            fn is_socket_write_vectored(&self) -> bool {
+                <Self as $crate::spi::AsyncIo>::is_write_vectored(self)
+            }
+
+            fn poll_socket_flush(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_flush(self, cx)
+            }
+
+            fn poll_socket_shutdown(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_shutdown(self, cx)
+            }
         }
     };
     ( $t:ident <S> ) => {
-        impl<S: $crate::AsyncSocket> $crate::runtime::AsyncRead for $t<S> {
-            spi_async_socket_impl_delegate!("AsyncRead");
+        $crate::spi_async_socket_impl!([S: $crate::AsyncSocket] $t<S>);
+    };
+    ( $t:ty ) => {
+        $crate::spi_async_socket_impl!([] $t);
+    };
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! spi_async_socket_impl_tokio {
+    ( [$($g:tt)*] $t:ty ) => {
+        impl<$($g)*> $crate::runtime::tokio::AsyncRead for $t {
+            fn poll_read(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &mut $crate::runtime::tokio::ReadBuf<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                let read = std::task::ready!(<Self as $crate::spi::AsyncIo>::poll_read(
+                    self,
+                    cx,
+                    buf.initialize_unfilled()
+                ))?;
+                buf.advance(read);
+                std::task::Poll::Ready(Ok(()))
+            }
         }
 
-        impl<S: $crate::AsyncSocket> $crate::runtime::AsyncWrite for $t<S> {
-            spi_async_socket_impl_delegate!("AsyncWrite");
+        impl<$($g)*> $crate::runtime::tokio::AsyncWrite for $t {
+            fn poll_write(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &[u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write(self, cx, buf)
+            }
+
+            fn poll_write_vectored(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                bufs: &[std::io::IoSlice<'_>],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write_vectored(self, cx, bufs)
+            }
+
+            fn is_write_vectored(&self) -> bool {
+                <Self as $crate::spi::AsyncIo>::is_write_vectored(self)
+            }
+
+            fn poll_flush(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_flush(self, cx)
+            }
+
+            fn poll_shutdown(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_shutdown(self, cx)
+            }
         }
     };
-    ( $t:ty ) => {
-        impl $crate::runtime::AsyncRead for $t {
-            spi_async_socket_impl_delegate!("AsyncRead");
+}
+
+#[cfg(not(feature = "runtime-tokio"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! spi_async_socket_impl_tokio {
+    ( $($tt:tt)* ) => {};
+}
+
+#[cfg(any(feature = "runtime-futures-io", not(feature = "runtime-tokio")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! spi_async_socket_impl_futures {
+    ( [$($g:tt)*] $t:ty ) => {
+        impl<$($g)*> $crate::runtime::futures::AsyncRead for $t {
+            fn poll_read(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &mut [u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_read(self, cx, buf)
+            }
         }
 
-        impl $crate::runtime::AsyncWrite for $t {
-            spi_async_socket_impl_delegate!("AsyncWrite");
+        impl<$($g)*> $crate::runtime::futures::AsyncWrite for $t {
+            fn poll_write(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                buf: &[u8],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write(self, cx, buf)
+            }
+
+            fn poll_write_vectored(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+                bufs: &[std::io::IoSlice<'_>],
+            ) -> std::task::Poll<std::io::Result<usize>> {
+                <Self as $crate::spi::AsyncIo>::poll_write_vectored(self, cx, bufs)
+            }
+
+            fn poll_flush(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_flush(self, cx)
+            }
+
+            fn poll_close(
+                self: std::pin::Pin<&mut Self>,
+                cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                <Self as $crate::spi::AsyncIo>::poll_shutdown(self, cx)
+            }
         }
     };
 }
 
+#[cfg(not(any(feature = "runtime-futures-io", not(feature = "runtime-tokio"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! spi_async_socket_impl_futures {
+    ( $($tt:tt)* ) => {};
+}
+
 /// Delegate [`AsyncSocket`] implementation to the underlying socket.
 ///
+/// The type must provide `deref_pin_mut_for_impl_socket` and `deref_for_impl_socket`
+/// functions returning an [`AsyncSocket`]. This macro implements
+/// [`AsyncIo`](crate::spi::AsyncIo) by delegating to them,
+/// and runtime IO traits with [`spi_async_socket_impl!`](crate::spi_async_socket_impl).
+///
 /// This is meant to be used only by API implementations.
 ///
 /// # See also
 /// * [PR in tokio](https://github.com/tokio-rs/tokio/pull/3540)
 /// * [PR in futures](https://github.com/rust-lang/futures-rs/pull/2352)
-#[cfg(feature = "runtime-async-std")]
 #[macro_export]
 macro_rules! spi_async_socket_impl_delegate {
-    ( "AsyncRead" ) => {
+    ( "AsyncIo" ) => {
         fn poll_read(
             self: std::pin::Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
             buf: &mut [u8],
         ) -> std::task::Poll<std::io::Result<usize>> {
-            self.deref_pin_mut_for_impl_socket().poll_read(cx, buf)
+            $crate::AsyncSocket::poll_socket_read(self.deref_pin_mut_for_impl_socket(), cx, buf)
         }
-    };
-    ( "AsyncWrite" ) => {
+
         fn poll_write(
             self: std::pin::Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
             buf: &[u8],
         ) -> std::task::Poll<std::io::Result<usize>> {
-            self.deref_pin_mut_for_impl_socket().poll_write(cx, buf)
+            $crate::AsyncSocket::poll_socket_write(self.deref_pin_mut_for_impl_socket(), cx, buf)
+        }
+
+        fn poll_write_vectored(
+            self: std::pin::Pin<&mut Self>,
+            cx: &mut std::task::Context<'_>,
+            bufs: &[std::io::IoSlice<'_>],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            $crate::AsyncSocket::poll_socket_write_vectored(
+                self.deref_pin_mut_for_impl_socket(),
+                cx,
+                bufs,
+            )
+        }
+
+        fn is_write_vectored(&self) -> bool {
+            $crate::AsyncSocket::is_socket_write_vectored(self.deref_for_impl_socket())
         }
 
         fn poll_flush(
             self: std::pin::Pin<&mut Self>,
             cx: &mut std::task::Context<'_>,
         ) -> std::task::Poll<std::io::Result<()>> {
-            self.deref_pin_mut_for_impl_socket().poll_flush(cx)
+            $crate::AsyncSocket::poll_socket_flush(self.deref_pin_mut_for_impl_socket(), cx)
         }
 
-        fn poll_close(
+        fn poll_shutdown(
             self: std::pin::Pin<&mut Self>,
-            ctx: &mut std::task::Context<'_>,
+            cx: &mut std::task::Context<'_>,
         ) -> std::task::Poll<std::io::Result<()>> {
-            self.deref_pin_mut_for_impl_socket().poll_close(ctx)
+            $crate::AsyncSocket::poll_socket_shutdown(self.deref_pin_mut_for_impl_socket(), cx)
         }
     };
     ( $t:ident <S> ) => {
-        impl<S: $crate::AsyncSocket> $crate::runtime::AsyncRead for $t<S> {
-            spi_async_socket_impl_delegate!("AsyncRead");
+        impl<S: $crate::AsyncSocket> $crate::spi::AsyncIo for $t<S> {
+            $crate::spi_async_socket_impl_delegate!("AsyncIo");
         }
 
-        impl<S: $crate::AsyncSocket> $crate::runtime::AsyncWrite for $t<S> {
-            spi_async_socket_impl_delegate!("AsyncWrite");
-        }
+        $crate::spi_async_socket_impl!($t<S>);
     };
     ( $t:ty ) => {
-        impl $crate::runtime::AsyncRead for $t {
-            spi_async_socket_impl_delegate!("AsyncRead");
+        impl $crate::spi::AsyncIo for $t {
+            $crate::spi_async_socket_impl_delegate!("AsyncIo");
         }
 
-        impl $crate::runtime::AsyncWrite for $t {
-            spi_async_socket_impl_delegate!("AsyncWrite");
-        }
+        $crate::spi_async_socket_impl!($t);
     };
 }
@@ -0,0 +1,77 @@
+use std::fmt;
+use std::io;
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::runtime::futures::AsyncRead;
+use crate::runtime::futures::AsyncWrite;
+use crate::spi::AsyncIo;
+use crate::spi_async_socket_impl;
+
+/// Socket implementing `futures` IO traits as an [`AsyncSocket`](crate::AsyncSocket).
+///
+/// Sockets of supported runtimes (e. g. `async_std::net::TcpStream` or `smol::net::TcpStream`)
+/// implement [`AsyncSocket`](crate::AsyncSocket) directly,
+/// this wrapper is for other sockets implementing `futures` IO traits.
+#[derive(Debug)]
+pub struct FuturesIo<S>(pub S);
+
+impl<S> FuturesIo<S> {
+    /// Wrap.
+    pub fn new(socket: S) -> FuturesIo<S> {
+        FuturesIo(socket)
+    }
+
+    /// Get a reference to the wrapped socket.
+    pub fn get_ref(&self) -> &S {
+        &self.0
+    }
+
+    /// Get a mutable reference to the wrapped socket.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+
+    /// Unwrap.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncIo for FuturesIo<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+    }
+
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_close(cx)
+    }
+}
+
+spi_async_socket_impl!([S: AsyncRead + AsyncWrite + fmt::Debug + Unpin + Send + 'static] FuturesIo<S>);
@@ -0,0 +1,85 @@
+use std::fmt;
+use std::io;
+use std::io::IoSlice;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+
+use crate::runtime::tokio::AsyncRead;
+use crate::runtime::tokio::AsyncWrite;
+use crate::runtime::tokio::ReadBuf;
+use crate::spi::AsyncIo;
+use crate::spi_async_socket_impl;
+
+/// Socket implementing `tokio` IO traits as an [`AsyncSocket`](crate::AsyncSocket).
+///
+/// Sockets of supported runtimes (e. g. `tokio::net::TcpStream`)
+/// implement [`AsyncSocket`](crate::AsyncSocket) directly,
+/// this wrapper is for other sockets implementing `tokio` IO traits.
+#[derive(Debug)]
+pub struct TokioIo<S>(pub S);
+
+impl<S> TokioIo<S> {
+    /// Wrap.
+    pub fn new(socket: S) -> TokioIo<S> {
+        TokioIo(socket)
+    }
+
+    /// Get a reference to the wrapped socket.
+    pub fn get_ref(&self) -> &S {
+        &self.0
+    }
+
+    /// Get a mutable reference to the wrapped socket.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+
+    /// Unwrap.
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncIo for TokioIo<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut read_buf = ReadBuf::new(buf);
+        ready!(Pin::new(&mut self.get_mut().0).poll_read(cx, &mut read_buf))?;
+        Poll::Ready(Ok(read_buf.filled().len()))
+    }
+
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_shutdown(cx)
+    }
+}
+
+spi_async_socket_impl!([S: AsyncRead + AsyncWrite + fmt::Debug + Unpin + Send + 'static] TokioIo<S>);
@@ -2,6 +2,7 @@ use crate::assert_kinds::assert_socket;
 use crate::assert_send;
 use crate::socket::AsyncSocket;
 use crate::spi_async_socket_impl_delegate;
+use crate::ChannelBindingType;
 use crate::ImplInfo;
 use crate::TlsStreamDyn;
 use crate::TlsStreamWithSocket;
@@ -16,6 +17,12 @@ fn _assert_kinds() {
     assert_socket::<TlsStream>();
 }
 
+#[cfg(all(feature = "runtime-tokio", feature = "runtime-futures-io"))]
+fn _assert_both_runtimes() {
+    crate::assert_kinds::assert_tokio_io::<TlsStream>();
+    crate::assert_kinds::assert_futures_io::<TlsStream>();
+}
+
 impl TlsStream {
     /// Wrap.
     pub fn new<S: AsyncSocket>(stream: TlsStreamWithSocket<S>) -> TlsStream {
@@ -36,6 +43,27 @@ impl TlsStreamDyn for TlsStream {
         self.0.get_alpn_protocol()
     }
 
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.0.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.0.channel_binding(kind)
+    }
+
     fn impl_info(&self) -> ImplInfo {
         self.0.impl_info()
     }
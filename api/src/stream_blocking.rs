@@ -0,0 +1,125 @@
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+
+use crate::assert_send;
+use crate::ChannelBindingType;
+use crate::ImplInfo;
+
+/// Trait implemented by all blocking TLS stream objects.
+///
+/// Blocking counterpart of [`TlsStreamDyn`](crate::TlsStreamDyn),
+/// see it for documentation of the introspection functions.
+pub trait TlsStreamBlockingDyn: Read + Write + fmt::Debug + Send + 'static {
+    /// Get negotiated ALPN protocol negotiated.
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Server name indication (SNI).
+    fn server_name(&self) -> anyhow::Result<Option<String>>;
+
+    /// DER-encoded OCSP response stapled by the server.
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Export keying material.
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Channel binding data of this connection.
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>>;
+
+    /// Implementation info for this stream (e. g. which crate provides it).
+    fn impl_info(&self) -> ImplInfo;
+
+    /// Send `close_notify` (if implementation supports it) and shut down
+    /// the write side of the socket.
+    fn shutdown(&mut self) -> io::Result<()>;
+
+    /// Get the underlying socket.
+    fn get_socket_mut(&mut self) -> &mut TcpStream;
+
+    /// Get the underlying socket.
+    fn get_socket_ref(&self) -> &TcpStream;
+}
+
+/// Blocking TLS stream over [`TcpStream`].
+///
+/// Returned by [`TlsConnector::connect_blocking`](crate::TlsConnector::connect_blocking)
+/// and [`TlsAcceptor::accept_blocking`](crate::TlsAcceptor::accept_blocking).
+#[derive(Debug)]
+pub struct TlsStreamBlocking(Box<dyn TlsStreamBlockingDyn>);
+
+fn _assert_kinds() {
+    assert_send::<TlsStreamBlocking>();
+}
+
+impl TlsStreamBlocking {
+    /// Wrap.
+    pub fn new<S: TlsStreamBlockingDyn>(stream: S) -> TlsStreamBlocking {
+        TlsStreamBlocking(Box::new(stream))
+    }
+}
+
+impl Read for TlsStreamBlocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStreamBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlocking {
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.get_alpn_protocol()
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.0.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.0.channel_binding(kind)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.0.impl_info()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        self.0.shutdown()
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        self.0.get_socket_mut()
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        self.0.get_socket_ref()
+    }
+}
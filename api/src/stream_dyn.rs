@@ -1,13 +1,48 @@
+use crate::runtime::RuntimeIo;
 use crate::AsyncSocket;
+use crate::ChannelBindingType;
 use crate::ImplInfo;
 
 /// Trait implemented by all `TlsStream` objects.
 ///
-/// Provide access to some TLS stream properties (only ALPN at the moment.)
-pub trait TlsStreamDyn: AsyncSocket {
+/// Provide access to some TLS stream properties.
+pub trait TlsStreamDyn: AsyncSocket + RuntimeIo {
     /// Get negotiated ALPN protocol negotiated.
     fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>>;
 
+    /// Server name indication (SNI).
+    ///
+    /// On server side, this is the name requested by the client.
+    /// On client side, this is the name sent to the server,
+    /// i. e. the domain passed to `connect` unless it is an IP address.
+    ///
+    /// `None` if SNI is not used.
+    fn server_name(&self) -> anyhow::Result<Option<String>>;
+
+    /// DER-encoded OCSP response stapled by the server.
+    ///
+    /// Only available on client side, when stapling was requested with
+    /// [`TlsConnectorBuilder::set_ocsp_stapling`](crate::TlsConnectorBuilder::set_ocsp_stapling).
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Export keying material ([RFC 5705](https://www.rfc-editor.org/rfc/rfc5705),
+    /// [RFC 8446 section 7.5](https://www.rfc-editor.org/rfc/rfc8446#section-7.5)).
+    ///
+    /// Fails with [`ChannelBindingError::ExportNotSupported`](crate::ChannelBindingError::ExportNotSupported)
+    /// if implementation cannot export keying material.
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>>;
+
+    /// Channel binding data of this connection, e. g. for SCRAM `-PLUS` mechanisms.
+    ///
+    /// Fails with [`ChannelBindingError`](crate::ChannelBindingError)
+    /// if implementation cannot provide requested binding.
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>>;
+
     /// Implementation info for this stream (e. g. which crate provides it).
     fn impl_info(&self) -> ImplInfo;
 
@@ -0,0 +1,765 @@
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::task::ready;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use crate::spi::AsyncIo;
+use crate::spi::TlsStreamWithUpcastDyn;
+use crate::spi_async_socket_impl;
+use crate::AsyncSocket;
+use crate::ChannelBindingType;
+use crate::ImplInfo;
+use crate::TlsConnectionDyn;
+use crate::TlsHandshakeState;
+use crate::TlsStreamDyn;
+use crate::TlsStreamWithSocketDyn;
+
+/// Size of the buffers for ciphertext read from and written to the socket.
+const BUF_SIZE: usize = 16 * 1024;
+
+/// Ciphertext buffer: allocated once, filled only when empty
+/// and consumed from the front by moving the offset.
+#[derive(Default)]
+struct CiphertextBuf {
+    data: Box<[u8]>,
+    start: usize,
+    end: usize,
+}
+
+impl fmt::Debug for CiphertextBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CiphertextBuf")
+            .field("len", &self.filled().len())
+            .finish()
+    }
+}
+
+impl CiphertextBuf {
+    fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    fn filled(&self) -> &[u8] {
+        &self.data[self.start..self.end]
+    }
+
+    /// Whole buffer to be filled, allocated on first use. Buffer must be empty.
+    fn unfilled(&mut self) -> &mut [u8] {
+        debug_assert!(self.is_empty());
+        if self.data.is_empty() {
+            self.data = vec![0; BUF_SIZE].into_boxed_slice();
+        }
+        &mut self.data
+    }
+
+    fn set_filled(&mut self, len: usize) {
+        self.start = 0;
+        self.end = len;
+    }
+
+    fn consume(&mut self, len: usize) {
+        self.start += len;
+        debug_assert!(self.start <= self.end);
+    }
+}
+
+/// Result of [`TlsStreamOverConnection::poll_read_ciphertext`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Feed {
+    /// Some ciphertext was fed to the connection.
+    Fed,
+    /// Connection does not accept more ciphertext until plaintext is read.
+    Full,
+    /// Socket returned EOF.
+    Eof,
+}
+
+/// Connection accepts no ciphertext and has no plaintext to read.
+fn stalled() -> io::Error {
+    io::Error::other("TLS connection does not accept more data")
+}
+
+/// Async TLS stream: [`TlsConnectionDyn`] driven by the async socket.
+///
+/// Used in API implementations.
+#[derive(Debug)]
+pub struct TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    socket: A,
+    /// TLS connection.
+    pub conn: C,
+    /// Ciphertext read from the socket, but not yet consumed by the connection.
+    incoming: CiphertextBuf,
+    /// Ciphertext taken from the connection, but not yet written to the socket.
+    outgoing: CiphertextBuf,
+    /// Socket returned EOF.
+    eof: bool,
+    close_notify_sent: bool,
+    /// Write waiting for plaintext to be read, so the connection accepts ciphertext again.
+    write_waker: Option<Waker>,
+}
+
+impl<A, C> TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    /// Constructor. Handshake is not performed.
+    pub fn new(socket: A, conn: C) -> TlsStreamOverConnection<A, C> {
+        TlsStreamOverConnection {
+            socket,
+            conn,
+            incoming: CiphertextBuf::default(),
+            outgoing: CiphertextBuf::default(),
+            eof: false,
+            close_notify_sent: false,
+            write_waker: None,
+        }
+    }
+
+    /// Perform the handshake, the stream is ready to send and receive after that.
+    pub async fn handshake(mut self) -> anyhow::Result<Self> {
+        std::future::poll_fn(|cx| self.poll_handshake(cx)).await?;
+        Ok(self)
+    }
+
+    /// Read ciphertext from the socket (unless there is some left from the previous read)
+    /// and feed it to the connection.
+    fn poll_read_ciphertext(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Feed>> {
+        if self.incoming.is_empty() {
+            let unfilled = self.incoming.unfilled();
+            let read = ready!(Pin::new(&mut self.socket).poll_socket_read(cx, unfilled))?;
+            if read == 0 {
+                return Poll::Ready(Ok(Feed::Eof));
+            }
+            self.incoming.set_filled(read);
+        }
+
+        match self.conn.feed_ciphertext(self.incoming.filled()) {
+            Ok(0) => Poll::Ready(Ok(Feed::Full)),
+            Ok(fed) => {
+                self.incoming.consume(fed);
+                Poll::Ready(Ok(Feed::Fed))
+            }
+            Err(e) => {
+                // Try to send the alert, the error is more important
+                let _ = self.poll_write_ciphertext(cx);
+                Poll::Ready(Err(e))
+            }
+        }
+    }
+
+    /// Write all ciphertext produced by the connection to the socket.
+    fn poll_write_ciphertext(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            if self.outgoing.is_empty() {
+                if !self.conn.wants_write() {
+                    return Poll::Ready(Ok(()));
+                }
+                let taken = self.conn.take_ciphertext(self.outgoing.unfilled())?;
+                self.outgoing.set_filled(taken);
+                if taken == 0 {
+                    return Poll::Ready(Ok(()));
+                }
+            }
+
+            let filled = self.outgoing.filled();
+            match ready!(Pin::new(&mut self.socket).poll_socket_write(cx, filled))? {
+                0 => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                written => self.outgoing.consume(written),
+            }
+        }
+    }
+
+    fn poll_handshake(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            ready!(self.poll_write_ciphertext(cx))?;
+            if self.conn.handshake_state() == TlsHandshakeState::Complete {
+                return Poll::Ready(Ok(()));
+            }
+            match ready!(self.poll_read_ciphertext(cx))? {
+                Feed::Fed => {}
+                // No plaintext to read before the handshake is complete
+                Feed::Full => return Poll::Ready(Err(stalled())),
+                Feed::Eof => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "EOF during TLS handshake",
+                    )))
+                }
+            }
+        }
+    }
+
+    fn poll_read_plaintext(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            match self.conn.read_plaintext(buf) {
+                Ok(read) => {
+                    // Connection may accept ciphertext again
+                    if let Some(waker) = self.write_waker.take() {
+                        waker.wake();
+                    }
+                    return Poll::Ready(Ok(read));
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+
+            // Records generated while reading (e. g. key update) are sent opportunistically,
+            // if the socket is not writable, they are sent with the next write.
+            if let Poll::Ready(Err(e)) = self.poll_write_ciphertext(cx) {
+                return Poll::Ready(Err(e));
+            }
+
+            // Peer closed the connection without sending `close_notify`
+            if self.eof {
+                if self.conn.requires_close_notify() {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "peer closed connection without sending TLS close_notify",
+                    )));
+                }
+                return Poll::Ready(Ok(0));
+            }
+
+            match ready!(self.poll_read_ciphertext(cx))? {
+                Feed::Fed => {}
+                // Plaintext was just read, and there is none
+                Feed::Full => return Poll::Ready(Err(stalled())),
+                Feed::Eof => self.eof = true,
+            }
+        }
+    }
+
+    fn poll_write_plaintext(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut written = 0;
+        loop {
+            // Connection may buffer limited amount of data
+            match self.conn.write_plaintext(&buf[written..]) {
+                Ok(accepted) => written += accepted,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    let had_ciphertext = self.conn.wants_write() || !self.outgoing.is_empty();
+                    ready!(self.poll_write_ciphertext(cx))?;
+                    if written != 0 {
+                        return Poll::Ready(Ok(written));
+                    }
+                    if had_ciphertext {
+                        continue;
+                    }
+                    // Connection needs records from the peer first (e. g. renegotiation)
+                    let feed = if self.eof {
+                        Feed::Eof
+                    } else {
+                        ready!(self.poll_read_ciphertext(cx))?
+                    };
+                    match feed {
+                        Feed::Fed => continue,
+                        // Wait for the reader to take the plaintext
+                        Feed::Full => {
+                            self.write_waker = Some(cx.waker().clone());
+                            return Poll::Pending;
+                        }
+                        Feed::Eof => {
+                            self.eof = true;
+                            return Poll::Ready(Err(io::Error::new(
+                                io::ErrorKind::UnexpectedEof,
+                                "peer closed connection during TLS renegotiation",
+                            )));
+                        }
+                    }
+                }
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+
+            match self.poll_write_ciphertext(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending if written == 0 => return Poll::Pending,
+                Poll::Pending => return Poll::Ready(Ok(written)),
+            }
+
+            if written == buf.len() {
+                return Poll::Ready(Ok(written));
+            }
+        }
+    }
+
+    fn poll_flush_impl(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_ciphertext(cx))?;
+        Pin::new(&mut self.socket).poll_socket_flush(cx)
+    }
+
+    /// Send `close_notify` and flush.
+    fn poll_close_notify(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.close_notify_sent {
+            self.conn.send_close_notify()?;
+            self.close_notify_sent = true;
+        }
+        self.poll_flush_impl(cx)
+    }
+}
+
+impl<A, C> AsyncIo for TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_read_plaintext(cx, buf)
+    }
+
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_write_plaintext(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_flush_impl(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let self_mut = self.get_mut();
+        ready!(self_mut.poll_close_notify(cx))?;
+        Pin::new(&mut self_mut.socket).poll_socket_shutdown(cx)
+    }
+}
+
+spi_async_socket_impl!([A: AsyncSocket, C: TlsConnectionDyn + Unpin] TlsStreamOverConnection<A, C>);
+
+impl<A, C> TlsStreamDyn for TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.conn.get_alpn_protocol()
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.conn.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.conn.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.conn.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.conn.channel_binding(kind)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.conn.impl_info()
+    }
+
+    fn get_socket_dyn_mut(&mut self) -> &mut dyn AsyncSocket {
+        &mut self.socket
+    }
+
+    fn get_socket_dyn_ref(&self) -> &dyn AsyncSocket {
+        &self.socket
+    }
+}
+
+impl<A, C> TlsStreamWithSocketDyn<A> for TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    fn get_socket_mut(&mut self) -> &mut A {
+        &mut self.socket
+    }
+
+    fn get_socket_ref(&self) -> &A {
+        &self.socket
+    }
+}
+
+impl<A, C> TlsStreamWithUpcastDyn<A> for TlsStreamOverConnection<A, C>
+where
+    A: AsyncSocket,
+    C: TlsConnectionDyn + Unpin,
+{
+    fn upcast_box(self: Box<Self>) -> Box<dyn TlsStreamDyn> {
+        self
+    }
+}
+
+/// Implement wrapper for [`TlsStreamOverConnection`].
+#[macro_export]
+macro_rules! spi_tls_stream_over_connection_wrapper {
+    ( $(#[$attr:meta])* $t:ident, $c:ty ) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $t<A: tls_api::AsyncSocket>(
+            pub(crate) tls_api::spi::TlsStreamOverConnection<A, $c>,
+        );
+
+        impl<A: tls_api::AsyncSocket> $t<A> {
+            fn deref_pin_mut_for_impl_socket(
+                self: std::pin::Pin<&mut Self>,
+            ) -> std::pin::Pin<&mut tls_api::spi::TlsStreamOverConnection<A, $c>> {
+                std::pin::Pin::new(&mut self.get_mut().0)
+            }
+
+            fn deref_for_impl_socket(&self) -> &tls_api::spi::TlsStreamOverConnection<A, $c> {
+                &self.0
+            }
+        }
+
+        tls_api::spi_async_socket_impl_delegate!($t<S>);
+
+        impl<A: tls_api::AsyncSocket> tls_api::TlsStreamDyn for $t<A> {
+            fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+                self.0.get_alpn_protocol()
+            }
+
+            fn server_name(&self) -> anyhow::Result<Option<String>> {
+                self.0.server_name()
+            }
+
+            fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+                self.0.ocsp_response()
+            }
+
+            fn export_keying_material(
+                &self,
+                label: &str,
+                context: Option<&[u8]>,
+                len: usize,
+            ) -> anyhow::Result<Vec<u8>> {
+                self.0.export_keying_material(label, context, len)
+            }
+
+            fn channel_binding(
+                &self,
+                kind: tls_api::ChannelBindingType,
+            ) -> anyhow::Result<Vec<u8>> {
+                self.0.channel_binding(kind)
+            }
+
+            fn impl_info(&self) -> tls_api::ImplInfo {
+                self.0.impl_info()
+            }
+
+            fn get_socket_dyn_mut(&mut self) -> &mut dyn tls_api::AsyncSocket {
+                self.0.get_socket_dyn_mut()
+            }
+
+            fn get_socket_dyn_ref(&self) -> &dyn tls_api::AsyncSocket {
+                self.0.get_socket_dyn_ref()
+            }
+        }
+
+        impl<A: tls_api::AsyncSocket> tls_api::TlsStreamWithSocketDyn<A> for $t<A> {
+            fn get_socket_mut(&mut self) -> &mut A {
+                self.0.get_socket_mut()
+            }
+
+            fn get_socket_ref(&self) -> &A {
+                self.0.get_socket_ref()
+            }
+        }
+
+        impl<A: tls_api::AsyncSocket> tls_api::spi::TlsStreamWithUpcastDyn<A> for $t<A> {
+            fn upcast_box(self: Box<Self>) -> Box<dyn tls_api::TlsStreamDyn> {
+                self
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ChannelBindingError;
+
+    /// Socket over memory buffers, reads are pending until data is added.
+    #[derive(Debug, Default)]
+    struct MemSocket {
+        incoming: Vec<u8>,
+        written: Vec<u8>,
+    }
+
+    impl AsyncIo for MemSocket {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let self_mut = self.get_mut();
+            if self_mut.incoming.is_empty() {
+                return Poll::Pending;
+            }
+            let len = buf.len().min(self_mut.incoming.len());
+            buf[..len].copy_from_slice(&self_mut.incoming[..len]);
+            self_mut.incoming.drain(..len);
+            Poll::Ready(Ok(len))
+        }
+
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            self.get_mut().written.extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    spi_async_socket_impl!([] MemSocket);
+
+    /// Connection which cannot accept plaintext until a record from the peer
+    /// is received, like `openssl` during renegotiation.
+    #[derive(Debug, Default)]
+    struct RenegotiatingConnection {
+        renegotiated: bool,
+        outgoing: Vec<u8>,
+    }
+
+    impl TlsConnectionDyn for RenegotiatingConnection {
+        fn handshake_state(&self) -> TlsHandshakeState {
+            TlsHandshakeState::Complete
+        }
+
+        fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.renegotiated = true;
+            Ok(data.len())
+        }
+
+        fn wants_write(&self) -> bool {
+            !self.outgoing.is_empty()
+        }
+
+        fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.outgoing.len());
+            buf[..len].copy_from_slice(&self.outgoing[..len]);
+            self.outgoing.drain(..len);
+            Ok(len)
+        }
+
+        fn read_plaintext(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::WouldBlock.into())
+        }
+
+        fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if !self.renegotiated {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            self.outgoing.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn send_close_notify(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn server_name(&self) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn export_keying_material(
+            &self,
+            _label: &str,
+            _context: Option<&[u8]>,
+            _len: usize,
+        ) -> anyhow::Result<Vec<u8>> {
+            Err(ChannelBindingError::ExportNotSupported("test").into())
+        }
+
+        fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+            Err(ChannelBindingError::NotSupported(kind, "test").into())
+        }
+
+        fn impl_info(&self) -> ImplInfo {
+            ImplInfo::default()
+        }
+    }
+
+    /// Connection which buffers one byte of plaintext,
+    /// and does not accept ciphertext until it is read.
+    /// Plaintext can be written after two bytes of ciphertext are received.
+    #[derive(Debug, Default)]
+    struct OneByteConnection {
+        plaintext: Option<u8>,
+        received: usize,
+    }
+
+    impl TlsConnectionDyn for OneByteConnection {
+        fn handshake_state(&self) -> TlsHandshakeState {
+            TlsHandshakeState::Complete
+        }
+
+        fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+            match (self.plaintext, data.first()) {
+                (None, Some(&b)) => {
+                    self.plaintext = Some(b);
+                    self.received += 1;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+
+        fn wants_write(&self) -> bool {
+            false
+        }
+
+        fn take_ciphertext(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Ok(0)
+        }
+
+        fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.plaintext.take() {
+                Some(b) => {
+                    buf[0] = b;
+                    Ok(1)
+                }
+                None => Err(io::ErrorKind::WouldBlock.into()),
+            }
+        }
+
+        fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.received < 2 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+            Ok(buf.len())
+        }
+
+        fn send_close_notify(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn server_name(&self) -> anyhow::Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+            Ok(None)
+        }
+
+        fn export_keying_material(
+            &self,
+            _label: &str,
+            _context: Option<&[u8]>,
+            _len: usize,
+        ) -> anyhow::Result<Vec<u8>> {
+            Err(ChannelBindingError::ExportNotSupported("test").into())
+        }
+
+        fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+            Err(ChannelBindingError::NotSupported(kind, "test").into())
+        }
+
+        fn impl_info(&self) -> ImplInfo {
+            ImplInfo::default()
+        }
+    }
+
+    #[test]
+    fn write_waits_for_peer_records() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut stream =
+            TlsStreamOverConnection::new(MemSocket::default(), RenegotiatingConnection::default());
+
+        assert!(stream.poll_write_plaintext(&mut cx, b"hello").is_pending());
+
+        stream.socket.incoming.extend_from_slice(b"record");
+        match stream.poll_write_plaintext(&mut cx, b"hello") {
+            Poll::Ready(Ok(5)) => {}
+            r => panic!("{:?}", r),
+        }
+        assert_eq!(b"hello", &stream.socket.written[..]);
+    }
+
+    #[test]
+    fn feed_after_plaintext_read() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut stream =
+            TlsStreamOverConnection::new(MemSocket::default(), OneByteConnection::default());
+        stream.socket.incoming.extend_from_slice(b"abc");
+
+        let mut received = Vec::new();
+        let mut buf = [0; 10];
+        while received.len() < 3 {
+            match stream.poll_read_plaintext(&mut cx, &mut buf) {
+                Poll::Ready(Ok(read)) => received.extend_from_slice(&buf[..read]),
+                r => panic!("{:?}", r),
+            }
+        }
+        assert_eq!(b"abc", &received[..]);
+        assert!(stream.poll_read_plaintext(&mut cx, &mut buf).is_pending());
+    }
+
+    #[test]
+    fn write_waits_for_plaintext_read() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut stream =
+            TlsStreamOverConnection::new(MemSocket::default(), OneByteConnection::default());
+        stream.socket.incoming.extend_from_slice(b"ab");
+
+        assert!(stream.poll_write_plaintext(&mut cx, b"x").is_pending());
+        assert!(stream.write_waker.is_some());
+
+        let mut buf = [0; 10];
+        match stream.poll_read_plaintext(&mut cx, &mut buf) {
+            Poll::Ready(Ok(1)) => assert_eq!(b'a', buf[0]),
+            r => panic!("{:?}", r),
+        }
+        assert!(stream.write_waker.is_none());
+
+        match stream.poll_write_plaintext(&mut cx, b"x") {
+            Poll::Ready(Ok(1)) => {}
+            r => panic!("{:?}", r),
+        }
+    }
+}
@@ -5,11 +5,10 @@ use std::pin::Pin;
 
 use crate::assert_kinds::assert_socket;
 use crate::assert_send;
-use crate::runtime::AsyncRead;
-use crate::runtime::AsyncWrite;
 use crate::socket::AsyncSocket;
 use crate::spi::TlsStreamWithUpcastDyn;
 use crate::spi_async_socket_impl_delegate;
+use crate::ChannelBindingType;
 use crate::ImplInfo;
 use crate::TlsStream;
 use crate::TlsStreamDyn;
@@ -17,25 +16,28 @@ use crate::TlsStreamWithSocketDyn;
 
 /// TLS stream object returned by `connect_with_socket` and `accept_with_socket` operations.
 ///
-/// Since Rust has no HKT, it is not possible to declare something like
+/// This is a box to concrete TLS implementation stream,
+/// so each operation perform a virtual call (which is not a big deal for sockets).
 ///
-/// ```ignore
-/// trait TlsConnector {
-///     type <S> TlsStream<S> : TlsStreamImpl;
-/// }
-/// ```
-///
-/// So `TlsStream` is actually a box to concrete TLS implementation.
-/// So each operation perform a virtual call (which is not a big deal for sockets).
+/// [`TlsConnector::connect_static`](crate::TlsConnector::connect_static) and
+/// [`TlsAcceptor::accept_static`](crate::TlsAcceptor::accept_static)
+/// return the implementation stream
+/// [`TlsConnector::Stream<S>`](crate::TlsConnector::Stream) without boxing.
 ///
 /// This type is parameterized by socket type, [`TlsStream`] is simpler version of this stream.
 pub struct TlsStreamWithSocket<S: AsyncSocket>(pub(crate) Box<dyn TlsStreamWithUpcastDyn<S>>);
 
-fn _assert_kinds<S: AsyncRead + AsyncWrite + fmt::Debug + Unpin + Send + 'static>() {
+fn _assert_kinds<S: AsyncSocket>() {
     assert_send::<TlsStreamWithSocket<S>>();
     assert_socket::<TlsStreamWithSocket<S>>();
 }
 
+#[cfg(all(feature = "runtime-tokio", feature = "runtime-futures-io"))]
+fn _assert_both_runtimes<S: AsyncSocket>() {
+    crate::assert_kinds::assert_tokio_io::<TlsStreamWithSocket<S>>();
+    crate::assert_kinds::assert_futures_io::<TlsStreamWithSocket<S>>();
+}
+
 impl<S: AsyncSocket> fmt::Debug for TlsStreamWithSocket<S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("TlsStream").field(&self.0).finish()
@@ -47,6 +49,27 @@ impl<S: AsyncSocket> TlsStreamDyn for TlsStreamWithSocket<S> {
         self.0.get_alpn_protocol()
     }
 
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.0.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.0.channel_binding(kind)
+    }
+
     fn impl_info(&self) -> ImplInfo {
         self.0.impl_info()
     }
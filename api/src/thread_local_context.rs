@@ -1,37 +1,131 @@
-use std::cell::Cell;
-use std::ptr;
+//! Pass the future context to sync IO callbacks through a thread local.
+//!
+//! Sync TLS implementations (`openssl`, `native-tls`, `security-framework`)
+//! call `Read`/`Write` on the socket wrapper without a way to pass
+//! the [`Context`] explicitly. So the future saves the context with [`save_context`]
+//! before calling the implementation, and the socket wrapper
+//! fetches it with [`restore_context`].
+//!
+//! The thread local holds a clone of the [`Waker`], not a pointer to the context,
+//! so there is no `unsafe` and no way to observe a dangling context.
+
+use std::cell::RefCell;
+use std::io;
 use std::task::Context;
+use std::task::Waker;
 
 thread_local! {
-    pub static CONTEXT: Cell<*mut ()> = Cell::new(ptr::null_mut());
+    static WAKER: RefCell<Option<Waker>> = const { RefCell::new(None) };
 }
 
-struct RestoreOnDrop(*mut ());
+struct RestoreOnDrop(Option<Waker>);
 
 impl Drop for RestoreOnDrop {
     fn drop(&mut self) {
-        CONTEXT.with(|cell| {
-            cell.set(self.0);
-        })
+        let prev = self.0.take();
+        WAKER.with(|cell| *cell.borrow_mut() = prev);
     }
 }
 
-/// Store future context in the thread local.
-pub fn save_context<R>(context: &mut Context<'_>, callback: impl FnOnce() -> R) -> R {
-    CONTEXT.with(|cell| {
-        let prev = cell.replace(context as *mut Context<'_> as *mut ());
-        let _restore_on_drop = RestoreOnDrop(prev);
-        callback()
-    })
+/// Store future context in the thread local for the duration of the callback.
+///
+/// Calls may be nested, the previous context is restored when the callback returns
+/// (or panics).
+pub fn save_context<R>(context: &Context<'_>, callback: impl FnOnce() -> R) -> R {
+    let prev = WAKER.with(|cell| cell.replace(Some(context.waker().clone())));
+    let _restore_on_drop = RestoreOnDrop(prev);
+    callback()
 }
 
 /// Fetch future context from the thread local.
-pub fn restore_context<R>(callback: impl FnOnce(&mut Context<'_>) -> R) -> R {
-    CONTEXT.with(|cell| {
-        let context = cell.replace(ptr::null_mut());
-        let _restore_on_drop = RestoreOnDrop(context);
-        assert!(!context.is_null());
-        cell.set(ptr::null_mut());
-        callback(unsafe { &mut *(context as *mut Context) })
-    })
+///
+/// When called outside of [`save_context`] (for example, sync IO is performed
+/// from an implementation callback outside of a poll), there is no task to wake up,
+/// so an error is logged and [`WouldBlock`](io::ErrorKind::WouldBlock) is returned
+/// without invoking the callback.
+pub fn restore_context<R>(
+    callback: impl FnOnce(&mut Context<'_>) -> io::Result<R>,
+) -> io::Result<R> {
+    let waker = WAKER.with(|cell| cell.borrow().clone());
+    match waker {
+        Some(waker) => callback(&mut Context::from_waker(&waker)),
+        None => {
+            log::error!("sync IO on async socket outside of poll; returning WouldBlock");
+            Err(io::Error::from(io::ErrorKind::WouldBlock))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn counting_waker() -> (Arc<CountingWaker>, Waker) {
+        let counter = Arc::new(CountingWaker::default());
+        (counter.clone(), Waker::from(counter))
+    }
+
+    fn wake_saved() -> io::Result<()> {
+        restore_context(|cx| {
+            cx.waker().wake_by_ref();
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn outside_of_save_is_would_block() {
+        let err =
+            restore_context(|_| -> io::Result<()> { panic!("must not be called") }).unwrap_err();
+        assert_eq!(io::ErrorKind::WouldBlock, err.kind());
+    }
+
+    #[test]
+    fn wakes_saved_waker() {
+        let (counter, waker) = counting_waker();
+        save_context(&Context::from_waker(&waker), || wake_saved().unwrap());
+        assert_eq!(1, counter.0.load(Ordering::SeqCst));
+        // Context is not leaked after return
+        assert_eq!(io::ErrorKind::WouldBlock, wake_saved().unwrap_err().kind());
+    }
+
+    #[test]
+    fn nested() {
+        let (outer_counter, outer) = counting_waker();
+        let (inner_counter, inner) = counting_waker();
+        save_context(&Context::from_waker(&outer), || {
+            save_context(&Context::from_waker(&inner), || wake_saved().unwrap());
+            wake_saved().unwrap();
+        });
+        assert_eq!(1, outer_counter.0.load(Ordering::SeqCst));
+        assert_eq!(1, inner_counter.0.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn restored_after_panic() {
+        let (_counter, waker) = counting_waker();
+        let r = panic::catch_unwind(|| {
+            save_context(&Context::from_waker(&waker), || panic!("callback panic"))
+        });
+        assert!(r.is_err());
+        assert_eq!(io::ErrorKind::WouldBlock, wake_saved().unwrap_err().kind());
+    }
 }
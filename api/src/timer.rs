@@ -0,0 +1,146 @@
+//! Runtime-independent timer: one thread wakes tasks at their deadlines.
+//!
+//! Used by implementations (e. g. for DTLS retransmissions) and by fault injection,
+//! which cannot depend on timers of a particular runtime.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::task::Waker;
+use std::thread;
+use std::time::Instant;
+
+enum Command {
+    /// Set or replace the wakeup of the timer.
+    Set(u64, Instant, Waker),
+    /// Timer is dropped.
+    Cancel(u64),
+}
+
+static TIMER: OnceLock<Mutex<mpsc::Sender<Command>>> = OnceLock::new();
+
+fn run(rx: mpsc::Receiver<Command>) {
+    let mut pending: HashMap<u64, (Instant, Waker)> = HashMap::new();
+    loop {
+        let now = Instant::now();
+        pending.retain(|_, (deadline, waker)| {
+            if *deadline <= now {
+                waker.wake_by_ref();
+                false
+            } else {
+                true
+            }
+        });
+        let command = match pending.values().map(|(deadline, _)| *deadline).min() {
+            Some(next) => match rx.recv_timeout(next.saturating_duration_since(now)) {
+                Ok(command) => command,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            },
+            None => match rx.recv() {
+                Ok(command) => command,
+                Err(mpsc::RecvError) => return,
+            },
+        };
+        match command {
+            Command::Set(id, deadline, waker) => {
+                pending.insert(id, (deadline, waker));
+            }
+            Command::Cancel(id) => {
+                pending.remove(&id);
+            }
+        }
+    }
+}
+
+fn send(command: Command) {
+    let sender = TIMER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("tls-api-timer".to_owned())
+            .spawn(move || run(rx))
+            .expect("spawn timer thread");
+        Mutex::new(tx)
+    });
+    // Thread never exits while the sender is alive
+    let _ = sender.lock().unwrap().send(command);
+}
+
+/// Timer with at most one pending wakeup,
+/// each [`wake_at`](Timer::wake_at) replaces the previous one.
+///
+/// Pending wakeup is cancelled when the timer is dropped.
+#[derive(Debug)]
+pub struct Timer {
+    id: u64,
+}
+
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new()
+    }
+}
+
+impl Timer {
+    /// Create a timer without pending wakeup.
+    pub fn new() -> Timer {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Timer {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Wake the task at given instant.
+    pub fn wake_at(&self, deadline: Instant, waker: Waker) {
+        send(Command::Set(self.id, deadline, waker));
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if TIMER.get().is_some() {
+            send(Command::Cancel(self.id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::time::Duration;
+
+    use super::*;
+
+    struct SendWaker(Mutex<mpsc::Sender<()>>);
+
+    impl Wake for SendWaker {
+        fn wake(self: Arc<Self>) {
+            let _ = self.0.lock().unwrap().send(());
+        }
+    }
+
+    fn channel_waker() -> (Waker, mpsc::Receiver<()>) {
+        let (tx, rx) = mpsc::channel();
+        (Waker::from(Arc::new(SendWaker(Mutex::new(tx)))), rx)
+    }
+
+    #[test]
+    fn wake_replace_cancel() {
+        let (waker, rx) = channel_waker();
+        let timer = Timer::new();
+        timer.wake_at(Instant::now() + Duration::from_secs(3600), waker.clone());
+        timer.wake_at(Instant::now() + Duration::from_millis(10), waker);
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+
+        let (waker, rx) = channel_waker();
+        let timer = Timer::new();
+        timer.wake_at(Instant::now() + Duration::from_millis(50), waker);
+        drop(timer);
+        assert!(rx.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+}
@@ -37,7 +37,7 @@ fn steps(rt: &str, os: Os, channel: RustToolchain) -> Vec<Step> {
         if os == WINDOWS {
             match c.as_str() {
                 // TODO: figure out how to enable openssl on windows
-                "examples" | "impl-openssl" | "interop" => continue,
+                "examples" | "impl-boring" | "impl-openssl" | "impl-s2n" | "interop" => continue,
                 _ => {}
             }
         }
@@ -51,12 +51,53 @@ fn steps(rt: &str, os: Os, channel: RustToolchain) -> Vec<Step> {
         let mut step = cargo_test(&format!("cargo test {}", c), &args);
         step.timeout_minutes = Some(5);
         r.push(step);
+        if c == "impl-default" {
+            // Default step tests the fallback to stub on platforms other than Apple
+            for backend in &["rustls", "openssl"] {
+                if os == WINDOWS && *backend == "openssl" {
+                    continue;
+                }
+                let mut step = cargo_test(
+                    &format!("cargo test {} {}", c, backend),
+                    &format!("{},{}", args, backend),
+                );
+                step.timeout_minutes = Some(5);
+                r.push(step);
+            }
+        }
+        if c == "interop" {
+            // BoringSSL instead of openssl and native-tls (see `interop/build.rs`)
+            let mut step = cargo_test(
+                &format!("cargo test {} boring", c),
+                &format!("{},boring", args),
+            );
+            step.timeout_minutes = Some(5);
+            r.push(step);
+        }
+        if c == "impl-rustls" && os != WINDOWS {
+            // Whole test suite under other crypto providers
+            for provider in &["aws-lc-rs", "fips"] {
+                let mut step = cargo_test(
+                    &format!("cargo test {} {}", c, provider),
+                    &format!("{},{}", args, provider),
+                );
+                step.timeout_minutes = Some(5);
+                r.push(step);
+            }
+        }
     }
     r
 }
 
 fn runtimes() -> Vec<&'static str> {
-    vec!["runtime-tokio", "runtime-async-std"]
+    vec![
+        "runtime-tokio",
+        "runtime-async-std",
+        // smol and other executors using `futures` IO traits
+        "runtime-futures-io",
+        // Both runtimes in one build, like in a workspace where crates use different runtimes
+        "runtime-tokio,runtime-async-std",
+    ]
 }
 
 #[derive(PartialEq, Eq, Copy, Clone)]
@@ -108,7 +149,7 @@ fn jobs() -> Vec<Job> {
                     continue;
                 }
                 r.push(Job {
-                    id: format!("{}-{}-{}", rt, os.name, channel),
+                    id: format!("{}-{}-{}", rt.replace(',', "-"), os.name, channel),
                     name: format!("{} {} {}", rt, os.name, channel),
                     runs_on: os.ghwf,
                     env: vec![("RUST_BACKTRACE".to_owned(), "1".to_owned())],
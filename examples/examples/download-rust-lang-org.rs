@@ -1,22 +1,34 @@
 use std::net::ToSocketAddrs;
-use tls_api::runtime::AsyncReadExt;
-use tls_api::runtime::AsyncWriteExt;
 
-// Users are not supposed to use tokio and async-std side by side.
-// Use both just as an example.
+// The example runs over tokio, async-std or smol depending on enabled features,
+// in this order of preference when several are enabled.
 
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 use async_std::main;
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 use async_std::net::TcpStream;
+#[cfg(not(feature = "runtime-tokio"))]
+use tls_api::runtime::futures::AsyncReadExt;
+#[cfg(not(feature = "runtime-tokio"))]
+use tls_api::runtime::futures::AsyncWriteExt;
 
 #[cfg(feature = "runtime-tokio")]
 use tokio::main;
 #[cfg(feature = "runtime-tokio")]
 use tokio::net::TcpStream;
+#[cfg(feature = "runtime-tokio")]
+use tls_api::runtime::tokio::AsyncReadExt;
+#[cfg(feature = "runtime-tokio")]
+use tls_api::runtime::tokio::AsyncWriteExt;
+
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+use smol::net::TcpStream;
 
 async fn download_impl<C: tls_api::TlsConnector>() {
-    if !C::IMPLEMENTED {
+    if !C::CAPABILITIES.implemented {
         eprintln!(
             "skipping {}, it is not available on this platform",
             C::info().name
@@ -52,6 +64,13 @@ async fn download_impl<C: tls_api::TlsConnector>() {
     println!("downloaded {} bytes using {}", data.len(), C::info().name);
 }
 
+async fn download_all() {
+    download_impl::<tls_api_native_tls::TlsConnector>().await;
+    download_impl::<tls_api_openssl::TlsConnector>().await;
+    download_impl::<tls_api_security_framework::TlsConnector>().await;
+    download_impl::<tls_api_rustls::TlsConnector>().await;
+}
+
 /// Try it:
 /// ```
 /// $ cargo run -p tls-api-examples --example download-rust-lang-org
@@ -61,10 +80,14 @@ async fn download_impl<C: tls_api::TlsConnector>() {
 /// $ cargo run -p tls-api-examples --example download-rust-lang-org \
 ///      --no-default-features --features=runtime-async-std
 /// ```
+/// or with `--features=runtime-futures-io` for smol.
+#[cfg(any(feature = "runtime-tokio", feature = "runtime-async-std"))]
 #[crate::main]
 async fn main() {
-    download_impl::<tls_api_native_tls::TlsConnector>().await;
-    download_impl::<tls_api_openssl::TlsConnector>().await;
-    download_impl::<tls_api_security_framework::TlsConnector>().await;
-    download_impl::<tls_api_rustls::TlsConnector>().await;
+    download_all().await;
+}
+
+#[cfg(not(any(feature = "runtime-tokio", feature = "runtime-async-std")))]
+fn main() {
+    smol::block_on(download_all());
 }
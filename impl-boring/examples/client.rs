@@ -0,0 +1,30 @@
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
+use async_std::net::TcpStream;
+use std::fs;
+use test_cert_gen::Cert;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api_test::block_on;
+#[cfg(feature = "runtime-tokio")]
+use tokio::net::TcpStream;
+// smol
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+use tls_api_test::TcpStream;
+
+async fn run() {
+    let socket = TcpStream::connect(("127.0.0.1", 4433)).await.unwrap();
+
+    let mut builder = tls_api_boring::TlsConnector::builder().unwrap();
+    builder
+        .add_root_certificate(&Cert::from_der(fs::read("ca.der").unwrap()).get_der())
+        .unwrap();
+    let connector = builder.build().unwrap();
+    connector.connect("localhost", socket).await.unwrap();
+}
+
+fn main() {
+    block_on(run());
+}
@@ -0,0 +1,231 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use boring::pkcs12::ParsedPkcs12;
+use foreign_types::ForeignType;
+
+use tls_api::spi_acceptor_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+use crate::encode_alpn_protos;
+use crate::ocsp::StapledOcspResponse;
+use anyhow::Context;
+
+pub struct TlsAcceptorBuilder {
+    pub builder: boring::ssl::SslAcceptorBuilder,
+    ocsp_response: StapledOcspResponse,
+}
+
+pub struct TlsAcceptor {
+    pub acceptor: boring::ssl::SslAcceptor,
+    ocsp_response: StapledOcspResponse,
+}
+
+fn to_boring_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<ParsedPkcs12> {
+    let pkcs12 = boring::pkcs12::Pkcs12::from_der(pkcs12)?;
+    Ok(pkcs12.parse(passphrase).context("Parse passphrase")?)
+}
+
+impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
+    type Acceptor = TlsAcceptor;
+
+    type Underlying = boring::ssl::SslAcceptorBuilder;
+
+    fn underlying_mut(&mut self) -> &mut boring::ssl::SslAcceptorBuilder {
+        &mut self.builder
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        let protocols = encode_alpn_protos(protocols)?;
+        self.builder
+            .set_alpn_select_callback(move |_ssl, client_protocols| {
+                match boring::ssl::select_next_proto(&protocols, client_protocols) {
+                    Some(selected) => Ok(selected),
+                    None => Err(boring::ssl::AlpnError::NOACK),
+                }
+            });
+        Ok(())
+    }
+
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let cert = boring::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .add_client_ca(&cert)
+            .map_err(anyhow::Error::new)?;
+        self.builder
+            .cert_store_mut()
+            .add_cert(cert)
+            .map_err(anyhow::Error::new)?;
+        // Session resumption fails without session id context when peer is verified
+        self.builder
+            .set_session_id_context(b"tls-api")
+            .map_err(anyhow::Error::new)?;
+        self.builder.set_verify(client_auth_verify_mode());
+
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.ocsp_response.set(ocsp_response);
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        crate::key_log::install(&mut self.builder, key_log);
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<TlsAcceptor> {
+        self.ocsp_response.install(&mut self.builder)?;
+        Ok(TlsAcceptor {
+            acceptor: self.builder.build(),
+            ocsp_response: self.ocsp_response,
+        })
+    }
+}
+
+fn client_auth_verify_mode() -> boring::ssl::SslVerifyMode {
+    boring::ssl::SslVerifyMode::PEER | boring::ssl::SslVerifyMode::FAIL_IF_NO_PEER_CERT
+}
+
+impl TlsAcceptorBuilder {
+    fn new(builder: boring::ssl::SslAcceptorBuilder) -> TlsAcceptorBuilder {
+        TlsAcceptorBuilder {
+            builder,
+            ocsp_response: StapledOcspResponse::default(),
+        }
+    }
+
+    pub fn builder_mut(&mut self) -> &mut boring::ssl::SslAcceptorBuilder {
+        &mut self.builder
+    }
+}
+
+impl TlsAcceptor {
+    async fn accept_impl<S>(&self, stream: S) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn()?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self) -> anyhow::Result<crate::TlsConnection> {
+        let ssl = boring::ssl::Ssl::new(self.acceptor.context())?;
+        // `boring` exposes `SSL_set_accept_state` only for `SslStreamBuilder`
+        unsafe { boring_sys::SSL_set_accept_state(ssl.as_ptr()) };
+        crate::TlsConnection::new(ssl)
+    }
+
+    /// Blocking version of [`accept_impl`](Self::accept_impl).
+    pub fn accept_blocking_impl(
+        &self,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.acceptor.accept(stream))
+    }
+}
+
+impl tls_api::TlsAcceptor for TlsAcceptor {
+    type Builder = TlsAcceptorBuilder;
+
+    type Underlying = boring::ssl::SslAcceptor;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut Self::Underlying {
+        &mut self.acceptor
+    }
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        der_keys: true,
+        pkcs12_keys: true,
+        client_auth: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::into()
+    }
+
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.ocsp_response.set(ocsp_response);
+        Ok(())
+    }
+
+    fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<TlsAcceptorBuilder> {
+        let cert = boring::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+        let pkey = boring::pkey::PKey::private_key_from_der(key).map_err(anyhow::Error::new)?;
+
+        let mut builder =
+            boring::ssl::SslAcceptor::mozilla_intermediate_v5(boring::ssl::SslMethod::tls())
+                .map_err(anyhow::Error::new)?;
+
+        builder
+            .set_certificate(cert.as_ref())
+            .map_err(anyhow::Error::new)?;
+        builder
+            .set_private_key(pkey.as_ref())
+            .map_err(anyhow::Error::new)?;
+
+        Ok(TlsAcceptorBuilder::new(builder))
+    }
+
+    fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<TlsAcceptorBuilder> {
+        let mut builder =
+            boring::ssl::SslAcceptor::mozilla_intermediate_v5(boring::ssl::SslMethod::tls())
+                .map_err(anyhow::Error::new)?;
+
+        let pkcs12 = to_boring_pkcs12(pkcs12, passphrase)?;
+        if let Some(chain) = pkcs12.chain {
+            for x509 in chain {
+                builder
+                    .add_extra_chain_cert(x509)
+                    .map_err(anyhow::Error::new)?;
+            }
+        }
+
+        builder
+            .set_certificate(&pkcs12.cert)
+            .map_err(anyhow::Error::new)?;
+        builder
+            .set_private_key(&pkcs12.pkey)
+            .map_err(anyhow::Error::new)?;
+
+        Ok(TlsAcceptorBuilder::new(builder))
+    }
+
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.accept_blocking_impl(stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.new_conn().map(TlsConnection::new)
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
+}
@@ -0,0 +1,118 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpStream;
+use std::result;
+
+use boring::ssl::HandshakeError;
+use boring::ssl::NameType;
+use boring::ssl::SslRef;
+use boring::ssl::SslStream;
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsStreamBlockingDyn;
+
+/// Blocking TLS stream: [`SslStream`] over [`TcpStream`].
+#[derive(Debug)]
+pub struct TlsStreamBlocking(SslStream<TcpStream>);
+
+pub(crate) fn handshake(
+    r: result::Result<SslStream<TcpStream>, HandshakeError<TcpStream>>,
+) -> anyhow::Result<TlsStreamBlocking> {
+    match r {
+        Ok(stream) => Ok(TlsStreamBlocking(stream)),
+        Err(HandshakeError::Failure(e)) => Err(anyhow::Error::new(e.into_error())),
+        // `WouldBlock` is only possible if socket has a timeout
+        Err(e) => Err(anyhow::Error::new(e)),
+    }
+}
+
+impl TlsStreamBlocking {
+    /// Get the [`SslRef`] object for the stream.
+    pub fn get_ssl_ref(&self) -> &SslRef {
+        self.0.ssl()
+    }
+}
+
+impl Read for TlsStreamBlocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStreamBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlocking {
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.0.ssl().selected_alpn_protocol().map(Vec::from))
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .0
+            .ssl()
+            .servername(NameType::HOST_NAME)
+            .map(str::to_owned))
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.0.ssl().ocsp_status().map(Vec::from))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut out = vec![0; len];
+        self.0
+            .ssl()
+            .export_keying_material(&mut out, label, context)?;
+        Ok(out)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => {
+                crate::channel_binding::tls_server_end_point(self.0.ssl())
+            }
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::into()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        match self.0.shutdown() {
+            Ok(_) => {}
+            // Peer already closed the connection
+            Err(e) if e.code() == boring::ssl::ErrorCode::ZERO_RETURN => {}
+            Err(e) => return Err(e.into_io_error().unwrap_or_else(io::Error::other)),
+        }
+        self.0.get_ref().shutdown(Shutdown::Write)
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        self.0.get_mut()
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        self.0.get_ref()
+    }
+}
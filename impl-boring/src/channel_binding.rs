@@ -0,0 +1,41 @@
+use boring::hash::MessageDigest;
+use boring::nid::Nid;
+use boring::ssl::SslRef;
+use boring::x509::X509;
+use tls_api::ChannelBindingError;
+
+/// Server certificate: own certificate on the server side, peer certificate on the client side.
+fn server_certificate(ssl: &SslRef) -> Option<X509> {
+    if ssl.is_server() {
+        ssl.certificate().map(|c| c.to_owned())
+    } else {
+        ssl.peer_certificate()
+    }
+}
+
+/// `tls-server-end-point` channel binding (RFC 5929 section 4.1).
+pub(crate) fn tls_server_end_point(ssl: &SslRef) -> anyhow::Result<Vec<u8>> {
+    let cert = server_certificate(ssl).ok_or(ChannelBindingError::NoServerCertificate)?;
+
+    let signature_nid = cert.signature_algorithm().object().nid();
+    let unsupported = || {
+        ChannelBindingError::UnsupportedSignatureAlgorithm(
+            signature_nid
+                .long_name()
+                .map(str::to_owned)
+                .unwrap_or_else(|_| format!("{:?}", signature_nid)),
+        )
+    };
+
+    let digest_nid = match signature_nid.signature_algorithms() {
+        Some(algorithms) if algorithms.digest != Nid::UNDEF => algorithms.digest,
+        _ => return Err(unsupported().into()),
+    };
+    // MD5 and SHA-1 are replaced with SHA-256
+    let digest = match digest_nid {
+        Nid::MD5 | Nid::SHA1 => MessageDigest::sha256(),
+        nid => MessageDigest::from_nid(nid).ok_or_else(unsupported)?,
+    };
+
+    Ok(cert.digest(digest)?.to_vec())
+}
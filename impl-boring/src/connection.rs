@@ -0,0 +1,185 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use boring::ssl::ErrorCode;
+use boring::ssl::NameType;
+use boring::ssl::Ssl;
+use boring::ssl::SslRef;
+use boring::ssl::SslStream;
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsHandshakeState;
+
+/// Memory BIO: ciphertext fed by and taken by the caller.
+#[derive(Debug, Default)]
+struct MemIo {
+    /// Ciphertext received from the peer, not yet read by `boring`.
+    incoming: Vec<u8>,
+    /// Ciphertext written by `boring`, not yet taken.
+    outgoing: Vec<u8>,
+}
+
+impl Read for MemIo {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.incoming.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let len = self.incoming.len().min(buf.len());
+        buf[..len].copy_from_slice(&self.incoming[..len]);
+        self.incoming.drain(..len);
+        Ok(len)
+    }
+}
+
+impl Write for MemIo {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.outgoing.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub(crate) fn ssl_error_to_io(e: boring::ssl::Error) -> io::Error {
+    match e.code() {
+        ErrorCode::WANT_READ | ErrorCode::WANT_WRITE => io::ErrorKind::WouldBlock.into(),
+        _ => e.into_io_error().unwrap_or_else(io::Error::other),
+    }
+}
+
+/// Sans-IO connection: [`SslStream`] over memory BIO.
+#[derive(Debug)]
+pub struct TlsConnection {
+    stream: SslStream<MemIo>,
+    handshaking: bool,
+}
+
+impl TlsConnection {
+    /// Wrap `Ssl` which has connect or accept state set, and start the handshake.
+    pub(crate) fn new(ssl: Ssl) -> anyhow::Result<TlsConnection> {
+        let mut conn = TlsConnection {
+            stream: SslStream::new(ssl, MemIo::default())?,
+            handshaking: true,
+        };
+        // Client writes `ClientHello`
+        conn.continue_handshake()?;
+        Ok(conn)
+    }
+
+    /// Get the [`SslRef`] object for the connection.
+    pub fn get_ssl_ref(&self) -> &SslRef {
+        self.stream.ssl()
+    }
+
+    fn continue_handshake(&mut self) -> io::Result<()> {
+        match self.stream.do_handshake() {
+            Ok(()) => {
+                self.handshaking = false;
+                Ok(())
+            }
+            Err(e) if e.code() == ErrorCode::WANT_READ => Ok(()),
+            Err(e) => Err(ssl_error_to_io(e)),
+        }
+    }
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        if self.handshaking {
+            TlsHandshakeState::InProgress
+        } else {
+            TlsHandshakeState::Complete
+        }
+    }
+
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.stream.get_mut().incoming.extend_from_slice(data);
+        if self.handshaking {
+            self.continue_handshake()?;
+        }
+        Ok(data.len())
+    }
+
+    fn wants_write(&self) -> bool {
+        !self.stream.get_ref().outgoing.is_empty()
+    }
+
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let outgoing = &mut self.stream.get_mut().outgoing;
+        let len = outgoing.len().min(buf.len());
+        buf[..len].copy_from_slice(&outgoing[..len]);
+        outgoing.drain(..len);
+        Ok(len)
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.handshaking {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        match self.stream.ssl_read(buf) {
+            Ok(read) => Ok(read),
+            Err(e) if e.code() == ErrorCode::ZERO_RETURN => Ok(0),
+            Err(e) => Err(ssl_error_to_io(e)),
+        }
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.handshaking {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.stream.ssl_write(buf).map_err(ssl_error_to_io)
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        self.stream.shutdown().map_err(ssl_error_to_io)?;
+        Ok(())
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.get_ssl_ref().selected_alpn_protocol().map(Vec::from))
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .get_ssl_ref()
+            .servername(NameType::HOST_NAME)
+            .map(str::to_owned))
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.get_ssl_ref().ocsp_status().map(Vec::from))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut out = vec![0; len];
+        self.get_ssl_ref()
+            .export_keying_material(&mut out, label, context)?;
+        Ok(out)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => {
+                crate::channel_binding::tls_server_end_point(self.get_ssl_ref())
+            }
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::into()
+    }
+}
@@ -0,0 +1,199 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use foreign_types::ForeignType;
+use tls_api::spi_connector_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+use crate::encode_alpn_protos;
+
+pub struct TlsConnectorBuilder {
+    pub builder: boring::ssl::SslConnectorBuilder,
+    pub verify_hostname: bool,
+    pub ocsp_stapling: OcspStapling,
+}
+
+pub struct TlsConnector {
+    pub connector: boring::ssl::SslConnector,
+    pub verify_hostname: bool,
+    pub ocsp_stapling: OcspStapling,
+}
+
+impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
+    type Connector = TlsConnector;
+
+    type Underlying = boring::ssl::SslConnectorBuilder;
+
+    fn underlying_mut(&mut self) -> &mut boring::ssl::SslConnectorBuilder {
+        &mut self.builder
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.builder
+            .set_alpn_protos(&encode_alpn_protos(protocols)?)
+            .map_err(anyhow::Error::new)
+    }
+
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
+        self.verify_hostname = verify;
+        Ok(())
+    }
+
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let cert = boring::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .cert_store_mut()
+            .add_cert(cert)
+            .map_err(anyhow::Error::new)?;
+
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        let cert = boring::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+        let pkey = boring::pkey::PKey::private_key_from_der(key).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .set_certificate(&cert)
+            .map_err(anyhow::Error::new)?;
+        self.builder
+            .set_private_key(&pkey)
+            .map_err(anyhow::Error::new)?;
+
+        Ok(())
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.ocsp_stapling = mode;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        crate::key_log::install(&mut self.builder, key_log);
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<TlsConnector> {
+        crate::ocsp::configure_client(&mut self.builder, self.ocsp_stapling)?;
+        Ok(TlsConnector {
+            connector: self.builder.build(),
+            verify_hostname: self.verify_hostname,
+            ocsp_stapling: self.ocsp_stapling,
+        })
+    }
+}
+
+impl TlsConnectorBuilder {
+    pub fn builder_mut(&mut self) -> &mut boring::ssl::SslConnectorBuilder {
+        &mut self.builder
+    }
+}
+
+impl TlsConnector {
+    fn configure(&self) -> anyhow::Result<boring::ssl::ConnectConfiguration> {
+        let client_configuration = self.connector.configure()?;
+        let mut client_configuration = client_configuration.verify_hostname(self.verify_hostname);
+        if self.ocsp_stapling != OcspStapling::Disabled {
+            client_configuration.set_status_type(boring::ssl::StatusType::OCSP)?;
+        }
+        Ok(client_configuration)
+    }
+
+    pub async fn connect_impl<S>(
+        &self,
+        domain: &str,
+        stream: S,
+    ) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn(domain)?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self, domain: &str) -> anyhow::Result<crate::TlsConnection> {
+        let ssl = self.configure()?.into_ssl(domain)?;
+        // `boring` exposes `SSL_set_connect_state` only for `SslStreamBuilder`
+        unsafe { boring_sys::SSL_set_connect_state(ssl.as_ptr()) };
+        crate::TlsConnection::new(ssl)
+    }
+
+    /// Blocking version of [`connect_impl`](Self::connect_impl).
+    pub fn connect_blocking_impl(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.configure()?.connect(domain, stream))
+    }
+}
+
+impl tls_api::TlsConnector for TlsConnector {
+    type Builder = TlsConnectorBuilder;
+
+    type Underlying = boring::ssl::SslConnector;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut Self::Underlying {
+        &mut self.connector
+    }
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        client_auth: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::into()
+    }
+
+    fn builder() -> anyhow::Result<TlsConnectorBuilder> {
+        let builder = boring::ssl::SslConnector::builder(boring::ssl::SslMethod::tls())
+            .map_err(anyhow::Error::new)?;
+        Ok(TlsConnectorBuilder {
+            builder,
+            verify_hostname: true,
+            ocsp_stapling: OcspStapling::default(),
+        })
+    }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.connect_blocking_impl(domain, stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.new_conn(domain).map(TlsConnection::new)
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
+}
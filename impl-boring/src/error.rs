@@ -0,0 +1,7 @@
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error("incorrect ALPN protocol name length")]
+    AlpnProtocolLen,
+    #[error("boring does not support certificate revocation lists")]
+    CrlNotSupported,
+}
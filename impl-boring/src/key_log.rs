@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use boring::ssl::SslContextBuilder;
+use tls_api::KeyLog;
+
+/// Report secrets to `key_log`.
+///
+/// BoringSSL provides secrets as lines of NSS key log file, so they are parsed back.
+pub(crate) fn install(builder: &mut SslContextBuilder, key_log: Arc<dyn KeyLog>) {
+    builder.set_keylog_callback(move |_ssl, line| {
+        if let Some((label, client_random, secret)) = parse_line(line) {
+            key_log.log(label, &client_random, &secret);
+        }
+    });
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match *pair {
+            [hi, lo] => Some((hex_digit(hi)? << 4) | hex_digit(lo)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn parse_line(line: &str) -> Option<(&str, Vec<u8>, Vec<u8>)> {
+    let mut parts = line.split(' ');
+    let label = parts.next()?;
+    let client_random = decode_hex(parts.next()?)?;
+    let secret = decode_hex(parts.next()?)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((label, client_random, secret))
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_line() {
+    assert_eq!(
+        Some(("CLIENT_RANDOM", vec![0x0a, 0xff], vec![0x00, 0x01, 0x02])),
+        parse_line("CLIENT_RANDOM 0aFF 000102")
+    );
+    assert_eq!(None, parse_line("CLIENT_RANDOM 0a"));
+    assert_eq!(None, parse_line("CLIENT_RANDOM 0a 0"));
+    assert_eq!(None, parse_line("CLIENT_RANDOM 0a zz"));
+}
@@ -0,0 +1,68 @@
+//! [`tls_api`] implementation for [`boring`] (BoringSSL).
+//!
+//! `stream`, `blocking`, `connection`, `ocsp` and `channel_binding` modules
+//! are copies of `tls-api-openssl` modules with `openssl` replaced by `boring`.
+//! `boring` types mirror `openssl` types, but are distinct, there is no trait
+//! implemented by both, and the two crates cannot be linked into one binary,
+//! so there is no crate the code could be shared in without a macro
+//! over the crate name. Copies also let the implementations diverge where
+//! BoringSSL differs from OpenSSL. Fixes to these modules should be applied to both.
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod acceptor;
+mod blocking;
+mod channel_binding;
+mod connection;
+mod connector;
+mod error;
+mod key_log;
+mod ocsp;
+mod stream;
+
+pub use acceptor::TlsAcceptor;
+pub use acceptor::TlsAcceptorBuilder;
+pub use connector::TlsConnector;
+pub use connector::TlsConnectorBuilder;
+
+pub use blocking::TlsStreamBlocking;
+pub use connection::TlsConnection;
+pub(crate) use error::Error;
+pub(crate) use stream::TlsStream;
+
+use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
+
+fn encode_alpn_protos(protos: &[&[u8]]) -> anyhow::Result<Vec<u8>> {
+    let mut r = Vec::new();
+    for proto in protos {
+        if proto.len() > 255 {
+            return Err(crate::Error::AlpnProtocolLen.into());
+        }
+        r.push(proto.len() as u8);
+        r.extend_from_slice(proto);
+    }
+    Ok(r)
+}
+
+#[cfg(test)]
+#[test]
+fn test_encode_alpn_protos() {
+    assert_eq!(
+        &b"\x06spdy/1\x08http/1.1"[..],
+        &encode_alpn_protos(&[b"spdy/1", b"http/1.1"]).unwrap()[..]
+    );
+}
+
+pub(crate) fn into() -> ImplInfo {
+    ImplInfo {
+        name: "boring",
+        version: boring::version::version(),
+        crypto_provider: None,
+        fips: cfg!(feature = "fips"),
+    }
+}
+
+/// BoringSSL disables TLS 1.0 and 1.1 by default.
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] =
+    &[ProtocolVersion::Tls12, ProtocolVersion::Tls13];
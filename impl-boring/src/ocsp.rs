@@ -0,0 +1,50 @@
+//! OCSP stapling
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use boring::ssl::SslContextBuilder;
+
+use tls_api::OcspStapling;
+
+/// OCSP response stapled by the server.
+///
+/// Shared between the acceptor and the status callback,
+/// so the response can be replaced after the acceptor is built.
+#[derive(Clone, Default)]
+pub(crate) struct StapledOcspResponse(Arc<Mutex<Option<Vec<u8>>>>);
+
+impl StapledOcspResponse {
+    pub(crate) fn set(&self, ocsp_response: &[u8]) {
+        *self.0.lock().unwrap() = Some(ocsp_response.to_vec());
+    }
+
+    /// Install server status callback which staples current response.
+    pub(crate) fn install(&self, builder: &mut SslContextBuilder) -> anyhow::Result<()> {
+        let ocsp_response = self.0.clone();
+        builder
+            .set_status_callback(move |ssl| match &*ocsp_response.lock().unwrap() {
+                Some(ocsp_response) => {
+                    ssl.set_ocsp_status(ocsp_response)?;
+                    Ok(true)
+                }
+                // No response is sent
+                None => Ok(false),
+            })
+            .map_err(anyhow::Error::new)
+    }
+}
+
+/// Install client status callback which fails the handshake
+/// if server did not staple the response when it is required.
+pub(crate) fn configure_client(
+    builder: &mut SslContextBuilder,
+    mode: OcspStapling,
+) -> anyhow::Result<()> {
+    if mode != OcspStapling::MustStaple {
+        return Ok(());
+    }
+    builder
+        .set_status_callback(|ssl| Ok(ssl.ocsp_status().is_some()))
+        .map_err(anyhow::Error::new)
+}
@@ -0,0 +1,28 @@
+use boring::ssl::SslRef;
+use tls_api::spi::TlsStreamOverConnection;
+use tls_api::spi_tls_stream_over_connection_wrapper;
+use tls_api::AsyncSocket;
+
+spi_tls_stream_over_connection_wrapper!(
+    /// TLS stream: [`TlsConnection`](crate::TlsConnection) driven by the async socket.
+    TlsStream,
+    crate::TlsConnection
+);
+
+impl<A: AsyncSocket> TlsStream<A> {
+    /// Perform the handshake over the socket.
+    pub(crate) async fn handshake(
+        socket: A,
+        conn: crate::TlsConnection,
+    ) -> anyhow::Result<TlsStream<A>> {
+        TlsStreamOverConnection::new(socket, conn)
+            .handshake()
+            .await
+            .map(TlsStream)
+    }
+
+    /// Get the [`SslRef`] object for the stream.
+    pub fn get_ssl_ref(&self) -> &SslRef {
+        self.0.conn.get_ssl_ref()
+    }
+}
@@ -0,0 +1,100 @@
+//! TLS API implementation best suited for the target platform.
+//!
+//! Implementation is selected by cargo features and target:
+//!
+//! * `openssl` if `openssl` feature is enabled
+//! * otherwise security-framework on macOS and iOS
+//! * otherwise `rustls` if `rustls` feature is enabled (default)
+//! * otherwise [stub](tls_api_stub), so the code still compiles,
+//!   but any operation returns an error
+//!
+//! ```
+//! use tls_api::TlsConnector as _;
+//! use tls_api::TlsConnectorBuilder as _;
+//!
+//! fn connector() -> tls_api_default::DefaultTlsConnector {
+//!     let builder = tls_api_default::DefaultTlsConnector::builder().expect("builder");
+//!     builder.build().expect("build")
+//! }
+//! ```
+
+#![deny(rustdoc::broken_intra_doc_links)]
+#![deny(missing_docs)]
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorType;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorType;
+use tls_api::TlsImplRegistry;
+
+#[cfg(feature = "openssl")]
+mod selected {
+    pub use tls_api_openssl::TlsAcceptor;
+    pub use tls_api_openssl::TlsConnector;
+}
+
+#[cfg(all(not(feature = "openssl"), any(target_os = "macos", target_os = "ios")))]
+mod selected {
+    pub use tls_api_security_framework::TlsAcceptor;
+    pub use tls_api_security_framework::TlsConnector;
+}
+
+#[cfg(all(
+    not(feature = "openssl"),
+    not(any(target_os = "macos", target_os = "ios")),
+    feature = "rustls"
+))]
+mod selected {
+    pub use tls_api_rustls::TlsAcceptor;
+    pub use tls_api_rustls::TlsConnector;
+}
+
+#[cfg(all(
+    not(feature = "openssl"),
+    not(any(target_os = "macos", target_os = "ios")),
+    not(feature = "rustls")
+))]
+mod selected {
+    pub use tls_api_stub::TlsAcceptor;
+    pub use tls_api_stub::TlsConnector;
+}
+
+/// Connector of the implementation selected for this build.
+pub type DefaultTlsConnector = selected::TlsConnector;
+/// Acceptor of the implementation selected for this build.
+pub type DefaultTlsAcceptor = selected::TlsAcceptor;
+
+/// Dynamic type of [`DefaultTlsConnector`].
+pub const CONNECTOR_TYPE_DYN: &dyn TlsConnectorType = DefaultTlsConnector::TYPE_DYN;
+/// Dynamic type of [`DefaultTlsAcceptor`].
+pub const ACCEPTOR_TYPE_DYN: &dyn TlsAcceptorType = DefaultTlsAcceptor::TYPE_DYN;
+
+/// Whether a real implementation is selected (i. e. not stub).
+pub const IMPLEMENTED: bool =
+    DefaultTlsConnector::CAPABILITIES.implemented && DefaultTlsAcceptor::CAPABILITIES.implemented;
+
+/// Registry of implementations enabled in this build, default first.
+///
+/// Can be used to override the default with
+/// [`TLS_API_IMPL`](tls_api::TLS_API_IMPL_ENV) environment variable.
+pub fn registry() -> TlsImplRegistry {
+    let mut registry = TlsImplRegistry::new();
+    register::<DefaultTlsConnector, DefaultTlsAcceptor>(&mut registry);
+    #[cfg(feature = "openssl")]
+    register::<tls_api_openssl::TlsConnector, tls_api_openssl::TlsAcceptor>(&mut registry);
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    register::<tls_api_security_framework::TlsConnector, tls_api_security_framework::TlsAcceptor>(
+        &mut registry,
+    );
+    #[cfg(feature = "rustls")]
+    register::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>(&mut registry);
+    registry
+}
+
+/// Register unless already registered as default.
+fn register<C: TlsConnector, A: TlsAcceptor>(registry: &mut TlsImplRegistry) {
+    let name = C::TYPE_DYN.info().name;
+    if registry.all().iter().all(|i| i.name() != name) {
+        registry.register::<C, A>();
+    }
+}
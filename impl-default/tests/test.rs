@@ -0,0 +1,47 @@
+use tls_api::TlsConnector;
+
+#[test]
+fn client_server_der() {
+    tls_api_test::test_client_server_der::<
+        tls_api_default::DefaultTlsConnector,
+        tls_api_default::DefaultTlsAcceptor,
+    >();
+}
+
+#[test]
+fn client_server_dyn_der() {
+    tls_api_test::test_client_server_dyn_der(
+        tls_api_default::CONNECTOR_TYPE_DYN,
+        tls_api_default::ACCEPTOR_TYPE_DYN,
+    );
+}
+
+#[test]
+fn selected_impl() {
+    let expected = if cfg!(feature = "openssl") {
+        "openssl"
+    } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+        "security-framework"
+    } else if cfg!(feature = "rustls") {
+        "rustls"
+    } else {
+        "stub"
+    };
+    assert_eq!(expected, tls_api_default::CONNECTOR_TYPE_DYN.info().name);
+    assert_eq!(expected, tls_api_default::ACCEPTOR_TYPE_DYN.info().name);
+    assert_eq!(
+        expected != "stub",
+        tls_api_default::DefaultTlsConnector::CAPABILITIES.implemented
+    );
+}
+
+#[test]
+fn registry_default_first() {
+    let registry = tls_api_default::registry();
+    let names: Vec<&str> = registry.all().iter().map(|i| i.name()).collect();
+    assert_eq!(tls_api_default::CONNECTOR_TYPE_DYN.info().name, names[0]);
+    let mut dedup = names.clone();
+    dedup.sort_unstable();
+    dedup.dedup();
+    assert_eq!(names.len(), dedup.len(), "{:?}", names);
+}
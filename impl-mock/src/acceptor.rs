@@ -0,0 +1,136 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use tls_api::spi_acceptor_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+use crate::MockScript;
+
+/// Builder for [`TlsAcceptor`]. Keys and configuration are accepted and ignored.
+pub struct TlsAcceptorBuilder {
+    pub script: MockScript,
+}
+
+impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
+    type Acceptor = TlsAcceptor;
+    type Underlying = MockScript;
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        let _ = protocols;
+        Ok(())
+    }
+
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let _ = cert;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let _ = crl;
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        let _ = policy;
+        Ok(())
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        let _ = ocsp_response;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        let _ = key_log;
+        Ok(())
+    }
+
+    fn underlying_mut(&mut self) -> &mut MockScript {
+        &mut self.script
+    }
+
+    fn build(self) -> anyhow::Result<TlsAcceptor> {
+        Ok(TlsAcceptor {
+            script: self.script,
+        })
+    }
+}
+
+/// Acceptor which makes connections according to its [`MockScript`].
+pub struct TlsAcceptor {
+    pub script: MockScript,
+}
+
+impl TlsAcceptor {
+    async fn accept_impl<S>(&self, stream: S) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn()?;
+        Ok(crate::TlsStream::new(stream, conn))
+    }
+
+    fn new_conn(&self) -> anyhow::Result<crate::TlsConnection> {
+        Ok(crate::TlsConnection::new(self.script.next(), None)?)
+    }
+}
+
+impl tls_api::TlsAcceptor for TlsAcceptor {
+    type Builder = TlsAcceptorBuilder;
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        der_keys: true,
+        pkcs12_keys: true,
+        connection: true,
+        ..Capabilities::NONE
+    };
+
+    type Underlying = MockScript;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut MockScript {
+        &mut self.script
+    }
+
+    fn info() -> ImplInfo {
+        crate::info()
+    }
+
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        let _ = ocsp_response;
+        Ok(())
+    }
+
+    fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<TlsAcceptorBuilder> {
+        let _ = (cert, key);
+        Ok(TlsAcceptorBuilder {
+            script: MockScript::default(),
+        })
+    }
+
+    fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<TlsAcceptorBuilder> {
+        let _ = (pkcs12, passphrase);
+        Ok(TlsAcceptorBuilder {
+            script: MockScript::default(),
+        })
+    }
+
+    fn accept_blocking(&self, _stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.new_conn().map(TlsConnection::new)
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
+}
@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Scripted outcome of a single mock connection.
+///
+/// Default behavior is a successful handshake without ALPN protocol and peer certificate.
+#[derive(Debug, Clone, Default)]
+pub struct MockBehavior {
+    pub(crate) alpn_protocol: Option<Vec<u8>>,
+    pub(crate) peer_certificate: Option<Vec<u8>>,
+    pub(crate) handshake_error: Option<(io::ErrorKind, String)>,
+    pub(crate) close_after: Option<usize>,
+}
+
+impl MockBehavior {
+    /// Report this protocol as negotiated with ALPN.
+    pub fn alpn_protocol(mut self, protocol: &[u8]) -> MockBehavior {
+        self.alpn_protocol = Some(protocol.to_vec());
+        self
+    }
+
+    /// Report this DER certificate as the peer certificate.
+    pub fn peer_certificate(mut self, cert: &[u8]) -> MockBehavior {
+        self.peer_certificate = Some(cert.to_vec());
+        self
+    }
+
+    /// Fail the handshake with [`io::Error`] of given kind and message.
+    pub fn fail_handshake(mut self, kind: io::ErrorKind, message: &str) -> MockBehavior {
+        self.handshake_error = Some((kind, message.to_owned()));
+        self
+    }
+
+    /// Fail reads with [`UnexpectedEof`](io::ErrorKind::UnexpectedEof)
+    /// and writes with [`BrokenPipe`](io::ErrorKind::BrokenPipe)
+    /// after this number of bytes was read,
+    /// as if the peer closed the connection without `close_notify`.
+    pub fn close_after(mut self, bytes: usize) -> MockBehavior {
+        self.close_after = Some(bytes);
+        self
+    }
+
+    pub(crate) fn handshake_result(&self) -> io::Result<()> {
+        match &self.handshake_error {
+            Some((kind, message)) => Err(io::Error::new(*kind, message.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Behaviors of the next connections of a connector or acceptor.
+///
+/// Clones share the queue, so the script can be updated after the connector
+/// or acceptor is built. Connections made after the script is exhausted
+/// get the [default](MockBehavior::default) behavior.
+#[derive(Debug, Clone, Default)]
+pub struct MockScript(Arc<Mutex<VecDeque<MockBehavior>>>);
+
+impl MockScript {
+    /// Script the next connection which is not scripted yet.
+    pub fn push(&self, behavior: MockBehavior) {
+        self.0.lock().unwrap().push_back(behavior);
+    }
+
+    /// Number of scripted connections not made yet.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// No scripted connections left.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn next(&self) -> MockBehavior {
+        self.0.lock().unwrap().pop_front().unwrap_or_default()
+    }
+}
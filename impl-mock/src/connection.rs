@@ -0,0 +1,149 @@
+use std::io;
+
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsHandshakeState;
+
+use crate::MockBehavior;
+
+/// Sans-IO connection which passes data through as is,
+/// and reports what its [`MockBehavior`] says.
+///
+/// Handshake is complete from the start.
+#[derive(Debug)]
+pub struct TlsConnection {
+    behavior: MockBehavior,
+    server_name: Option<String>,
+    /// Data received from the peer, not yet read.
+    incoming: Vec<u8>,
+    /// Data written, not yet taken.
+    outgoing: Vec<u8>,
+    /// Number of bytes read.
+    read: usize,
+}
+
+/// Move data from the front of `from` to `to`.
+fn take_front(from: &mut Vec<u8>, to: &mut [u8]) -> usize {
+    let len = from.len().min(to.len());
+    to[..len].copy_from_slice(&from[..len]);
+    from.drain(..len);
+    len
+}
+
+impl TlsConnection {
+    /// Perform the scripted handshake.
+    pub(crate) fn new(
+        behavior: MockBehavior,
+        server_name: Option<String>,
+    ) -> io::Result<TlsConnection> {
+        behavior.handshake_result()?;
+        Ok(TlsConnection {
+            behavior,
+            server_name,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            read: 0,
+        })
+    }
+
+    /// Peer certificate set by [`MockBehavior::peer_certificate`].
+    pub fn peer_certificate(&self) -> Option<&[u8]> {
+        self.behavior.peer_certificate.as_deref()
+    }
+
+    /// How many more bytes can be read before the scripted close.
+    fn remaining(&self) -> Option<usize> {
+        self.behavior
+            .close_after
+            .map(|close_after| close_after.saturating_sub(self.read))
+    }
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        TlsHandshakeState::Complete
+    }
+
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.incoming.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn wants_write(&self) -> bool {
+        !self.outgoing.is_empty()
+    }
+
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(take_front(&mut self.outgoing, buf))
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len();
+        let buf = match self.remaining() {
+            Some(0) if !buf.is_empty() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "mock connection closed mid-stream",
+                ));
+            }
+            Some(remaining) => &mut buf[..remaining.min(len)],
+            None => buf,
+        };
+        if self.incoming.is_empty() && !buf.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        let read = take_front(&mut self.incoming, buf);
+        self.read += read;
+        Ok(read)
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.remaining() == Some(0) {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "mock connection closed mid-stream",
+            ));
+        }
+        self.outgoing.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn requires_close_notify(&self) -> bool {
+        false
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.behavior.alpn_protocol.clone())
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.server_name.clone())
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("mock").into())
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::NotSupported(kind, "mock").into())
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::info()
+    }
+}
@@ -0,0 +1,139 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use tls_api::spi_connector_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+use crate::MockScript;
+
+/// Builder for [`TlsConnector`]. Configuration is accepted and ignored.
+pub struct TlsConnectorBuilder {
+    pub script: MockScript,
+}
+
+impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
+    type Connector = TlsConnector;
+    type Underlying = MockScript;
+
+    fn underlying_mut(&mut self) -> &mut MockScript {
+        &mut self.script
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        let _ = protocols;
+        Ok(())
+    }
+
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
+        let _ = verify;
+        Ok(())
+    }
+
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let _ = cert;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let _ = crl;
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        let _ = policy;
+        Ok(())
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        let _ = (cert, key);
+        Ok(())
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        let _ = mode;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        let _ = key_log;
+        Ok(())
+    }
+
+    fn build(self) -> anyhow::Result<TlsConnector> {
+        Ok(TlsConnector {
+            script: self.script,
+        })
+    }
+}
+
+/// Connector which makes connections according to its [`MockScript`].
+pub struct TlsConnector {
+    pub script: MockScript,
+}
+
+impl TlsConnector {
+    async fn connect_impl<S>(&self, domain: &str, stream: S) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn(domain)?;
+        Ok(crate::TlsStream::new(stream, conn))
+    }
+
+    fn new_conn(&self, domain: &str) -> anyhow::Result<crate::TlsConnection> {
+        Ok(crate::TlsConnection::new(
+            self.script.next(),
+            Some(domain.to_owned()),
+        )?)
+    }
+}
+
+impl tls_api::TlsConnector for TlsConnector {
+    type Builder = TlsConnectorBuilder;
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        connection: true,
+        ..Capabilities::NONE
+    };
+
+    type Underlying = MockScript;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut MockScript {
+        &mut self.script
+    }
+
+    fn info() -> ImplInfo {
+        crate::info()
+    }
+
+    fn builder() -> anyhow::Result<TlsConnectorBuilder> {
+        Ok(TlsConnectorBuilder {
+            script: MockScript::default(),
+        })
+    }
+
+    fn connect_blocking(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.new_conn(domain).map(TlsConnection::new)
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error("blocking API is not implemented for mock")]
+    BlockingNotSupported,
+}
@@ -0,0 +1,38 @@
+//! Mock implementation of TLS API for tests.
+//!
+//! Like `tls-api-not-tls`, data is passed over the socket as is,
+//! but what each connection reports or how it fails is scripted
+//! with [`MockBehavior`] pushed to [`MockScript`] of connector or acceptor.
+//!
+//! This is NOT TLS implementation.
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod acceptor;
+mod behavior;
+mod connection;
+mod connector;
+mod error;
+mod stream;
+
+pub(crate) use error::Error;
+
+pub use acceptor::TlsAcceptor;
+pub use acceptor::TlsAcceptorBuilder;
+pub use behavior::MockBehavior;
+pub use behavior::MockScript;
+pub use connection::TlsConnection;
+pub use connector::TlsConnector;
+pub use connector::TlsConnectorBuilder;
+pub use stream::TlsStream;
+
+use tls_api::ImplInfo;
+
+pub(crate) fn info() -> ImplInfo {
+    ImplInfo {
+        name: "mock",
+        version: "none",
+        crypto_provider: None,
+        fips: false,
+    }
+}
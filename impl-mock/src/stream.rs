@@ -0,0 +1,20 @@
+use tls_api::spi::TlsStreamOverConnection;
+use tls_api::spi_tls_stream_over_connection_wrapper;
+use tls_api::AsyncSocket;
+
+spi_tls_stream_over_connection_wrapper!(
+    /// Mock stream: [`TlsConnection`](crate::TlsConnection) driven by the async socket.
+    TlsStream,
+    crate::TlsConnection
+);
+
+impl<A: AsyncSocket> TlsStream<A> {
+    pub(crate) fn new(socket: A, conn: crate::TlsConnection) -> TlsStream<A> {
+        TlsStream(TlsStreamOverConnection::new(socket, conn))
+    }
+
+    /// Peer certificate set by [`MockBehavior::peer_certificate`](crate::MockBehavior::peer_certificate).
+    pub fn peer_certificate(&self) -> Option<&[u8]> {
+        self.0.conn.peer_certificate()
+    }
+}
@@ -0,0 +1,265 @@
+use std::future::Future;
+use std::io;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+
+use tls_api::FallbackConnectorBuilder;
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorBuilder;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorBuilder;
+use tls_api::TlsStreamDyn;
+use tls_api_mock::MockBehavior;
+use tls_api_test::block_on;
+use tls_api_test::runtime::AsyncReadExt;
+use tls_api_test::runtime::AsyncWriteExt;
+use tls_api_test::TcpListener;
+use tls_api_test::TcpStream;
+use tls_api_test::BIND_HOST;
+
+/// Accept one connection with `behavior` in a thread and pass the stream to `session`.
+async fn serve<F, R>(behavior: MockBehavior, session: F) -> (u16, thread::JoinHandle<()>)
+where
+    F: FnOnce(anyhow::Result<tls_api_mock::TlsStream<TcpStream>>) -> R + Send + 'static,
+    R: Future<Output = ()>,
+{
+    let acceptor = tls_api_mock::TlsAcceptor::builder_from_der_key(&[], &[])
+        .unwrap()
+        .build()
+        .unwrap();
+    acceptor.script.push(behavior);
+    #[allow(unused_mut)]
+    let mut listener = TcpListener::bind((BIND_HOST, 0)).await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let j = thread::spawn(move || {
+        block_on(async {
+            let socket = listener.accept().await.unwrap().0;
+            session(acceptor.accept_static(socket).await).await;
+        })
+    });
+    (port, j)
+}
+
+fn connector(behavior: MockBehavior) -> tls_api_mock::TlsConnector {
+    let connector = tls_api_mock::TlsConnector::builder()
+        .unwrap()
+        .build()
+        .unwrap();
+    connector.script.push(behavior);
+    connector
+}
+
+#[test]
+fn client_server_der() {
+    tls_api_test::test_client_server_der::<tls_api_mock::TlsConnector, tls_api_mock::TlsAcceptor>();
+}
+
+#[test]
+fn client_server_dyn_der() {
+    tls_api_test::test_client_server_dyn_der(
+        tls_api_mock::TlsConnector::TYPE_DYN,
+        tls_api_mock::TlsAcceptor::TYPE_DYN,
+    );
+}
+
+#[test]
+fn scripted_alpn_and_peer_certificate() {
+    block_on(async {
+        let (port, j) = serve(
+            MockBehavior::default().alpn_protocol(b"h2"),
+            |socket| async {
+                let mut socket = socket.unwrap();
+                assert_eq!(Some(b"h2".to_vec()), socket.get_alpn_protocol().unwrap());
+                assert_eq!(None, socket.peer_certificate());
+                socket.write_all(b"hello").await.unwrap();
+                socket.flush().await.unwrap();
+            },
+        )
+        .await;
+
+        let socket = TcpStream::connect((BIND_HOST, port)).await.unwrap();
+        let connector = connector(
+            MockBehavior::default()
+                .alpn_protocol(b"h2")
+                .peer_certificate(b"cert"),
+        );
+        let mut socket = connector.connect_static("localhost", socket).await.unwrap();
+        assert_eq!(Some(b"h2".to_vec()), socket.get_alpn_protocol().unwrap());
+        assert_eq!(Some(&b"cert"[..]), socket.peer_certificate());
+        assert_eq!(Some("localhost".to_owned()), socket.server_name().unwrap());
+
+        let mut buf = Vec::new();
+        socket.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(b"hello", &buf[..]);
+
+        j.join().unwrap();
+    });
+}
+
+#[test]
+fn handshake_error() {
+    block_on(async {
+        let (port, j) = serve(
+            MockBehavior::default().fail_handshake(io::ErrorKind::InvalidData, "bad certificate"),
+            |socket| async {
+                let err = socket.expect_err("error");
+                let err: &io::Error = err.downcast_ref().expect("io::Error");
+                assert_eq!(io::ErrorKind::InvalidData, err.kind());
+                assert_eq!("bad certificate", err.to_string());
+            },
+        )
+        .await;
+
+        let socket = TcpStream::connect((BIND_HOST, port)).await.unwrap();
+        let connector = connector(MockBehavior::default());
+        let mut socket = connector.connect("localhost", socket).await.unwrap();
+        // Server dropped the socket
+        let mut buf = Vec::new();
+        assert_eq!(0, socket.read_to_end(&mut buf).await.unwrap());
+
+        j.join().unwrap();
+    });
+}
+
+#[test]
+fn close_mid_stream() {
+    block_on(async {
+        let (port, j) = serve(MockBehavior::default().close_after(3), |socket| async {
+            let mut socket = socket.unwrap();
+            let mut buf = [0; 5];
+            let err = socket.read_exact(&mut buf).await.unwrap_err();
+            assert_eq!(io::ErrorKind::UnexpectedEof, err.kind());
+            assert_eq!(b"hel", &buf[..3]);
+            let err = socket.write_all(b"world").await.unwrap_err();
+            assert_eq!(io::ErrorKind::BrokenPipe, err.kind());
+        })
+        .await;
+
+        let socket = TcpStream::connect((BIND_HOST, port)).await.unwrap();
+        let connector = connector(MockBehavior::default());
+        let mut socket = connector.connect("localhost", socket).await.unwrap();
+        socket.write_all(b"hello").await.unwrap();
+        let mut buf = Vec::new();
+        socket.read_to_end(&mut buf).await.unwrap();
+        assert!(buf.is_empty());
+
+        j.join().unwrap();
+    });
+}
+
+#[test]
+fn script_is_consumed_in_order() {
+    let connector = connector(MockBehavior::default().alpn_protocol(b"a"));
+    connector
+        .script
+        .push(MockBehavior::default().fail_handshake(io::ErrorKind::Other, "second"));
+    assert_eq!(2, connector.script.len());
+
+    let first = connector.new_connection("localhost").unwrap();
+    assert_eq!(Some(b"a".to_vec()), first.get_alpn_protocol().unwrap());
+    assert!(connector.new_connection("localhost").is_err());
+    assert!(connector.script.is_empty());
+    // Default behavior when the script is exhausted
+    let third = connector.new_connection("localhost").unwrap();
+    assert_eq!(None, third.get_alpn_protocol().unwrap());
+}
+
+/// Serve `count` connections, replying `world` to `hello`.
+async fn serve_hello(count: usize) -> (u16, thread::JoinHandle<()>) {
+    let acceptor = tls_api_mock::TlsAcceptor::builder_from_der_key(&[], &[])
+        .unwrap()
+        .build()
+        .unwrap();
+    #[allow(unused_mut)]
+    let mut listener = TcpListener::bind((BIND_HOST, 0)).await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let j = thread::spawn(move || {
+        block_on(async {
+            for _ in 0..count {
+                let socket = listener.accept().await.unwrap().0;
+                let mut socket = acceptor.accept(socket).await.unwrap();
+                let mut buf = [0; 5];
+                // Client drops the socket of a failed attempt
+                if socket.read_exact(&mut buf).await.is_ok() {
+                    socket.write_all(b"world").await.unwrap();
+                    socket.flush().await.unwrap();
+                }
+            }
+        })
+    });
+    (port, j)
+}
+
+fn fallback_connector(
+    port: u16,
+    first_error: &str,
+    reconnects: Arc<AtomicUsize>,
+) -> tls_api::TlsConnectorBox {
+    let mut builder = FallbackConnectorBuilder::new(move |_domain: &str| {
+        reconnects.fetch_add(1, Ordering::SeqCst);
+        TcpStream::connect((BIND_HOST, port))
+    });
+    builder.add_connector(
+        connector(MockBehavior::default().fail_handshake(io::ErrorKind::Other, first_error))
+            .into_dyn(),
+    );
+    builder.add_connector(connector(MockBehavior::default().alpn_protocol(b"second")).into_dyn());
+    builder.build().unwrap()
+}
+
+#[test]
+fn fallback_on_retryable_error() {
+    block_on(async {
+        let (port, j) = serve_hello(2).await;
+
+        let reconnects = Arc::new(AtomicUsize::new(0));
+        let connector = fallback_connector(
+            port,
+            "received fatal alert: ProtocolVersion",
+            reconnects.clone(),
+        );
+        let socket = TcpStream::connect((BIND_HOST, port)).await.unwrap();
+        let mut socket = connector.connect("localhost", socket).await.unwrap();
+        assert_eq!(1, reconnects.load(Ordering::SeqCst));
+        assert_eq!(
+            Some(b"second".to_vec()),
+            socket.get_alpn_protocol().unwrap()
+        );
+
+        socket.write_all(b"hello").await.unwrap();
+        let mut buf = [0; 5];
+        socket.read_exact(&mut buf).await.unwrap();
+        assert_eq!(b"world", &buf);
+
+        j.join().unwrap();
+    });
+}
+
+#[test]
+fn no_fallback_on_other_error() {
+    block_on(async {
+        let (port, j) = serve_hello(1).await;
+
+        let reconnects = Arc::new(AtomicUsize::new(0));
+        let connector = fallback_connector(
+            port,
+            "invalid peer certificate: UnknownIssuer",
+            reconnects.clone(),
+        );
+        let socket = TcpStream::connect((BIND_HOST, port)).await.unwrap();
+        let err = connector.connect("localhost", socket).await.unwrap_err();
+        assert_eq!("invalid peer certificate: UnknownIssuer", err.to_string());
+        assert_eq!(0, reconnects.load(Ordering::SeqCst));
+
+        j.join().unwrap();
+    });
+}
+
+#[test]
+fn fallback_async_only() {
+    let connector = fallback_connector(0, "", Arc::new(AtomicUsize::new(0)));
+    assert!(connector.new_connection("localhost").is_err());
+}
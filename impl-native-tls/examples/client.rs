@@ -1,10 +1,16 @@
 use std::fs;
 
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 use async_std::net::TcpStream;
 
 #[cfg(feature = "runtime-tokio")]
 use tokio::net::TcpStream;
+// smol
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+use tls_api_test::TcpStream;
 
 use test_cert_gen::Cert;
 use tls_api::TlsConnector;
@@ -1,11 +1,18 @@
 use crate::handshake::HandshakeFuture;
 
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 pub struct TlsAcceptorBuilder(pub native_tls::TlsAcceptorBuilder);
 pub struct TlsAcceptor(pub native_tls::TlsAcceptor);
@@ -21,6 +28,26 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
         Err(crate::Error::AlpnNotSupported.into())
     }
 
+    fn add_client_auth_root_certificate(&mut self, _cert: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_ocsp_response(&mut self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
     fn underlying_mut(&mut self) -> &mut native_tls::TlsAcceptorBuilder {
         &mut self.0
     }
@@ -40,6 +67,14 @@ impl TlsAcceptor {
     {
         HandshakeFuture::Initial(move |s| self.0.accept(s), AsyncIoAsSyncIo::new(stream))
     }
+
+    /// Blocking version of [`accept_impl`](Self::accept_impl).
+    pub fn accept_blocking_impl(
+        &self,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.0.accept(stream))
+    }
 }
 
 impl tls_api::TlsAcceptor for TlsAcceptor {
@@ -52,22 +87,38 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
         &mut self.0
     }
 
-    const IMPLEMENTED: bool = true;
-    /// Server side of `native-tls` does not support ALPN,
-    /// because `security-framework` does not support it.
-    const SUPPORTS_ALPN: bool = false;
-    const SUPPORTS_DER_KEYS: bool = false;
-    const SUPPORTS_PKCS12_KEYS: bool = true;
+    // Server side of `native-tls` does not support ALPN,
+    // because `security-framework` does not support it.
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        pkcs12_keys: true,
+        blocking: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
     }
 
+    fn set_ocsp_response(&self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
     fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<Self::Builder> {
         Ok(TlsAcceptorBuilder(native_tls::TlsAcceptor::builder(
             native_tls::Identity::from_pkcs12(pkcs12, passphrase).map_err(anyhow::Error::new)?,
         )))
     }
 
-    spi_acceptor_common!();
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.accept_blocking_impl(stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        Err(crate::Error::ConnectionNotSupported.into())
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
 }
@@ -0,0 +1,98 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpStream;
+use std::result;
+
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsStreamBlockingDyn;
+
+/// Blocking TLS stream: [`native_tls::TlsStream`] over [`TcpStream`].
+#[derive(Debug)]
+pub struct TlsStreamBlocking(native_tls::TlsStream<TcpStream>);
+
+pub(crate) fn handshake(
+    r: result::Result<native_tls::TlsStream<TcpStream>, native_tls::HandshakeError<TcpStream>>,
+) -> anyhow::Result<TlsStreamBlocking> {
+    match r {
+        Ok(stream) => Ok(TlsStreamBlocking(stream)),
+        Err(native_tls::HandshakeError::Failure(e)) => Err(anyhow::Error::new(e)),
+        // Only possible if socket has a timeout
+        Err(native_tls::HandshakeError::WouldBlock(_)) => Err(anyhow::Error::new(io::Error::from(
+            io::ErrorKind::WouldBlock,
+        ))),
+    }
+}
+
+impl Read for TlsStreamBlocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStreamBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlocking {
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.negotiated_alpn().map_err(anyhow::Error::new)
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Err(crate::Error::ServerNameNotSupported.into())
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("native-tls").into())
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => self
+                .0
+                .tls_server_end_point()?
+                .ok_or_else(|| ChannelBindingError::NoServerCertificate.into()),
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::info()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        self.0.shutdown()?;
+        self.0.get_ref().shutdown(Shutdown::Write)
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        self.0.get_mut()
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        self.0.get_ref()
+    }
+}
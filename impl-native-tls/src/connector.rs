@@ -1,4 +1,6 @@
+use std::net::TcpStream;
 use std::str;
+use std::sync::Arc;
 
 use crate::handshake::HandshakeFuture;
 
@@ -7,7 +9,13 @@ use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 pub struct TlsConnectorBuilder {
     pub builder: native_tls::TlsConnectorBuilder,
@@ -51,6 +59,26 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
         Ok(())
     }
 
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_client_certificate(&mut self, _cert: &[u8], _key: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn set_ocsp_stapling(&mut self, _mode: OcspStapling) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
     fn build(self) -> anyhow::Result<TlsConnector> {
         let connector = self.builder.build().map_err(anyhow::Error::new)?;
         Ok(TlsConnector {
@@ -74,6 +102,15 @@ impl TlsConnector {
             AsyncIoAsSyncIo::new(stream),
         )
     }
+
+    /// Blocking version of [`connect_impl`](Self::connect_impl).
+    pub fn connect_blocking_impl(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.connector.connect(domain, stream))
+    }
 }
 
 impl tls_api::TlsConnector for TlsConnector {
@@ -86,8 +123,13 @@ impl tls_api::TlsConnector for TlsConnector {
         &mut self.connector
     }
 
-    const IMPLEMENTED: bool = true;
-    const SUPPORTS_ALPN: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        blocking: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
@@ -101,5 +143,18 @@ impl tls_api::TlsConnector for TlsConnector {
         })
     }
 
-    spi_connector_common!();
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.connect_blocking_impl(domain, stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self, _domain: &str) -> anyhow::Result<TlsConnection> {
+        Err(crate::Error::ConnectionNotSupported.into())
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
 }
@@ -6,4 +6,16 @@ pub(crate) enum Error {
     AlpnNotSupported,
     #[error("given alpn protocol is not UTF-8: {}", _0)]
     AlpnProtocolNotUtf8(Utf8Error),
+    #[error("native-tls does not support certificate revocation lists")]
+    CrlNotSupported,
+    #[error("native-tls does not support client authentication")]
+    ClientAuthNotSupported,
+    #[error("native-tls does not support OCSP stapling")]
+    OcspStaplingNotSupported,
+    #[error("native-tls does not support key logging")]
+    KeyLogNotSupported,
+    #[error("native-tls does not expose server name")]
+    ServerNameNotSupported,
+    #[error("native-tls does not support sans-IO connections")]
+    ConnectionNotSupported,
 }
@@ -3,6 +3,7 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod acceptor;
+mod blocking;
 mod connector;
 mod error;
 mod handshake;
@@ -13,13 +14,60 @@ pub use acceptor::TlsAcceptorBuilder;
 pub use connector::TlsConnector;
 pub use connector::TlsConnectorBuilder;
 
+pub use blocking::TlsStreamBlocking;
 pub(crate) use error::Error;
 pub use stream::TlsStream;
 use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
+
+/// Version requirement of `native-tls` in `Cargo.toml`, checked by a test.
+#[cfg_attr(
+    not(any(target_os = "windows", target_os = "macos", target_os = "ios")),
+    allow(dead_code)
+)]
+const NATIVE_TLS_VERSION: &str = "^0.2";
+
+/// OpenSSL version where `native-tls` uses OpenSSL.
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "ios")))]
+fn version() -> &'static str {
+    openssl::version::version()
+}
+
+/// System library version is not available, report `native-tls` requirement.
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "ios"))]
+fn version() -> &'static str {
+    NATIVE_TLS_VERSION
+}
 
 pub(crate) fn info() -> ImplInfo {
     ImplInfo {
         name: "native-tls",
-        version: "unknown",
+        version: version(),
+        crypto_provider: None,
+        fips: false,
+    }
+}
+
+/// Secure Transport on macOS and iOS does not implement TLS 1.3,
+/// OpenSSL 3 allows TLS 1.0 and 1.1 only at security level 0.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] = &[
+    ProtocolVersion::Tls10,
+    ProtocolVersion::Tls11,
+    ProtocolVersion::Tls12,
+];
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] =
+    &[ProtocolVersion::Tls12, ProtocolVersion::Tls13];
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn dep_version() {
+        tls_api_test::test_dep_version(
+            include_str!("../Cargo.toml"),
+            "native-tls",
+            super::NATIVE_TLS_VERSION,
+        );
     }
 }
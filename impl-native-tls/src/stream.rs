@@ -1,5 +1,6 @@
 use native_tls::TlsStream as native_tls_TlsStream;
 use std::fmt;
+use std::io;
 use std::marker::PhantomData;
 use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::async_as_sync::AsyncWrapperOps;
@@ -7,6 +8,7 @@ use tls_api::async_as_sync::TlsStreamOverSyncIo;
 use tls_api::spi_async_socket_impl_delegate;
 use tls_api::spi_tls_stream_over_sync_io_wrapper;
 use tls_api::AsyncSocket;
+use tls_api::ChannelBindingError;
 use tls_api::ImplInfo;
 
 spi_tls_stream_over_sync_io_wrapper!(TlsStream, native_tls_TlsStream);
@@ -43,4 +45,30 @@ where
     fn get_alpn_protocol(w: &Self::SyncWrapper) -> anyhow::Result<Option<Vec<u8>>> {
         w.negotiated_alpn().map_err(anyhow::Error::new)
     }
+
+    fn server_name(_w: &Self::SyncWrapper) -> anyhow::Result<Option<String>> {
+        Err(crate::Error::ServerNameNotSupported.into())
+    }
+
+    fn ocsp_response(_w: &Self::SyncWrapper) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        _w: &Self::SyncWrapper,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("native-tls").into())
+    }
+
+    fn tls_server_end_point(w: &Self::SyncWrapper) -> anyhow::Result<Vec<u8>> {
+        w.tls_server_end_point()?
+            .ok_or_else(|| ChannelBindingError::NoServerCertificate.into())
+    }
+
+    fn shutdown(w: &mut Self::SyncWrapper) -> io::Result<()> {
+        w.shutdown()
+    }
 }
@@ -1,10 +1,17 @@
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 use std::fmt;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 pub struct TlsAcceptorBuilder(pub ());
 
@@ -17,6 +24,31 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
         Err(crate::Error::Alpn.into())
     }
 
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let _ = cert;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let _ = crl;
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        let _ = policy;
+        Ok(())
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        let _ = ocsp_response;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        let _ = key_log;
+        Ok(())
+    }
+
     fn underlying_mut(&mut self) -> &mut Self::Underlying {
         &mut self.0
     }
@@ -43,10 +75,12 @@ impl TlsAcceptor {
 impl tls_api::TlsAcceptor for TlsAcceptor {
     type Builder = TlsAcceptorBuilder;
 
-    const IMPLEMENTED: bool = true;
-    const SUPPORTS_ALPN: bool = false;
-    const SUPPORTS_DER_KEYS: bool = false;
-    const SUPPORTS_PKCS12_KEYS: bool = false;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        blocking: true,
+        connection: true,
+        ..Capabilities::NONE
+    };
 
     type Underlying = ();
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
@@ -59,5 +93,18 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
         crate::info()
     }
 
-    spi_acceptor_common!();
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        let _ = ocsp_response;
+        Ok(())
+    }
+
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        Ok(TlsStreamBlocking::new(crate::TlsStreamBlocking(stream)))
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        Ok(TlsConnection::new(crate::TlsConnection::default()))
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
 }
@@ -0,0 +1,73 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpStream;
+
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsStreamBlockingDyn;
+
+/// Plain [`TcpStream`] pretending to be a blocking TLS stream.
+#[derive(Debug)]
+pub struct TlsStreamBlocking(pub TcpStream);
+
+impl Read for TlsStreamBlocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for TlsStreamBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlocking {
+    fn impl_info(&self) -> ImplInfo {
+        crate::info()
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Err(crate::Error::Alpn.into())
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("not-tls").into())
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::NotSupported(kind, "not-tls").into())
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        self.0.shutdown(Shutdown::Write)
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        &mut self.0
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        &self.0
+    }
+}
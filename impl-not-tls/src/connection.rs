@@ -0,0 +1,94 @@
+use std::io;
+
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsHandshakeState;
+
+/// Sans-IO connection which passes data through as is.
+///
+/// Handshake is complete from the start.
+#[derive(Debug, Default)]
+pub struct TlsConnection {
+    /// Data received from the peer, not yet read.
+    incoming: Vec<u8>,
+    /// Data written, not yet taken.
+    outgoing: Vec<u8>,
+}
+
+/// Move data from the front of `from` to `to`.
+fn take_front(from: &mut Vec<u8>, to: &mut [u8]) -> usize {
+    let len = from.len().min(to.len());
+    to[..len].copy_from_slice(&from[..len]);
+    from.drain(..len);
+    len
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        TlsHandshakeState::Complete
+    }
+
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.incoming.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn wants_write(&self) -> bool {
+        !self.outgoing.is_empty()
+    }
+
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(take_front(&mut self.outgoing, buf))
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.incoming.is_empty() && !buf.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        Ok(take_front(&mut self.incoming, buf))
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.outgoing.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn requires_close_notify(&self) -> bool {
+        false
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Err(crate::Error::Alpn.into())
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("not-tls").into())
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::NotSupported(kind, "not-tls").into())
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::info()
+    }
+}
@@ -1,9 +1,17 @@
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 pub struct TlsConnectorBuilder(pub ());
 
@@ -30,6 +38,31 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
         Ok(())
     }
 
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let _ = crl;
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        let _ = policy;
+        Ok(())
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        let _ = (cert, key);
+        Ok(())
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        let _ = mode;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        let _ = key_log;
+        Ok(())
+    }
+
     fn build(self) -> anyhow::Result<Self::Connector> {
         Ok(TlsConnector(self.0))
     }
@@ -54,8 +87,11 @@ impl TlsConnector {
 impl tls_api::TlsConnector for TlsConnector {
     type Builder = TlsConnectorBuilder;
 
-    const IMPLEMENTED: bool = false;
-    const SUPPORTS_ALPN: bool = false;
+    const CAPABILITIES: Capabilities = Capabilities {
+        blocking: true,
+        connection: true,
+        ..Capabilities::NONE
+    };
 
     type Underlying = ();
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
@@ -72,5 +108,19 @@ impl tls_api::TlsConnector for TlsConnector {
         Ok(TlsConnectorBuilder(()))
     }
 
-    spi_connector_common!();
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        let _ = domain;
+        Ok(TlsStreamBlocking::new(crate::TlsStreamBlocking(stream)))
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        let _ = domain;
+        Ok(TlsConnection::new(crate::TlsConnection::default()))
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
 }
@@ -8,6 +8,8 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod acceptor;
+mod blocking;
+mod connection;
 mod connector;
 mod error;
 mod stream;
@@ -16,6 +18,8 @@ pub(crate) use error::Error;
 
 pub use acceptor::TlsAcceptor;
 pub use acceptor::TlsAcceptorBuilder;
+pub use blocking::TlsStreamBlocking;
+pub use connection::TlsConnection;
 pub use connector::TlsConnector;
 pub use connector::TlsConnectorBuilder;
 pub use stream::TlsStream;
@@ -26,5 +30,7 @@ pub(crate) fn info() -> ImplInfo {
     ImplInfo {
         name: "not-tls",
         version: "none",
+        crypto_provider: None,
+        fips: false,
     }
 }
@@ -1,11 +1,9 @@
-use std::io;
 use std::pin::Pin;
-use std::task::Context;
-use std::task::Poll;
-use tls_api::runtime::AsyncRead;
-use tls_api::runtime::AsyncWrite;
 use tls_api::spi::TlsStreamWithUpcastDyn;
+use tls_api::spi_async_socket_impl_delegate;
 use tls_api::AsyncSocket;
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
 use tls_api::ImplInfo;
 use tls_api::TlsStreamDyn;
 use tls_api::TlsStreamWithSocketDyn;
@@ -16,9 +14,13 @@ where
     A: AsyncSocket;
 
 impl<A: AsyncSocket> TlsStream<A> {
-    fn get_inner(self: Pin<&mut Self>) -> Pin<&mut A> {
+    fn deref_pin_mut_for_impl_socket(self: Pin<&mut Self>) -> Pin<&mut A> {
         Pin::new(&mut self.get_mut().0)
     }
+
+    fn deref_for_impl_socket(&self) -> &A {
+        &self.0
+    }
 }
 
 impl<A: AsyncSocket> TlsStreamDyn for TlsStream<A> {
@@ -30,6 +32,27 @@ impl<A: AsyncSocket> TlsStreamDyn for TlsStream<A> {
         Err(crate::Error::Alpn.into())
     }
 
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("not-tls").into())
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::NotSupported(kind, "not-tls").into())
+    }
+
     fn get_socket_dyn_mut(&mut self) -> &mut dyn AsyncSocket {
         &mut self.0
     }
@@ -55,55 +78,4 @@ impl<A: AsyncSocket> TlsStreamWithUpcastDyn<A> for TlsStream<A> {
     }
 }
 
-impl<A: AsyncSocket> AsyncRead for TlsStream<A> {
-    #[cfg(feature = "runtime-tokio")]
-    fn poll_read(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut tokio::io::ReadBuf,
-    ) -> Poll<io::Result<()>> {
-        self.get_inner().poll_read(cx, buf)
-    }
-
-    #[cfg(feature = "runtime-async-std")]
-    fn poll_read(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut [u8],
-    ) -> Poll<io::Result<usize>> {
-        self.get_inner().poll_read(cx, buf)
-    }
-}
-
-impl<A: AsyncSocket> AsyncWrite for TlsStream<A> {
-    fn poll_write(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &[u8],
-    ) -> Poll<io::Result<usize>> {
-        self.get_inner().poll_write(cx, buf)
-    }
-
-    #[cfg(feature = "runtime-async-std")]
-    fn poll_write_vectored(
-        self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        bufs: &[std::io::IoSlice<'_>],
-    ) -> Poll<io::Result<usize>> {
-        self.get_inner().poll_write_vectored(cx, bufs)
-    }
-
-    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.get_inner().poll_flush(cx)
-    }
-
-    #[cfg(feature = "runtime-tokio")]
-    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.get_inner().poll_shutdown(cx)
-    }
-
-    #[cfg(feature = "runtime-async-std")]
-    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        self.get_inner().poll_close(cx)
-    }
-}
+spi_async_socket_impl_delegate!(TlsStream<S>);
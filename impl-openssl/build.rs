@@ -1,3 +1,14 @@
+use std::env;
+
 fn main() {
     tls_api_test::gen_tests_and_benches();
+
+    // Set by `openssl-sys` build script, same as `openssl` crate does
+    println!("cargo:rustc-check-cfg=cfg(ossl300)");
+    if let Ok(version) = env::var("DEP_OPENSSL_VERSION_NUMBER") {
+        let version = u64::from_str_radix(&version, 16).unwrap();
+        if version >= 0x3000_0000 {
+            println!("cargo:rustc-cfg=ossl300");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 use async_std::net::TcpStream;
 use std::fs;
 use test_cert_gen::Cert;
@@ -7,6 +7,12 @@ use tls_api::TlsConnectorBuilder;
 use tls_api_test::block_on;
 #[cfg(feature = "runtime-tokio")]
 use tokio::net::TcpStream;
+// smol
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+use tls_api_test::TcpStream;
 
 async fn run() {
     let socket = TcpStream::connect(("127.0.0.1", 4433)).await.unwrap();
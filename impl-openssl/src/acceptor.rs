@@ -1,19 +1,35 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
 use openssl::pkcs12::ParsedPkcs12;
 
-use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 use crate::encode_alpn_protos;
-use crate::handshake::HandshakeFuture;
+use crate::ocsp::StapledOcspResponse;
+use crate::select_alpn_proto;
 use anyhow::Context;
-use std::future::Future;
 
-pub struct TlsAcceptorBuilder(pub openssl::ssl::SslAcceptorBuilder);
+pub struct TlsAcceptorBuilder {
+    pub builder: openssl::ssl::SslAcceptorBuilder,
+    pub client_auth: bool,
+    pub crls: Vec<openssl::x509::X509Crl>,
+    pub revocation_policy: RevocationPolicy,
+    ocsp_response: StapledOcspResponse,
+}
 
-pub struct TlsAcceptor(pub openssl::ssl::SslAcceptor);
+pub struct TlsAcceptor {
+    pub acceptor: openssl::ssl::SslAcceptor,
+    ocsp_response: StapledOcspResponse,
+}
 
 fn to_openssl_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<ParsedPkcs12> {
     let pkcs12 = openssl::pkcs12::Pkcs12::from_der(pkcs12)?;
@@ -26,44 +42,118 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
     type Underlying = openssl::ssl::SslAcceptorBuilder;
 
     fn underlying_mut(&mut self) -> &mut openssl::ssl::SslAcceptorBuilder {
-        &mut self.0
+        &mut self.builder
     }
 
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
         let protocols = encode_alpn_protos(protocols)?;
-        self.0
+        self.builder
             .set_alpn_select_callback(move |_ssl, client_protocols| {
-                match openssl::ssl::select_next_proto(&protocols, client_protocols) {
-                    Some(selected) => Ok(selected),
-                    None => Err(openssl::ssl::AlpnError::NOACK),
-                }
+                select_alpn_proto(&protocols, client_protocols)
             });
         Ok(())
     }
 
-    fn build(self) -> anyhow::Result<TlsAcceptor> {
-        Ok(TlsAcceptor(self.0.build()))
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let cert = openssl::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .add_client_ca(&cert)
+            .map_err(anyhow::Error::new)?;
+        self.builder
+            .cert_store_mut()
+            .add_cert(cert)
+            .map_err(anyhow::Error::new)?;
+        // Session resumption fails without session id context when peer is verified
+        self.builder
+            .set_session_id_context(b"tls-api")
+            .map_err(anyhow::Error::new)?;
+        self.builder.set_verify(client_auth_verify_mode());
+        self.client_auth = true;
+
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let crl = openssl::x509::X509Crl::from_der(crl).map_err(anyhow::Error::new)?;
+        self.crls.push(crl);
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.revocation_policy = policy;
+        Ok(())
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.ocsp_response.set(ocsp_response);
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        crate::key_log::install(&mut self.builder, key_log);
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<TlsAcceptor> {
+        if !self.crls.is_empty() && !self.client_auth {
+            return Err(crate::Error::CrlWithoutClientAuth.into());
+        }
+        crate::crl::configure_revocation(
+            &mut self.builder,
+            client_auth_verify_mode(),
+            &self.crls,
+            self.revocation_policy,
+        )?;
+        self.ocsp_response.install(&mut self.builder)?;
+        Ok(TlsAcceptor {
+            acceptor: self.builder.build(),
+            ocsp_response: self.ocsp_response,
+        })
     }
 }
 
+fn client_auth_verify_mode() -> openssl::ssl::SslVerifyMode {
+    openssl::ssl::SslVerifyMode::PEER | openssl::ssl::SslVerifyMode::FAIL_IF_NO_PEER_CERT
+}
+
 impl TlsAcceptorBuilder {
+    fn new(builder: openssl::ssl::SslAcceptorBuilder) -> TlsAcceptorBuilder {
+        TlsAcceptorBuilder {
+            builder,
+            client_auth: false,
+            crls: Vec::new(),
+            revocation_policy: RevocationPolicy::default(),
+            ocsp_response: StapledOcspResponse::default(),
+        }
+    }
+
     pub fn builder_mut(&mut self) -> &mut openssl::ssl::SslAcceptorBuilder {
-        &mut self.0
+        &mut self.builder
     }
 }
 
 impl TlsAcceptor {
-    fn accept_impl<'a, S>(
-        &'a self,
-        stream: S,
-    ) -> impl Future<Output = anyhow::Result<crate::TlsStream<S>>> + 'a
+    async fn accept_impl<S>(&self, stream: S) -> anyhow::Result<crate::TlsStream<S>>
     where
         S: AsyncSocket,
     {
-        HandshakeFuture::Initial(
-            move |stream| self.0.accept(stream),
-            AsyncIoAsSyncIo::new(stream),
-        )
+        let conn = self.new_conn()?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self) -> anyhow::Result<crate::TlsConnection> {
+        let mut ssl = openssl::ssl::Ssl::new(self.acceptor.context())?;
+        ssl.set_accept_state();
+        crate::TlsConnection::new(ssl)
+    }
+
+    /// Blocking version of [`accept_impl`](Self::accept_impl).
+    pub fn accept_blocking_impl(
+        &self,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.acceptor.accept(stream))
     }
 }
 
@@ -74,18 +164,34 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
 
     fn underlying_mut(&mut self) -> &mut Self::Underlying {
-        &mut self.0
+        &mut self.acceptor
     }
 
-    const IMPLEMENTED: bool = true;
-    const SUPPORTS_ALPN: bool = true;
-    const SUPPORTS_DER_KEYS: bool = true;
-    const SUPPORTS_PKCS12_KEYS: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        der_keys: true,
+        pkcs12_keys: true,
+        client_auth: true,
+        crl: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::into()
     }
 
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.ocsp_response.set(ocsp_response);
+        Ok(())
+    }
+
     fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<TlsAcceptorBuilder> {
         let cert = openssl::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
         let pkey = openssl::pkey::PKey::private_key_from_der(key).map_err(anyhow::Error::new)?;
@@ -101,7 +207,7 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
             .set_private_key(pkey.as_ref())
             .map_err(anyhow::Error::new)?;
 
-        Ok(TlsAcceptorBuilder(builder))
+        Ok(TlsAcceptorBuilder::new(builder))
     }
 
     fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<TlsAcceptorBuilder> {
@@ -125,8 +231,26 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
             .set_private_key(&pkcs12.pkey)
             .map_err(anyhow::Error::new)?;
 
-        Ok(TlsAcceptorBuilder(builder))
+        Ok(TlsAcceptorBuilder::new(builder))
+    }
+
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.accept_blocking_impl(stream)
+            .map(TlsStreamBlocking::new)
     }
 
-    spi_acceptor_common!();
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.new_conn().map(TlsConnection::new)
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
+}
+
+#[cfg(target_os = "linux")]
+impl tls_api::KtlsAcceptor for TlsAcceptor {
+    fn accept_ktls(&self, stream: TcpStream) -> anyhow::Result<tls_api::MaybeKtlsStream> {
+        let mut ssl = openssl::ssl::Ssl::new(self.acceptor.context())?;
+        ssl.set_accept_state();
+        crate::ktls::handshake(ssl, stream)
+    }
 }
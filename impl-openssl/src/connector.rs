@@ -1,22 +1,32 @@
-use tls_api::async_as_sync::AsyncIoAsSyncIo;
+use std::net::TcpStream;
+use std::sync::Arc;
+
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
-use tls_api::BoxFuture;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RetryableError;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 use crate::encode_alpn_protos;
-use crate::handshake::HandshakeFuture;
-use std::future::Future;
 
 pub struct TlsConnectorBuilder {
     pub builder: openssl::ssl::SslConnectorBuilder,
     pub verify_hostname: bool,
+    pub crls: Vec<openssl::x509::X509Crl>,
+    pub revocation_policy: RevocationPolicy,
+    pub ocsp_stapling: OcspStapling,
 }
 
 pub struct TlsConnector {
     pub connector: openssl::ssl::SslConnector,
     pub verify_hostname: bool,
+    pub ocsp_stapling: OcspStapling,
 }
 
 impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
@@ -50,10 +60,53 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
         Ok(())
     }
 
-    fn build(self) -> anyhow::Result<TlsConnector> {
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        let crl = openssl::x509::X509Crl::from_der(crl).map_err(anyhow::Error::new)?;
+        self.crls.push(crl);
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.revocation_policy = policy;
+        Ok(())
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        let cert = openssl::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+        let pkey = openssl::pkey::PKey::private_key_from_der(key).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .set_certificate(&cert)
+            .map_err(anyhow::Error::new)?;
+        self.builder
+            .set_private_key(&pkey)
+            .map_err(anyhow::Error::new)?;
+
+        Ok(())
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.ocsp_stapling = mode;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        crate::key_log::install(&mut self.builder, key_log);
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<TlsConnector> {
+        crate::crl::configure_revocation(
+            &mut self.builder,
+            openssl::ssl::SslVerifyMode::PEER,
+            &self.crls,
+            self.revocation_policy,
+        )?;
+        crate::ocsp::configure_client(&mut self.builder, self.ocsp_stapling)?;
         Ok(TlsConnector {
             connector: self.builder.build(),
             verify_hostname: self.verify_hostname,
+            ocsp_stapling: self.ocsp_stapling,
         })
     }
 }
@@ -65,23 +118,40 @@ impl TlsConnectorBuilder {
 }
 
 impl TlsConnector {
-    pub fn connect_impl<'a, S>(
-        &'a self,
-        domain: &'a str,
+    fn configure(&self) -> anyhow::Result<openssl::ssl::ConnectConfiguration> {
+        let client_configuration = self.connector.configure()?;
+        let mut client_configuration = client_configuration.verify_hostname(self.verify_hostname);
+        if self.ocsp_stapling != OcspStapling::Disabled {
+            client_configuration.set_status_type(openssl::ssl::StatusType::OCSP)?;
+        }
+        Ok(client_configuration)
+    }
+
+    pub async fn connect_impl<S>(
+        &self,
+        domain: &str,
         stream: S,
-    ) -> impl Future<Output = anyhow::Result<crate::TlsStream<S>>> + 'a
+    ) -> anyhow::Result<crate::TlsStream<S>>
     where
         S: AsyncSocket,
     {
-        let client_configuration = match self.connector.configure() {
-            Ok(client_configuration) => client_configuration,
-            Err(e) => return BoxFuture::new(async { Err(anyhow::Error::new(e)) }),
-        };
-        let client_configuration = client_configuration.verify_hostname(self.verify_hostname);
-        BoxFuture::new(HandshakeFuture::Initial(
-            move |stream| client_configuration.connect(domain, stream),
-            AsyncIoAsSyncIo::new(stream),
-        ))
+        let conn = self.new_conn(domain)?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self, domain: &str) -> anyhow::Result<crate::TlsConnection> {
+        let mut ssl = self.configure()?.into_ssl(domain)?;
+        ssl.set_connect_state();
+        crate::TlsConnection::new(ssl)
+    }
+
+    /// Blocking version of [`connect_impl`](Self::connect_impl).
+    pub fn connect_blocking_impl(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        crate::blocking::handshake(self.configure()?.connect(domain, stream))
     }
 }
 
@@ -95,8 +165,19 @@ impl tls_api::TlsConnector for TlsConnector {
         &mut self.connector
     }
 
-    const IMPLEMENTED: bool = true;
-    const SUPPORTS_ALPN: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        crl: true,
+        client_auth: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::into()
@@ -108,8 +189,41 @@ impl tls_api::TlsConnector for TlsConnector {
         Ok(TlsConnectorBuilder {
             builder,
             verify_hostname: true,
+            crls: Vec::new(),
+            revocation_policy: RevocationPolicy::default(),
+            ocsp_stapling: OcspStapling::default(),
         })
     }
 
-    spi_connector_common!();
+    fn classify_error(error: &anyhow::Error) -> Option<RetryableError> {
+        crate::error::classify(tls_api::spi::find_error(error)?)
+    }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.connect_blocking_impl(domain, stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.new_conn(domain).map(TlsConnection::new)
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
+}
+
+#[cfg(target_os = "linux")]
+impl tls_api::KtlsConnector for TlsConnector {
+    fn connect_ktls(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<tls_api::MaybeKtlsStream> {
+        let mut ssl = self.configure()?.into_ssl(domain)?;
+        ssl.set_connect_state();
+        crate::ktls::handshake(ssl, stream)
+    }
 }
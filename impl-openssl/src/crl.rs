@@ -0,0 +1,67 @@
+//! Certificate revocation lists
+
+use std::os::raw::c_int;
+
+use foreign_types::ForeignType;
+use foreign_types::ForeignTypeRef;
+use openssl::ssl::SslContextBuilder;
+use openssl::ssl::SslVerifyMode;
+use openssl::x509::store::X509StoreBuilderRef;
+use openssl::x509::verify::X509VerifyFlags;
+use openssl::x509::X509Crl;
+
+use tls_api::RevocationPolicy;
+
+extern "C" {
+    // Not exposed by `openssl-sys`
+    fn X509_STORE_add_crl(
+        store: *mut openssl_sys::X509_STORE,
+        x: *mut openssl_sys::X509_CRL,
+    ) -> c_int;
+}
+
+fn add_crl(store: &mut X509StoreBuilderRef, crl: &X509Crl) -> anyhow::Result<()> {
+    // `X509_STORE_add_crl` increments the reference count of CRL
+    let r = unsafe { X509_STORE_add_crl(store.as_ptr(), crl.as_ptr()) };
+    if r <= 0 {
+        return Err(anyhow::Error::new(openssl::error::ErrorStack::get()));
+    }
+    Ok(())
+}
+
+/// Add CRLs to the certificate store and enable CRL checks according to the policy.
+///
+/// Does nothing if CRL list is empty.
+pub(crate) fn configure_revocation(
+    builder: &mut SslContextBuilder,
+    verify_mode: SslVerifyMode,
+    crls: &[X509Crl],
+    policy: RevocationPolicy,
+) -> anyhow::Result<()> {
+    if crls.is_empty() {
+        return Ok(());
+    }
+
+    for crl in crls {
+        add_crl(builder.cert_store_mut(), crl)?;
+    }
+
+    let flags = match policy {
+        RevocationPolicy::HardFail | RevocationPolicy::SoftFail => {
+            X509VerifyFlags::CRL_CHECK | X509VerifyFlags::CRL_CHECK_ALL
+        }
+        RevocationPolicy::LeafOnly => X509VerifyFlags::CRL_CHECK,
+    };
+    builder
+        .verify_param_mut()
+        .set_flags(flags)
+        .map_err(anyhow::Error::new)?;
+
+    if policy == RevocationPolicy::SoftFail {
+        builder.set_verify_callback(verify_mode, |preverify_ok, ctx| {
+            preverify_ok || ctx.error().as_raw() == openssl_sys::X509_V_ERR_UNABLE_TO_GET_CRL
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+
+use openssl::ex_data::Index;
+use openssl::ssl::Ssl;
+use openssl::ssl::SslContextBuilder;
+use openssl::ssl::SslMethod;
+use openssl::ssl::SslOptions;
+use tls_api::AsyncDatagramSocket;
+use tls_api::BoxFuture;
+use tls_api::Capabilities;
+use tls_api::DtlsStream;
+use tls_api::ImplInfo;
+
+use crate::dtls_stream::DEFAULT_MTU;
+use crate::encode_alpn_protos;
+use crate::select_alpn_proto;
+
+pub struct DtlsAcceptorBuilder {
+    pub builder: SslContextBuilder,
+    pub mtu: u32,
+    peer_addr_index: Index<Ssl, SocketAddr>,
+}
+
+pub struct DtlsAcceptor {
+    pub context: openssl::ssl::SslContext,
+    pub mtu: u32,
+    peer_addr_index: Index<Ssl, SocketAddr>,
+}
+
+/// `Ssl` ex data index holding the peer address for cookie callbacks.
+///
+/// Indices are never freed, so it is allocated once per process.
+fn peer_addr_index() -> anyhow::Result<Index<Ssl, SocketAddr>> {
+    static INDEX: OnceLock<Index<Ssl, SocketAddr>> = OnceLock::new();
+    if let Some(index) = INDEX.get() {
+        return Ok(*index);
+    }
+    let index = Ssl::new_ex_index()?;
+    Ok(*INDEX.get_or_init(|| index))
+}
+
+/// Cookie is a keyed hash of the client address: the client proves
+/// it can receive datagrams sent to that address.
+fn cookie(secret: &[u8], peer_addr: &SocketAddr) -> [u8; 32] {
+    let mut hasher = openssl::sha::Sha256::new();
+    hasher.update(secret);
+    hasher.update(peer_addr.to_string().as_bytes());
+    hasher.finish()
+}
+
+fn configure_cookie_exchange(
+    builder: &mut SslContextBuilder,
+    peer_addr_index: Index<Ssl, SocketAddr>,
+) -> anyhow::Result<()> {
+    let mut secret = [0; 32];
+    openssl::rand::rand_bytes(&mut secret)?;
+
+    builder.set_options(SslOptions::COOKIE_EXCHANGE);
+    builder.set_cookie_generate_cb(move |ssl, buf| {
+        let peer_addr = match ssl.ex_data(peer_addr_index) {
+            Some(peer_addr) => peer_addr,
+            None => return Err(openssl::error::ErrorStack::get()),
+        };
+        let cookie = cookie(&secret, peer_addr);
+        buf[..cookie.len()].copy_from_slice(&cookie);
+        Ok(cookie.len())
+    });
+    builder.set_cookie_verify_cb(move |ssl, received| match ssl.ex_data(peer_addr_index) {
+        Some(peer_addr) => {
+            let cookie = cookie(&secret, peer_addr);
+            received.len() == cookie.len() && openssl::memcmp::eq(received, &cookie)
+        }
+        None => false,
+    });
+    Ok(())
+}
+
+impl tls_api::DtlsAcceptorBuilder for DtlsAcceptorBuilder {
+    type Acceptor = DtlsAcceptor;
+
+    type Underlying = SslContextBuilder;
+
+    fn underlying_mut(&mut self) -> &mut SslContextBuilder {
+        &mut self.builder
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        let protocols = encode_alpn_protos(protocols)?;
+        self.builder
+            .set_alpn_select_callback(move |_ssl, client_protocols| {
+                select_alpn_proto(&protocols, client_protocols)
+            });
+        Ok(())
+    }
+
+    fn set_mtu(&mut self, mtu: u32) -> anyhow::Result<()> {
+        self.mtu = mtu;
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<DtlsAcceptor> {
+        // Use configured MTU instead of asking the BIO
+        self.builder.set_options(SslOptions::NO_QUERY_MTU);
+        Ok(DtlsAcceptor {
+            context: self.builder.build(),
+            mtu: self.mtu,
+            peer_addr_index: self.peer_addr_index,
+        })
+    }
+}
+
+impl DtlsAcceptor {
+    pub async fn accept_impl<S>(&self, socket: S) -> anyhow::Result<crate::DtlsStream<S>>
+    where
+        S: AsyncDatagramSocket,
+    {
+        let mut ssl = Ssl::new(&self.context)?;
+        ssl.set_ex_data(self.peer_addr_index, socket.peer_addr()?);
+        ssl.set_mtu(self.mtu)?;
+        ssl.set_accept_state();
+        crate::DtlsStream::handshake(socket, ssl).await
+    }
+}
+
+impl tls_api::DtlsAcceptor for DtlsAcceptor {
+    type Builder = DtlsAcceptorBuilder;
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        protocol_versions: crate::DTLS_PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::into()
+    }
+
+    fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<DtlsAcceptorBuilder> {
+        let cert = openssl::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+        let pkey = openssl::pkey::PKey::private_key_from_der(key).map_err(anyhow::Error::new)?;
+
+        let mut builder =
+            openssl::ssl::SslContext::builder(SslMethod::dtls()).map_err(anyhow::Error::new)?;
+        builder
+            .set_certificate(cert.as_ref())
+            .map_err(anyhow::Error::new)?;
+        builder
+            .set_private_key(pkey.as_ref())
+            .map_err(anyhow::Error::new)?;
+        builder.set_options(SslOptions::NO_COMPRESSION | SslOptions::CIPHER_SERVER_PREFERENCE);
+
+        let peer_addr_index = peer_addr_index()?;
+        configure_cookie_exchange(&mut builder, peer_addr_index)?;
+
+        Ok(DtlsAcceptorBuilder {
+            builder,
+            mtu: DEFAULT_MTU,
+            peer_addr_index,
+        })
+    }
+
+    fn accept<'a, S>(&'a self, socket: S) -> BoxFuture<'a, anyhow::Result<DtlsStream>>
+    where
+        S: AsyncDatagramSocket,
+    {
+        BoxFuture::new(async move { self.accept_impl(socket).await.map(DtlsStream::new) })
+    }
+}
@@ -0,0 +1,123 @@
+use openssl::ssl::SslMethod;
+use openssl::ssl::SslOptions;
+use tls_api::AsyncDatagramSocket;
+use tls_api::BoxFuture;
+use tls_api::Capabilities;
+use tls_api::DtlsStream;
+use tls_api::ImplInfo;
+
+use crate::dtls_stream::DEFAULT_MTU;
+use crate::encode_alpn_protos;
+
+pub struct DtlsConnectorBuilder {
+    pub builder: openssl::ssl::SslConnectorBuilder,
+    pub verify_hostname: bool,
+    pub mtu: u32,
+}
+
+pub struct DtlsConnector {
+    pub connector: openssl::ssl::SslConnector,
+    pub verify_hostname: bool,
+    pub mtu: u32,
+}
+
+impl tls_api::DtlsConnectorBuilder for DtlsConnectorBuilder {
+    type Connector = DtlsConnector;
+
+    type Underlying = openssl::ssl::SslConnectorBuilder;
+
+    fn underlying_mut(&mut self) -> &mut openssl::ssl::SslConnectorBuilder {
+        &mut self.builder
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.builder
+            .set_alpn_protos(&encode_alpn_protos(protocols)?)
+            .map_err(anyhow::Error::new)
+    }
+
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
+        self.verify_hostname = verify;
+        Ok(())
+    }
+
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let cert = openssl::x509::X509::from_der(cert).map_err(anyhow::Error::new)?;
+
+        self.builder
+            .cert_store_mut()
+            .add_cert(cert)
+            .map_err(anyhow::Error::new)?;
+
+        Ok(())
+    }
+
+    fn set_mtu(&mut self, mtu: u32) -> anyhow::Result<()> {
+        self.mtu = mtu;
+        Ok(())
+    }
+
+    fn build(mut self) -> anyhow::Result<DtlsConnector> {
+        // Use configured MTU instead of asking the BIO
+        self.builder.set_options(SslOptions::NO_QUERY_MTU);
+        Ok(DtlsConnector {
+            connector: self.builder.build(),
+            verify_hostname: self.verify_hostname,
+            mtu: self.mtu,
+        })
+    }
+}
+
+impl DtlsConnector {
+    pub async fn connect_impl<S>(
+        &self,
+        domain: &str,
+        socket: S,
+    ) -> anyhow::Result<crate::DtlsStream<S>>
+    where
+        S: AsyncDatagramSocket,
+    {
+        let client_configuration = self.connector.configure()?;
+        let client_configuration = client_configuration.verify_hostname(self.verify_hostname);
+        let mut ssl = client_configuration.into_ssl(domain)?;
+        ssl.set_mtu(self.mtu)?;
+        ssl.set_connect_state();
+        crate::DtlsStream::handshake(socket, ssl).await
+    }
+}
+
+impl tls_api::DtlsConnector for DtlsConnector {
+    type Builder = DtlsConnectorBuilder;
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        protocol_versions: crate::DTLS_PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::into()
+    }
+
+    fn builder() -> anyhow::Result<DtlsConnectorBuilder> {
+        let builder =
+            openssl::ssl::SslConnector::builder(SslMethod::dtls()).map_err(anyhow::Error::new)?;
+        Ok(DtlsConnectorBuilder {
+            builder,
+            verify_hostname: true,
+            mtu: DEFAULT_MTU,
+        })
+    }
+
+    fn connect<'a, S>(
+        &'a self,
+        domain: &'a str,
+        socket: S,
+    ) -> BoxFuture<'a, anyhow::Result<DtlsStream>>
+    where
+        S: AsyncDatagramSocket,
+    {
+        BoxFuture::new(async move { self.connect_impl(domain, socket).await.map(DtlsStream::new) })
+    }
+}
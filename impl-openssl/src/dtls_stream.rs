@@ -0,0 +1,289 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::os::raw::c_long;
+use std::os::raw::c_void;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use foreign_types::ForeignTypeRef;
+use openssl::ssl::ErrorCode;
+use openssl::ssl::Ssl;
+use openssl::ssl::SslRef;
+use openssl::ssl::SslStream;
+use tls_api::spi::Timer;
+use tls_api::AsyncDatagramSocket;
+use tls_api::DtlsStreamDyn;
+use tls_api::ImplInfo;
+
+use crate::connection::ssl_error_to_io;
+
+// `DTLSv1_get_timeout` and `DTLSv1_handle_timeout` are macros not exposed by `openssl-sys`
+const DTLS_CTRL_GET_TIMEOUT: i32 = 73;
+const DTLS_CTRL_HANDLE_TIMEOUT: i32 = 74;
+
+/// Default maximum size of datagrams sent.
+pub(crate) const DEFAULT_MTU: u32 = 1200;
+
+/// Largest possible UDP payload.
+const MAX_DATAGRAM: usize = 65535;
+
+/// Memory BIO preserving datagram boundaries: each read returns
+/// a single datagram, each write is a single datagram.
+#[derive(Debug, Default)]
+pub(crate) struct DatagramMemIo {
+    /// Datagrams received from the peer, not yet read by `openssl`.
+    incoming: VecDeque<Vec<u8>>,
+    /// Datagrams written by `openssl`, not yet sent.
+    outgoing: VecDeque<Vec<u8>>,
+}
+
+impl Read for DatagramMemIo {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.incoming.pop_front() {
+            Some(datagram) => {
+                let len = datagram.len().min(buf.len());
+                buf[..len].copy_from_slice(&datagram[..len]);
+                Ok(len)
+            }
+            None => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl Write for DatagramMemIo {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.outgoing.push_back(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Time left until the DTLS retransmission timer expires, `None` if timer is not running.
+fn dtls_timeout(ssl: &SslRef) -> Option<Duration> {
+    #[cfg(unix)]
+    type Timeval = libc::timeval;
+    #[cfg(windows)]
+    #[repr(C)]
+    struct Timeval {
+        tv_sec: c_long,
+        tv_usec: c_long,
+    }
+
+    let mut timeval: Timeval = unsafe { std::mem::zeroed() };
+    let r = unsafe {
+        openssl_sys::SSL_ctrl(
+            ssl.as_ptr(),
+            DTLS_CTRL_GET_TIMEOUT,
+            0,
+            &mut timeval as *mut Timeval as *mut c_void,
+        )
+    };
+    if r <= 0 {
+        return None;
+    }
+    Some(Duration::from_secs(timeval.tv_sec as u64) + Duration::from_micros(timeval.tv_usec as u64))
+}
+
+/// Retransmit the last flight of handshake messages if the timer expired.
+fn dtls_handle_timeout(ssl: &SslRef) -> io::Result<()> {
+    let r: c_long = unsafe {
+        openssl_sys::SSL_ctrl(
+            ssl.as_ptr(),
+            DTLS_CTRL_HANDLE_TIMEOUT,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if r < 0 {
+        return Err(io::Error::other(openssl::error::ErrorStack::get()));
+    }
+    Ok(())
+}
+
+/// DTLS stream: [`SslStream`] over datagram memory BIO, driven by the datagram socket.
+pub struct DtlsStream<S: AsyncDatagramSocket> {
+    stream: SslStream<DatagramMemIo>,
+    socket: S,
+    handshaking: bool,
+    recv_buf: Vec<u8>,
+    /// Wakes the task to retransmit handshake messages.
+    timer: Timer,
+}
+
+impl<S: AsyncDatagramSocket> fmt::Debug for DtlsStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DtlsStream")
+            .field("socket", &self.socket)
+            .field("handshaking", &self.handshaking)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: AsyncDatagramSocket> DtlsStream<S> {
+    /// Perform the handshake over the socket.
+    ///
+    /// `ssl` must have connect or accept state set.
+    pub(crate) async fn handshake(socket: S, ssl: Ssl) -> anyhow::Result<DtlsStream<S>> {
+        let mut stream = DtlsStream {
+            stream: SslStream::new(ssl, DatagramMemIo::default())?,
+            socket,
+            handshaking: true,
+            recv_buf: vec![0; MAX_DATAGRAM],
+            timer: Timer::new(),
+        };
+        std::future::poll_fn(|cx| stream.poll_handshake(cx)).await?;
+        Ok(stream)
+    }
+
+    /// Get the [`SslRef`] object for the stream.
+    pub fn get_ssl_ref(&self) -> &SslRef {
+        self.stream.ssl()
+    }
+
+    /// Get a reference to the underlying socket.
+    pub fn get_socket_ref(&self) -> &S {
+        &self.socket
+    }
+
+    /// Send all datagrams queued by `openssl`.
+    fn poll_send_datagrams(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while let Some(datagram) = self.stream.get_ref().outgoing.front() {
+            match Pin::new(&mut self.socket).poll_send(cx, datagram) {
+                Poll::Ready(Ok(_)) => {
+                    self.stream.get_mut().outgoing.pop_front();
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    /// Receive a datagram and queue it for `openssl`.
+    fn poll_recv_datagram(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match Pin::new(&mut self.socket).poll_recv(cx, &mut self.recv_buf) {
+            Poll::Ready(Ok(len)) => {
+                let datagram = self.recv_buf[..len].to_vec();
+                self.stream.get_mut().incoming.push_back(datagram);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Wait for a datagram from the peer, retransmitting lost handshake messages
+    /// when the DTLS timer expires.
+    fn poll_recv_or_timeout(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.poll_recv_datagram(cx) {
+            Poll::Ready(r) => Poll::Ready(r),
+            Poll::Pending => match dtls_timeout(self.stream.ssl()) {
+                Some(timeout) if timeout.is_zero() => {
+                    dtls_handle_timeout(self.stream.ssl())?;
+                    Poll::Ready(Ok(()))
+                }
+                Some(timeout) => {
+                    self.timer
+                        .wake_at(Instant::now() + timeout, cx.waker().clone());
+                    Poll::Pending
+                }
+                None => Poll::Pending,
+            },
+        }
+    }
+
+    fn poll_handshake(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            match self.poll_send_datagrams(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+            if !self.handshaking {
+                return Poll::Ready(Ok(()));
+            }
+            match self.stream.do_handshake() {
+                Ok(()) => {
+                    self.handshaking = false;
+                    continue;
+                }
+                Err(e) if e.code() == ErrorCode::WANT_READ => {}
+                Err(e) => {
+                    // Try to send the alert
+                    let _ = self.poll_send_datagrams(cx);
+                    return Poll::Ready(Err(ssl_error_to_io(e)));
+                }
+            }
+            if !self.stream.get_ref().outgoing.is_empty() {
+                continue;
+            }
+            match self.poll_recv_or_timeout(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S: AsyncDatagramSocket> DtlsStreamDyn for DtlsStream<S> {
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        loop {
+            // Datagrams could be queued by the previous send or by `openssl`
+            // answering retransmitted handshake messages of the peer
+            match self.poll_send_datagrams(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+            match self.stream.ssl_read(buf) {
+                Ok(read) => return Poll::Ready(Ok(read)),
+                Err(e) if e.code() == ErrorCode::ZERO_RETURN => return Poll::Ready(Ok(0)),
+                Err(e) if e.code() == ErrorCode::WANT_READ => {}
+                Err(e) => return Poll::Ready(Err(ssl_error_to_io(e))),
+            }
+            if !self.stream.get_ref().outgoing.is_empty() {
+                continue;
+            }
+            match self.poll_recv_datagram(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.poll_send_datagrams(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+        let written = match self.stream.ssl_write(buf) {
+            Ok(written) => written,
+            Err(e) => return Poll::Ready(Err(ssl_error_to_io(e))),
+        };
+        // If the socket is not ready, the datagram is sent by the next operation
+        match self.poll_send_datagrams(cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Ready(Ok(())) | Poll::Pending => Poll::Ready(Ok(written)),
+        }
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.get_ssl_ref().selected_alpn_protocol().map(Vec::from))
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::into()
+    }
+}
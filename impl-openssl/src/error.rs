@@ -1,3 +1,6 @@
+use openssl::error::ErrorStack;
+use tls_api::RetryableError;
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
     #[allow(dead_code)]
@@ -5,4 +8,102 @@ pub(crate) enum Error {
     CompiledWithoutAlpn,
     #[error("incorrect ALPN protocol name length")]
     AlpnProtocolLen,
+    #[error("CRLs can only be used with client authentication enabled")]
+    CrlWithoutClientAuth,
+}
+
+// Library and reason codes from `openssl/err.h` and `openssl/sslerr.h`,
+// not exported by `openssl-sys`.
+const ERR_LIB_SSL: libc::c_int = 20;
+
+const SSL_R_NO_SUITABLE_SIGNATURE_ALGORITHM: libc::c_int = 118;
+const SSL_R_VERSION_TOO_HIGH: libc::c_int = 166;
+const SSL_R_NO_CIPHERS_AVAILABLE: libc::c_int = 181;
+const SSL_R_NO_PROTOCOLS_AVAILABLE: libc::c_int = 191;
+const SSL_R_NO_SHARED_CIPHER: libc::c_int = 193;
+const SSL_R_UNSUPPORTED_PROTOCOL: libc::c_int = 258;
+const SSL_R_WRONG_VERSION_NUMBER: libc::c_int = 267;
+const SSL_R_NO_SHARED_SIGNATURE_ALGORITHMS: libc::c_int = 376;
+const SSL_R_VERSION_TOO_LOW: libc::c_int = 396;
+// Received alerts are reported as `SSL_AD_REASON_OFFSET` (1000) + alert code.
+const SSL_R_SSLV3_ALERT_HANDSHAKE_FAILURE: libc::c_int = 1040;
+const SSL_R_TLSV1_ALERT_PROTOCOL_VERSION: libc::c_int = 1070;
+const SSL_R_TLSV1_ALERT_INSUFFICIENT_SECURITY: libc::c_int = 1071;
+
+/// Classify handshake errors for [`tls_api::TlsConnector::classify_error`].
+pub(crate) fn classify(stack: &ErrorStack) -> Option<RetryableError> {
+    stack
+        .errors()
+        .iter()
+        .filter(|e| e.library_code() == ERR_LIB_SSL)
+        .find_map(|e| match e.reason_code() {
+            SSL_R_VERSION_TOO_HIGH
+            | SSL_R_NO_PROTOCOLS_AVAILABLE
+            | SSL_R_UNSUPPORTED_PROTOCOL
+            | SSL_R_WRONG_VERSION_NUMBER
+            | SSL_R_VERSION_TOO_LOW
+            | SSL_R_TLSV1_ALERT_PROTOCOL_VERSION => Some(RetryableError::ProtocolVersion),
+            SSL_R_NO_CIPHERS_AVAILABLE | SSL_R_NO_SHARED_CIPHER => {
+                Some(RetryableError::CipherSuite)
+            }
+            SSL_R_NO_SUITABLE_SIGNATURE_ALGORITHM | SSL_R_NO_SHARED_SIGNATURE_ALGORITHMS => {
+                Some(RetryableError::SignatureAlgorithm)
+            }
+            SSL_R_SSLV3_ALERT_HANDSHAKE_FAILURE | SSL_R_TLSV1_ALERT_INSUFFICIENT_SECURITY => {
+                Some(RetryableError::HandshakeFailure)
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use openssl::ssl::Ssl;
+    use openssl::ssl::SslContext;
+    use openssl::ssl::SslMethod;
+    use openssl::ssl::SslVersion;
+    use tls_api::TlsConnectionDyn;
+    use tls_api::TlsConnector as _;
+
+    use super::*;
+    use crate::TlsConnection;
+    use crate::TlsConnector;
+
+    fn ssl(min: Option<SslVersion>, max: Option<SslVersion>) -> Ssl {
+        let mut builder = SslContext::builder(SslMethod::tls()).unwrap();
+        builder.set_min_proto_version(min).unwrap();
+        builder.set_max_proto_version(max).unwrap();
+        Ssl::new(&builder.build()).unwrap()
+    }
+
+    #[test]
+    fn classify_protocol_version() {
+        let mut client = ssl(None, Some(SslVersion::TLS1_2));
+        client.set_connect_state();
+        let mut server = ssl(Some(SslVersion::TLS1_3), None);
+        server.set_accept_state();
+        let mut client = TlsConnection::new(client).unwrap();
+        let mut server = TlsConnection::new(server).unwrap();
+
+        let mut buf = [0; 16 * 1024];
+        let len = client.take_ciphertext(&mut buf).unwrap();
+        let server_error = anyhow::Error::new(server.feed_ciphertext(&buf[..len]).unwrap_err());
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            TlsConnector::classify_error(&server_error)
+        );
+
+        // `protocol_version` alert
+        let len = server.take_ciphertext(&mut buf).unwrap();
+        let client_error = anyhow::Error::new(client.feed_ciphertext(&buf[..len]).unwrap_err());
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            TlsConnector::classify_error(&client_error)
+        );
+
+        assert_eq!(
+            None,
+            TlsConnector::classify_error(&anyhow::anyhow!("tlsv1 alert protocol version"))
+        );
+    }
 }
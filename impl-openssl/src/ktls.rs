@@ -0,0 +1,239 @@
+//! Kernel TLS offload by `openssl`.
+//!
+//! `openssl` configures kTLS itself when it does I/O on the socket directly
+//! (rather than through [`SslStream`](openssl::ssl::SslStream) BIO),
+//! so the handshake and the fallback stream use socket BIO.
+//!
+//! kTLS support appeared in OpenSSL 3.0, with older versions the stream
+//! is always [`MaybeKtlsStream::Userspace`].
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpStream;
+use std::os::raw::c_int;
+use std::os::unix::io::AsRawFd;
+
+use foreign_types::ForeignType;
+use openssl::error::ErrorStack;
+use openssl::ssl::NameType;
+use openssl::ssl::Ssl;
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::KtlsStream;
+use tls_api::MaybeKtlsStream;
+use tls_api::TlsStreamBlockingDyn;
+
+extern "C" {
+    // Not exposed by `openssl-sys`
+    fn SSL_set_fd(ssl: *mut openssl_sys::SSL, fd: c_int) -> c_int;
+}
+
+/// Constants and functions of OpenSSL 3, not exposed by `openssl-sys`.
+#[cfg(ossl300)]
+mod ossl300 {
+    use std::os::raw::c_int;
+
+    extern "C" {
+        // A function since OpenSSL 3.0, a macro before
+        pub fn SSL_set_options(ssl: *mut openssl_sys::SSL, op: u64) -> u64;
+    }
+
+    // `SSL_OP_BIT(3)` in `ssl.h`
+    pub const SSL_OP_ENABLE_KTLS: u64 = 1 << 3;
+    // `BIO_get_ktls_send` and `BIO_get_ktls_recv` are macros
+    pub const BIO_CTRL_GET_KTLS_SEND: c_int = 73;
+    pub const BIO_CTRL_GET_KTLS_RECV: c_int = 76;
+}
+
+#[cfg(ossl300)]
+fn enable_ktls(ssl: &Ssl) {
+    unsafe {
+        ossl300::SSL_set_options(ssl.as_ptr(), ossl300::SSL_OP_ENABLE_KTLS);
+    }
+}
+
+#[cfg(not(ossl300))]
+fn enable_ktls(_ssl: &Ssl) {}
+
+/// kTLS is configured in both directions.
+#[cfg(ossl300)]
+fn ktls_enabled(ssl: &Ssl) -> bool {
+    let (send, recv) = unsafe {
+        (
+            openssl_sys::BIO_ctrl(
+                openssl_sys::SSL_get_wbio(ssl.as_ptr()),
+                ossl300::BIO_CTRL_GET_KTLS_SEND,
+                0,
+                std::ptr::null_mut(),
+            ),
+            openssl_sys::BIO_ctrl(
+                openssl_sys::SSL_get_rbio(ssl.as_ptr()),
+                ossl300::BIO_CTRL_GET_KTLS_RECV,
+                0,
+                std::ptr::null_mut(),
+            ),
+        )
+    };
+    send > 0 && recv > 0
+}
+
+#[cfg(not(ossl300))]
+fn ktls_enabled(_ssl: &Ssl) -> bool {
+    false
+}
+
+/// Convert the result of `SSL_do_handshake`, `SSL_read` or `SSL_write` to an error.
+fn ssl_error(ssl: &Ssl, r: c_int) -> io::Error {
+    let code = unsafe { openssl_sys::SSL_get_error(ssl.as_ptr(), r) };
+    let stack = ErrorStack::get();
+    match code {
+        openssl_sys::SSL_ERROR_SYSCALL if stack.errors().is_empty() => {
+            match io::Error::last_os_error() {
+                // `errno` is not set on unexpected EOF
+                e if e.raw_os_error() == Some(0) => io::ErrorKind::UnexpectedEof.into(),
+                e => e,
+            }
+        }
+        _ => io::Error::other(stack),
+    }
+}
+
+/// Blocking TLS stream where `openssl` reads and writes the socket
+/// (with kTLS possibly enabled in one direction).
+#[derive(Debug)]
+pub struct TlsStreamBlockingKtlsFallback {
+    ssl: Ssl,
+    socket: TcpStream,
+}
+
+impl Read for TlsStreamBlockingKtlsFallback {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(c_int::MAX as usize) as c_int;
+        let r =
+            unsafe { openssl_sys::SSL_read(self.ssl.as_ptr(), buf.as_mut_ptr() as *mut _, len) };
+        if r > 0 {
+            return Ok(r as usize);
+        }
+        let code = unsafe { openssl_sys::SSL_get_error(self.ssl.as_ptr(), r) };
+        match code {
+            openssl_sys::SSL_ERROR_ZERO_RETURN => Ok(0),
+            // Missing `close_notify` is reported as `UnexpectedEof`
+            _ => Err(ssl_error(&self.ssl, r)),
+        }
+    }
+}
+
+impl Write for TlsStreamBlockingKtlsFallback {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let len = buf.len().min(c_int::MAX as usize) as c_int;
+        let r = unsafe { openssl_sys::SSL_write(self.ssl.as_ptr(), buf.as_ptr() as *const _, len) };
+        if r > 0 {
+            return Ok(r as usize);
+        }
+        Err(ssl_error(&self.ssl, r))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlockingKtlsFallback {
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.ssl.selected_alpn_protocol().map(Vec::from))
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.ssl.servername(NameType::HOST_NAME).map(str::to_owned))
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.ssl.ocsp_status().map(Vec::from))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut out = vec![0; len];
+        self.ssl.export_keying_material(&mut out, label, context)?;
+        Ok(out)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => {
+                crate::channel_binding::tls_server_end_point(&self.ssl)
+            }
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::into()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        let r = unsafe { openssl_sys::SSL_shutdown(self.ssl.as_ptr()) };
+        if r < 0 {
+            return Err(ssl_error(&self.ssl, r));
+        }
+        self.socket.shutdown(Shutdown::Write)
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        &mut self.socket
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        &self.socket
+    }
+}
+
+/// Perform the handshake on the socket with kTLS enabled.
+///
+/// `ssl` must have connect or accept state set.
+pub(crate) fn handshake(ssl: Ssl, socket: TcpStream) -> anyhow::Result<MaybeKtlsStream> {
+    enable_ktls(&ssl);
+    unsafe {
+        // Socket BIO does not close the socket
+        if SSL_set_fd(ssl.as_ptr(), socket.as_raw_fd()) != 1 {
+            return Err(anyhow::Error::new(ErrorStack::get()));
+        }
+    }
+    loop {
+        let r = unsafe { openssl_sys::SSL_do_handshake(ssl.as_ptr()) };
+        if r == 1 {
+            break;
+        }
+        match ssl_error(&ssl, r) {
+            e if e.kind() == io::ErrorKind::Interrupted => {}
+            e => return Err(anyhow::Error::new(e)),
+        }
+    }
+
+    if ktls_enabled(&ssl) {
+        let alpn_protocol = ssl.selected_alpn_protocol().map(Vec::from);
+        return Ok(MaybeKtlsStream::Ktls(KtlsStream::new(
+            socket,
+            alpn_protocol,
+            crate::into(),
+        )));
+    }
+    // `openssl` is older than 3.0 or compiled without kTLS, kernel module is not available,
+    // or kTLS is enabled only in one direction
+    Ok(MaybeKtlsStream::Userspace(tls_api::TlsStreamBlocking::new(
+        TlsStreamBlockingKtlsFallback { ssl, socket },
+    )))
+}
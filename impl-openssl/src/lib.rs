@@ -3,20 +3,38 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod acceptor;
+mod blocking;
+mod channel_binding;
+mod connection;
 mod connector;
+mod crl;
+mod dtls_acceptor;
+mod dtls_connector;
+mod dtls_stream;
 mod error;
-mod handshake;
+mod key_log;
+#[cfg(target_os = "linux")]
+mod ktls;
+mod ocsp;
 mod stream;
 
 pub use acceptor::TlsAcceptor;
 pub use acceptor::TlsAcceptorBuilder;
 pub use connector::TlsConnector;
 pub use connector::TlsConnectorBuilder;
+pub use dtls_acceptor::DtlsAcceptor;
+pub use dtls_acceptor::DtlsAcceptorBuilder;
+pub use dtls_connector::DtlsConnector;
+pub use dtls_connector::DtlsConnectorBuilder;
+pub use dtls_stream::DtlsStream;
 
+pub use blocking::TlsStreamBlocking;
+pub use connection::TlsConnection;
 pub(crate) use error::Error;
 pub(crate) use stream::TlsStream;
 
 use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
 
 fn encode_alpn_protos(protos: &[&[u8]]) -> anyhow::Result<Vec<u8>> {
     let mut r = Vec::new();
@@ -39,9 +57,54 @@ fn test_encode_alpn_protos() {
     );
 }
 
+/// ALPN select callback: pick the first of `server` protocols offered by the client.
+///
+/// Selected protocol is returned as a slice of `client`:
+/// `select_next_proto` returns a slice of `server`, which does not outlive the callback.
+fn select_alpn_proto<'a>(
+    server: &[u8],
+    client: &'a [u8],
+) -> Result<&'a [u8], openssl::ssl::AlpnError> {
+    let selected =
+        openssl::ssl::select_next_proto(server, client).ok_or(openssl::ssl::AlpnError::NOACK)?;
+    let mut rest = client;
+    while let Some((&len, tail)) = rest.split_first() {
+        if tail.len() < len as usize {
+            break;
+        }
+        let (proto, tail) = tail.split_at(len as usize);
+        if proto == selected {
+            return Ok(proto);
+        }
+        rest = tail;
+    }
+    Err(openssl::ssl::AlpnError::NOACK)
+}
+
+#[cfg(test)]
+#[test]
+fn test_select_alpn_proto() {
+    let server = encode_alpn_protos(&[b"h2", b"http/1.1"]).unwrap();
+    let client = encode_alpn_protos(&[b"http/1.1", b"h2"]).unwrap();
+    let selected = select_alpn_proto(&server, &client).unwrap();
+    assert_eq!(b"h2", selected);
+    assert!(client.as_ptr_range().contains(&selected.as_ptr()));
+
+    let client = encode_alpn_protos(&[b"spdy/1"]).unwrap();
+    assert!(select_alpn_proto(&server, &client).is_err());
+}
+
 pub(crate) fn into() -> ImplInfo {
     ImplInfo {
         name: "openssl",
         version: openssl::version::version(),
+        crypto_provider: None,
+        fips: false,
     }
 }
+
+/// OpenSSL 3 allows TLS 1.0 and 1.1 only at security level 0.
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] =
+    &[ProtocolVersion::Tls12, ProtocolVersion::Tls13];
+
+pub(crate) const DTLS_PROTOCOL_VERSIONS: &[ProtocolVersion] = &[ProtocolVersion::Dtls12];
@@ -5,3 +5,38 @@ fn connect_bad_hostname() {
         assert!(debug.contains("certificate verify failed"), "{}", debug);
     });
 }
+
+#[test]
+fn truncation() {
+    tls_api_test::test_truncation::<tls_api_openssl::TlsConnector, tls_api_openssl::TlsAcceptor>();
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[test]
+fn dtls_client_server() {
+    tls_api_test::test_dtls_client_server::<
+        tls_api_openssl::DtlsConnector,
+        tls_api_openssl::DtlsAcceptor,
+    >();
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[test]
+fn dtls_packet_loss() {
+    tls_api_test::test_dtls_packet_loss::<
+        tls_api_openssl::DtlsConnector,
+        tls_api_openssl::DtlsAcceptor,
+    >();
+}
+
+#[cfg(feature = "runtime-tokio")]
+#[test]
+fn dtls_alpn() {
+    tls_api_test::test_dtls_alpn::<tls_api_openssl::DtlsConnector, tls_api_openssl::DtlsAcceptor>();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn ktls() {
+    tls_api_test::test_ktls::<tls_api_openssl::TlsConnector, tls_api_openssl::TlsAcceptor>();
+}
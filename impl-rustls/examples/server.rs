@@ -3,13 +3,19 @@ use tls_api::TlsAcceptor;
 use tls_api::TlsAcceptorBuilder;
 use tls_api_test::block_on;
 
-#[cfg(feature = "runtime-async-std")]
+#[cfg(all(feature = "runtime-async-std", not(feature = "runtime-tokio")))]
 use async_std::net::TcpListener;
 #[cfg(feature = "runtime-tokio")]
 use tokio::net::TcpListener;
+// smol
+#[cfg(all(
+    feature = "runtime-futures-io",
+    not(any(feature = "runtime-tokio", feature = "runtime-async-std"))
+))]
+use tls_api_test::TcpListener;
 
 use test_cert_gen::pem_to_cert_key_pair;
-use tls_api::runtime::AsyncWriteExt;
+use tls_api_test::runtime::AsyncWriteExt;
 
 async fn run() {
     let (cert, key) = pem_to_cert_key_pair(fs::read_to_string("server.pem").unwrap().as_bytes());
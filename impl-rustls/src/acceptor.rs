@@ -1,20 +1,45 @@
+use std::convert::TryFrom;
+use std::net::TcpStream;
 use std::sync::Arc;
 
-use rustls::StreamOwned;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::CertificateRevocationListDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::server::WebPkiClientVerifier;
+use rustls::sign::CertifiedKey;
 
-use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
-use tls_api::BoxFuture;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::QuicConnection;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
-use crate::handshake::HandshakeFuture;
-use crate::RustlsStream;
-use std::future::Future;
+use crate::client_auth::LateClientCertVerifier;
+use crate::conn::Conn;
+use crate::key_log::RustlsKeyLog;
+use crate::ocsp::StaplingCertResolver;
+use crate::quic::SecretsKeyLog;
+use crate::CryptoProviderKind;
 
-pub struct TlsAcceptorBuilder(pub rustls::ServerConfig);
-pub struct TlsAcceptor(pub Arc<rustls::ServerConfig>);
+pub struct TlsAcceptorBuilder {
+    pub config: rustls::ServerConfig,
+    pub client_auth_root_store: rustls::RootCertStore,
+    pub crls: Vec<CertificateRevocationListDer<'static>>,
+    pub revocation_policy: RevocationPolicy,
+    cert_resolver: Arc<StaplingCertResolver>,
+    client_auth: Arc<LateClientCertVerifier>,
+    provider: CryptoProviderKind,
+}
+pub struct TlsAcceptor {
+    pub config: Arc<rustls::ServerConfig>,
+    cert_resolver: Arc<StaplingCertResolver>,
+    provider: CryptoProviderKind,
+}
 
 impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
     type Acceptor = TlsAcceptor;
@@ -22,39 +47,135 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
     type Underlying = rustls::ServerConfig;
 
     fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
-        self.0.alpn_protocols = protocols.into_iter().map(|p| p.to_vec()).collect();
+        self.config.alpn_protocols = protocols.into_iter().map(|p| p.to_vec()).collect();
+        Ok(())
+    }
+
+    fn add_client_auth_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        let cert = CertificateDer::from(cert.to_vec());
+        self.client_auth_root_store
+            .add(cert)
+            .map_err(anyhow::Error::new)?;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.crls
+            .push(CertificateRevocationListDer::from(crl.to_vec()));
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.revocation_policy = policy;
+        Ok(())
+    }
+
+    fn set_ocsp_response(&mut self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.cert_resolver.set_ocsp_response(ocsp_response);
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.config.key_log = Arc::new(RustlsKeyLog(key_log));
         Ok(())
     }
 
     fn underlying_mut(&mut self) -> &mut rustls::ServerConfig {
-        &mut self.0
+        &mut self.config
     }
 
     fn build(self) -> anyhow::Result<TlsAcceptor> {
-        Ok(TlsAcceptor(Arc::new(self.0)))
+        if self.client_auth_root_store.is_empty() {
+            if !self.crls.is_empty() {
+                return Err(crate::Error::CrlWithoutClientAuth.into());
+            }
+        } else {
+            let verifier = WebPkiClientVerifier::builder_with_provider(
+                self.client_auth_root_store.into(),
+                self.config.crypto_provider().clone(),
+            )
+            .with_crls(self.crls);
+            let verifier = match self.revocation_policy {
+                RevocationPolicy::HardFail => verifier,
+                RevocationPolicy::SoftFail => verifier.allow_unknown_revocation_status(),
+                RevocationPolicy::LeafOnly => verifier.only_check_end_entity_revocation(),
+            };
+            self.client_auth
+                .set(verifier.build().map_err(anyhow::Error::new)?);
+        }
+
+        Ok(TlsAcceptor {
+            config: Arc::new(self.config),
+            cert_resolver: self.cert_resolver,
+            provider: self.provider,
+        })
     }
 }
 
 impl TlsAcceptor {
-    pub fn accept_impl<'a, S>(
-        &'a self,
-        stream: S,
-    ) -> impl Future<Output = anyhow::Result<crate::TlsStream<S>>> + 'a
+    /// Create a builder from DER certificate and key using the given crypto provider
+    /// instead of the [default](CryptoProviderKind::default) one.
+    pub fn builder_from_der_key_with_crypto_provider(
+        cert: &[u8],
+        key: &[u8],
+        provider: CryptoProviderKind,
+    ) -> anyhow::Result<TlsAcceptorBuilder> {
+        let crypto_provider = provider.provider()?;
+        let cert = CertificateDer::from(cert.to_vec());
+        let key = PrivateKeyDer::try_from(key.to_vec()).map_err(crate::Error::PrivateKey)?;
+        let certified_key = CertifiedKey::from_der(vec![cert], key, &crypto_provider)
+            .map_err(anyhow::Error::new)?;
+        let cert_resolver = Arc::new(StaplingCertResolver::new(certified_key));
+        let client_auth = Arc::new(LateClientCertVerifier::default());
+        let config = rustls::ServerConfig::builder_with_provider(crypto_provider)
+            .with_safe_default_protocol_versions()
+            .map_err(anyhow::Error::new)?
+            .with_client_cert_verifier(client_auth.clone())
+            .with_cert_resolver(cert_resolver.clone());
+        Ok(TlsAcceptorBuilder {
+            config,
+            client_auth_root_store: rustls::RootCertStore::empty(),
+            crls: Vec::new(),
+            revocation_policy: RevocationPolicy::default(),
+            cert_resolver,
+            client_auth,
+            provider,
+        })
+    }
+
+    /// Crypto provider used by this acceptor.
+    pub fn crypto_provider(&self) -> CryptoProviderKind {
+        self.provider
+    }
+
+    fn new_conn(&self) -> anyhow::Result<Conn> {
+        self.new_conn_with_config(self.config.clone())
+    }
+
+    fn new_conn_with_config(&self, config: Arc<rustls::ServerConfig>) -> anyhow::Result<Conn> {
+        let conn = rustls::ServerConnection::new(config).map_err(anyhow::Error::new)?;
+        Ok(Conn::new_server(
+            conn,
+            self.cert_resolver.end_entity_cert(),
+            self.provider,
+        ))
+    }
+
+    pub async fn accept_impl<S>(&self, stream: S) -> anyhow::Result<crate::TlsStream<S>>
     where
         S: AsyncSocket,
     {
-        let conn = rustls::ServerConnection::new(self.0.clone());
-        let conn = match conn.map_err(|e| anyhow::Error::new(e)) {
-            Ok(conn) => conn,
-            Err(e) => return BoxFuture::new(async { Err(e) }),
-        };
-        let tls_stream: crate::TlsStream<S> =
-            crate::TlsStream::new(RustlsStream::Server(StreamOwned {
-                sock: AsyncIoAsSyncIo::new(stream),
-                conn,
-            }));
-
-        BoxFuture::new(HandshakeFuture::MidHandshake(tls_stream))
+        let conn = self.new_conn()?;
+        crate::TlsStream::handshake(stream, crate::TlsConnection(conn)).await
+    }
+
+    /// Blocking version of [`accept_impl`](Self::accept_impl).
+    pub fn accept_blocking_impl(
+        &self,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        let conn = self.new_conn()?;
+        crate::TlsStreamBlocking::handshake(stream, conn)
     }
 }
 
@@ -65,28 +186,105 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
 
     fn underlying_mut(&mut self) -> &mut Self::Underlying {
-        &mut self.0
+        &mut self.config
     }
 
-    const IMPLEMENTED: bool = true;
     // TODO: https://github.com/sfackler/rust-openssl/pull/646
-    const SUPPORTS_ALPN: bool = true;
-    const SUPPORTS_DER_KEYS: bool = true;
-    const SUPPORTS_PKCS12_KEYS: bool = false;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        der_keys: true,
+        client_auth: true,
+        crl: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
     }
 
+    /// Note response is only replaced if server certificate resolver
+    /// was not replaced with [`underlying_mut`](tls_api::TlsAcceptorBuilder::underlying_mut).
+    fn set_ocsp_response(&self, ocsp_response: &[u8]) -> anyhow::Result<()> {
+        self.cert_resolver.set_ocsp_response(ocsp_response);
+        Ok(())
+    }
+
     fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<TlsAcceptorBuilder> {
-        let cert = rustls::Certificate(cert.to_vec());
-        let config = rustls::ServerConfig::builder()
-            .with_safe_defaults()
-            .with_no_client_auth()
-            .with_single_cert(vec![cert], rustls::PrivateKey(key.to_vec()))
-            .map_err(anyhow::Error::new)?;
-        Ok(TlsAcceptorBuilder(config))
+        TlsAcceptor::builder_from_der_key_with_crypto_provider(
+            cert,
+            key,
+            CryptoProviderKind::default(),
+        )
+    }
+
+    fn accept_blocking(&self, stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        self.accept_blocking_impl(stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        Ok(TlsConnection::new(crate::TlsConnection(self.new_conn()?)))
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
+}
+
+impl tls_api::QuicAcceptor for TlsAcceptor {
+    fn new_quic_connection(&self, transport_parameters: &[u8]) -> anyhow::Result<QuicConnection> {
+        // Shallow copy to collect secrets of this connection, see `SecretsKeyLog`
+        let mut config = (*self.config).clone();
+        let secrets = SecretsKeyLog::new(config.key_log.clone());
+        config.key_log = secrets.clone();
+        let conn = rustls::quic::ServerConnection::new(
+            Arc::new(config),
+            crate::quic::QUIC_VERSION,
+            transport_parameters.to_vec(),
+        )
+        .map_err(anyhow::Error::new)?;
+        let conn = crate::QuicConnection::new(conn.into(), secrets, self.provider)?;
+        Ok(QuicConnection::new(conn))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl tls_api::KtlsAcceptor for TlsAcceptor {
+    fn accept_ktls(&self, stream: TcpStream) -> anyhow::Result<tls_api::MaybeKtlsStream> {
+        let mut config = (*self.config).clone();
+        config.enable_secret_extraction = true;
+        let conn = self.new_conn_with_config(Arc::new(config))?;
+        crate::ktls::handshake(stream, conn)
     }
+}
 
-    spi_acceptor_common!();
+#[cfg(test)]
+mod test {
+    use tls_api::TlsAcceptor as _;
+    use tls_api::TlsAcceptorBuilder as _;
+
+    use super::*;
+
+    #[test]
+    fn client_auth_keeps_config() {
+        let keys = test_cert_gen::keys();
+        let mut builder = TlsAcceptor::builder_from_der_key(
+            keys.server.cert_and_key.cert.get_der(),
+            keys.server.cert_and_key.key.get_der(),
+        )
+        .unwrap();
+        builder
+            .add_client_auth_root_certificate(keys.client.ca.get_der())
+            .unwrap();
+        builder.underlying_mut().max_early_data_size = 1024;
+        builder.underlying_mut().send_tls13_tickets = 0;
+        let acceptor = builder.build().unwrap();
+        assert_eq!(1024, acceptor.config.max_early_data_size);
+        assert_eq!(0, acceptor.config.send_tls13_tickets);
+    }
 }
@@ -0,0 +1,126 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::TcpStream;
+
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsStreamBlockingDyn;
+
+use crate::conn::Conn;
+use crate::RustlsSessionRef;
+
+/// Blocking TLS stream: `rustls` connection over [`TcpStream`].
+#[derive(Debug)]
+pub struct TlsStreamBlocking {
+    socket: TcpStream,
+    conn: Conn,
+}
+
+impl TlsStreamBlocking {
+    pub(crate) fn handshake(
+        mut socket: TcpStream,
+        mut conn: Conn,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        while conn.is_handshaking() {
+            conn.complete_io(&mut socket)?;
+        }
+        let mut stream = TlsStreamBlocking { socket, conn };
+        stream.write_all_tls()?;
+        Ok(stream)
+    }
+
+    /// Wrap a connection which completed the handshake.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn from_parts(socket: TcpStream, conn: Conn) -> TlsStreamBlocking {
+        TlsStreamBlocking { socket, conn }
+    }
+
+    /// Get the `rustls` session.
+    pub fn session(&self) -> RustlsSessionRef<'_> {
+        self.conn.session()
+    }
+
+    /// Write all buffered TLS records to the socket.
+    fn write_all_tls(&mut self) -> io::Result<()> {
+        while self.conn.wants_write() {
+            if self.conn.write_tls(&mut self.socket)? == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Read for TlsStreamBlocking {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.write_all_tls()?;
+        while self.conn.wants_read() {
+            if self.conn.complete_io(&mut self.socket)?.0 == 0 {
+                break;
+            }
+        }
+        // Missing `close_notify` is reported as `UnexpectedEof`
+        self.conn.reader().read(buf)
+    }
+}
+
+impl Write for TlsStreamBlocking {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.conn.writer().write(buf)?;
+        self.write_all_tls()?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.conn.writer().flush()?;
+        self.write_all_tls()?;
+        self.socket.flush()
+    }
+}
+
+impl TlsStreamBlockingDyn for TlsStreamBlocking {
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.conn.get_alpn_protocol()
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.conn.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.conn.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.conn.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.conn.channel_binding(kind)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.conn.impl_info()
+    }
+
+    fn shutdown(&mut self) -> io::Result<()> {
+        self.conn.send_close_notify();
+        self.flush()?;
+        self.socket.shutdown(Shutdown::Write)
+    }
+
+    fn get_socket_mut(&mut self) -> &mut TcpStream {
+        &mut self.socket
+    }
+
+    fn get_socket_ref(&self) -> &TcpStream {
+        &self.socket
+    }
+}
@@ -0,0 +1,252 @@
+//! `tls-server-end-point` channel binding.
+//!
+//! `rustls` does not parse certificates, so the signature algorithm
+//! is extracted with a minimal DER reader.
+
+use rustls::pki_types::CertificateDer;
+use tls_api::ChannelBindingError;
+
+use crate::CryptoProviderKind;
+
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_OID: u8 = 0x06;
+/// `[0] EXPLICIT`
+const TAG_CONTEXT_0: u8 = 0xa0;
+
+/// Hash function for `tls-server-end-point`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DigestAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+/// Encoded OID of `id-RSASSA-PSS`, its hash is in the algorithm parameters.
+const RSASSA_PSS: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0a";
+
+/// Encoded OIDs of signature algorithms and hashes to use for them.
+///
+/// MD5 and SHA-1 are replaced with SHA-256 (RFC 5929 section 4.1).
+const SIGNATURE_ALGORITHMS: &[(&[u8], DigestAlgorithm)] = &[
+    // md5WithRSAEncryption
+    (
+        b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x04",
+        DigestAlgorithm::Sha256,
+    ),
+    // sha1WithRSAEncryption
+    (
+        b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x05",
+        DigestAlgorithm::Sha256,
+    ),
+    // sha256WithRSAEncryption
+    (
+        b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b",
+        DigestAlgorithm::Sha256,
+    ),
+    // sha384WithRSAEncryption
+    (
+        b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0c",
+        DigestAlgorithm::Sha384,
+    ),
+    // sha512WithRSAEncryption
+    (
+        b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0d",
+        DigestAlgorithm::Sha512,
+    ),
+    // ecdsa-with-SHA1
+    (b"\x2a\x86\x48\xce\x3d\x04\x01", DigestAlgorithm::Sha256),
+    // ecdsa-with-SHA256
+    (b"\x2a\x86\x48\xce\x3d\x04\x03\x02", DigestAlgorithm::Sha256),
+    // ecdsa-with-SHA384
+    (b"\x2a\x86\x48\xce\x3d\x04\x03\x03", DigestAlgorithm::Sha384),
+    // ecdsa-with-SHA512
+    (b"\x2a\x86\x48\xce\x3d\x04\x03\x04", DigestAlgorithm::Sha512),
+];
+
+/// Encoded OIDs of hash algorithms in RSASSA-PSS parameters.
+const HASH_ALGORITHMS: &[(&[u8], DigestAlgorithm)] = &[
+    // id-sha1
+    (b"\x2b\x0e\x03\x02\x1a", DigestAlgorithm::Sha256),
+    // id-sha256
+    (
+        b"\x60\x86\x48\x01\x65\x03\x04\x02\x01",
+        DigestAlgorithm::Sha256,
+    ),
+    // id-sha384
+    (
+        b"\x60\x86\x48\x01\x65\x03\x04\x02\x02",
+        DigestAlgorithm::Sha384,
+    ),
+    // id-sha512
+    (
+        b"\x60\x86\x48\x01\x65\x03\x04\x02\x03",
+        DigestAlgorithm::Sha512,
+    ),
+];
+
+/// Split DER TLV with given tag into content and the rest of input.
+fn der_read(input: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual_tag, input) = input.split_first()?;
+    if actual_tag != tag {
+        return None;
+    }
+    let (&len, mut input) = input.split_first()?;
+    let len = if len < 0x80 {
+        len as usize
+    } else {
+        let len_len = (len & 0x7f) as usize;
+        if len_len == 0 || len_len > 4 || input.len() < len_len {
+            return None;
+        }
+        let (len_bytes, rem) = input.split_at(len_len);
+        input = rem;
+        len_bytes
+            .iter()
+            .fold(0usize, |len, &b| (len << 8) | b as usize)
+    };
+    if input.len() < len {
+        return None;
+    }
+    Some(input.split_at(len))
+}
+
+/// Certificate `signatureAlgorithm`: encoded OID and the rest (parameters).
+fn signature_algorithm(cert: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (cert, _) = der_read(cert, TAG_SEQUENCE)?;
+    let (_tbs_certificate, cert) = der_read(cert, TAG_SEQUENCE)?;
+    let (signature_algorithm, _) = der_read(cert, TAG_SEQUENCE)?;
+    der_read(signature_algorithm, TAG_OID)
+}
+
+/// Hash of RSASSA-PSS signature: `hashAlgorithm` of `RSASSA-PSS-params` (RFC 4055).
+fn rsassa_pss_hash(params: &[u8]) -> Option<DigestAlgorithm> {
+    let (params, _) = der_read(params, TAG_SEQUENCE)?;
+    let oid = match der_read(params, TAG_CONTEXT_0) {
+        Some((hash_algorithm, _)) => {
+            let (hash_algorithm, _) = der_read(hash_algorithm, TAG_SEQUENCE)?;
+            der_read(hash_algorithm, TAG_OID)?.0
+        }
+        // Default is SHA-1
+        None => HASH_ALGORITHMS[0].0,
+    };
+    find(HASH_ALGORITHMS, oid)
+}
+
+fn find(table: &[(&[u8], DigestAlgorithm)], oid: &[u8]) -> Option<DigestAlgorithm> {
+    table
+        .iter()
+        .find(|(known, _)| *known == oid)
+        .map(|(_, algorithm)| *algorithm)
+}
+
+/// Hash to use for certificate, `Err` with description if unsupported.
+fn digest_algorithm(cert: &[u8]) -> Result<DigestAlgorithm, ChannelBindingError> {
+    let (oid, params) = signature_algorithm(cert).ok_or_else(|| {
+        ChannelBindingError::UnsupportedSignatureAlgorithm("malformed certificate".to_owned())
+    })?;
+    let algorithm = if oid == RSASSA_PSS {
+        rsassa_pss_hash(params)
+    } else {
+        find(SIGNATURE_ALGORITHMS, oid)
+    };
+    algorithm
+        .ok_or_else(|| ChannelBindingError::UnsupportedSignatureAlgorithm(format!("{:02x?}", oid)))
+}
+
+/// Hash `data` with the crypto library of the `provider`.
+fn digest(
+    provider: CryptoProviderKind,
+    algorithm: DigestAlgorithm,
+    data: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    match provider {
+        #[cfg(all(feature = "ring", not(feature = "fips")))]
+        CryptoProviderKind::Ring => {
+            let algorithm = match algorithm {
+                DigestAlgorithm::Sha256 => &ring::digest::SHA256,
+                DigestAlgorithm::Sha384 => &ring::digest::SHA384,
+                DigestAlgorithm::Sha512 => &ring::digest::SHA512,
+            };
+            Ok(ring::digest::digest(algorithm, data).as_ref().to_vec())
+        }
+        #[cfg(feature = "aws-lc-rs")]
+        CryptoProviderKind::AwsLcRs => {
+            let algorithm = match algorithm {
+                DigestAlgorithm::Sha256 => &aws_lc_rs::digest::SHA256,
+                DigestAlgorithm::Sha384 => &aws_lc_rs::digest::SHA384,
+                DigestAlgorithm::Sha512 => &aws_lc_rs::digest::SHA512,
+            };
+            Ok(aws_lc_rs::digest::digest(algorithm, data).as_ref().to_vec())
+        }
+        #[cfg(not(feature = "aws-lc-rs"))]
+        CryptoProviderKind::AwsLcRs => {
+            Err(crate::Error::CryptoProviderNotEnabled("aws-lc-rs").into())
+        }
+    }
+}
+
+/// `tls-server-end-point` channel binding (RFC 5929 section 4.1).
+pub(crate) fn tls_server_end_point(
+    cert: &CertificateDer,
+    provider: CryptoProviderKind,
+) -> anyhow::Result<Vec<u8>> {
+    digest(provider, digest_algorithm(cert)?, cert)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn signature_algorithm_of_test_cert() {
+        let keys = test_cert_gen::keys();
+        let (oid, _) = signature_algorithm(keys.server.cert_and_key.cert.get_der()).unwrap();
+        // sha256WithRSAEncryption
+        assert_eq!(b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b", oid);
+    }
+
+    /// Algorithm chosen for a self-signed certificate and the channel binding.
+    fn binding_of_cert(req_args: &[&str]) -> (DigestAlgorithm, Vec<u8>, Vec<u8>) {
+        let cert = test_cert_gen::gen_self_signed("channel-binding.test", req_args).cert;
+        let cert = CertificateDer::from(cert.get_der());
+        let algorithm = digest_algorithm(&cert).unwrap();
+        let binding = tls_server_end_point(&cert, CryptoProviderKind::default()).unwrap();
+        (algorithm, binding, cert.to_vec())
+    }
+
+    #[test]
+    fn sha512_signed_cert() {
+        let (algorithm, binding, cert) = binding_of_cert(&["-sha512"]);
+        assert_eq!(DigestAlgorithm::Sha512, algorithm);
+        assert_eq!(
+            digest(
+                CryptoProviderKind::default(),
+                DigestAlgorithm::Sha512,
+                &cert
+            )
+            .unwrap(),
+            binding
+        );
+    }
+
+    #[test]
+    fn rsa_pss_signed_cert() {
+        let (algorithm, binding, cert) = binding_of_cert(&[
+            "-sha384",
+            "-sigopt",
+            "rsa_padding_mode:pss",
+            "-sigopt",
+            "rsa_pss_saltlen:-1",
+        ]);
+        assert_eq!(DigestAlgorithm::Sha384, algorithm);
+        assert_eq!(
+            digest(
+                CryptoProviderKind::default(),
+                DigestAlgorithm::Sha384,
+                &cert
+            )
+            .unwrap(),
+            binding
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Client authentication.
+
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::UnixTime;
+use rustls::server::danger::ClientCertVerified;
+use rustls::server::danger::ClientCertVerifier;
+use rustls::DigitallySignedStruct;
+use rustls::DistinguishedName;
+use rustls::SignatureScheme;
+
+/// Client certificate verifier which is installed when the acceptor builder is created,
+/// and configured when the acceptor is built.
+///
+/// Verifier cannot be replaced in existing `ServerConfig`,
+/// and a new config would lose settings made with `underlying_mut`.
+/// Client authentication is not requested until the verifier is set.
+#[derive(Debug, Default)]
+pub(crate) struct LateClientCertVerifier(OnceLock<Arc<dyn ClientCertVerifier>>);
+
+impl LateClientCertVerifier {
+    pub(crate) fn set(&self, verifier: Arc<dyn ClientCertVerifier>) {
+        // Builder is consumed by `build`, so the verifier is set at most once
+        let _ = self.0.set(verifier);
+    }
+
+    fn verifier(&self) -> Result<&Arc<dyn ClientCertVerifier>, rustls::Error> {
+        self.0.get().ok_or_else(|| {
+            rustls::Error::General("client authentication is not configured".to_owned())
+        })
+    }
+}
+
+impl ClientCertVerifier for LateClientCertVerifier {
+    fn offer_client_auth(&self) -> bool {
+        match self.0.get() {
+            Some(verifier) => verifier.offer_client_auth(),
+            None => false,
+        }
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        match self.0.get() {
+            Some(verifier) => verifier.client_auth_mandatory(),
+            None => false,
+        }
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        match self.0.get() {
+            Some(verifier) => verifier.root_hint_subjects(),
+            None => &[],
+        }
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        self.verifier()?
+            .verify_client_cert(end_entity, intermediates, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier()?.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier()?.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        match self.0.get() {
+            Some(verifier) => verifier.supported_verify_schemes(),
+            None => Vec::new(),
+        }
+    }
+}
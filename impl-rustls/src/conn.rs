@@ -0,0 +1,157 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use rustls::pki_types::CertificateDer;
+use rustls::ClientConnection;
+use rustls::Connection;
+use rustls::ServerConnection;
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+
+use crate::ocsp::OcspResponseSlot;
+use crate::CryptoProviderKind;
+use crate::RustlsSessionRef;
+
+/// Connection properties which `rustls` connection does not provide.
+#[derive(Debug)]
+enum Side {
+    Server {
+        /// Server certificate.
+        cert: Option<CertificateDer<'static>>,
+    },
+    Client {
+        /// SNI.
+        server_name: Option<String>,
+        /// OCSP response if stapling was requested.
+        ocsp_response: Option<OcspResponseSlot>,
+    },
+}
+
+/// `rustls` connection with properties needed for stream introspection.
+///
+/// Shared by async and blocking streams.
+#[derive(Debug)]
+pub(crate) struct Conn {
+    conn: Connection,
+    side: Side,
+    provider: CryptoProviderKind,
+}
+
+impl Deref for Conn {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl DerefMut for Conn {
+    fn deref_mut(&mut self) -> &mut Connection {
+        &mut self.conn
+    }
+}
+
+impl Conn {
+    pub fn new_server(
+        conn: ServerConnection,
+        cert: Option<CertificateDer<'static>>,
+        provider: CryptoProviderKind,
+    ) -> Conn {
+        Conn {
+            conn: Connection::Server(conn),
+            side: Side::Server { cert },
+            provider,
+        }
+    }
+
+    pub fn new_client(
+        conn: ClientConnection,
+        server_name: Option<String>,
+        ocsp_response: Option<OcspResponseSlot>,
+        provider: CryptoProviderKind,
+    ) -> Conn {
+        Conn {
+            conn: Connection::Client(conn),
+            side: Side::Client {
+                server_name,
+                ocsp_response,
+            },
+            provider,
+        }
+    }
+
+    /// Unwrap the `rustls` connection.
+    #[cfg(target_os = "linux")]
+    pub fn into_connection(self) -> Connection {
+        self.conn
+    }
+
+    pub fn session(&self) -> RustlsSessionRef<'_> {
+        match &self.conn {
+            Connection::Client(conn) => RustlsSessionRef::Client(conn),
+            Connection::Server(conn) => RustlsSessionRef::Server(conn),
+        }
+    }
+
+    pub fn impl_info(&self) -> ImplInfo {
+        crate::info_for(self.provider)
+    }
+
+    pub fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.conn.alpn_protocol().map(Vec::from))
+    }
+
+    pub fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(match (&self.conn, &self.side) {
+            (Connection::Server(conn), _) => conn.server_name().map(str::to_owned),
+            (Connection::Client(_), Side::Client { server_name, .. }) => server_name.clone(),
+            (Connection::Client(_), Side::Server { .. }) => None,
+        })
+    }
+
+    pub fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(match &self.side {
+            Side::Server { .. } => None,
+            Side::Client { ocsp_response, .. } => ocsp_response
+                .as_ref()
+                .and_then(|ocsp_response| ocsp_response.lock().unwrap().clone()),
+        })
+    }
+
+    pub fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.conn
+            .export_keying_material(vec![0; len], label.as_bytes(), context)
+            .map_err(anyhow::Error::new)
+    }
+
+    pub fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => self.tls_server_end_point(),
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn server_certificate(&self) -> Option<&CertificateDer<'static>> {
+        match &self.side {
+            Side::Server { cert } => cert.as_ref(),
+            Side::Client { .. } => self.conn.peer_certificates()?.first(),
+        }
+    }
+
+    fn tls_server_end_point(&self) -> anyhow::Result<Vec<u8>> {
+        let cert = self
+            .server_certificate()
+            .ok_or(ChannelBindingError::NoServerCertificate)?;
+        crate::channel_binding::tls_server_end_point(cert, self.provider)
+    }
+}
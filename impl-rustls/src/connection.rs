@@ -0,0 +1,94 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsHandshakeState;
+
+use crate::conn::Conn;
+use crate::RustlsSessionRef;
+
+/// Sans-IO connection: `rustls` connection does not perform I/O itself.
+#[derive(Debug)]
+pub struct TlsConnection(pub(crate) Conn);
+
+impl TlsConnection {
+    /// Get the `rustls` session.
+    pub fn session(&self) -> RustlsSessionRef<'_> {
+        self.0.session()
+    }
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        if self.0.is_handshaking() {
+            TlsHandshakeState::InProgress
+        } else {
+            TlsHandshakeState::Complete
+        }
+    }
+
+    fn feed_ciphertext(&mut self, mut data: &[u8]) -> io::Result<usize> {
+        // `rustls` treats empty read as EOF
+        if data.is_empty() {
+            return Ok(0);
+        }
+        let read = self.0.read_tls(&mut data)?;
+        self.0
+            .process_new_packets()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(read)
+    }
+
+    fn wants_write(&self) -> bool {
+        self.0.wants_write()
+    }
+
+    fn take_ciphertext(&mut self, mut buf: &mut [u8]) -> io::Result<usize> {
+        self.0.write_tls(&mut buf)
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.reader().read(buf)
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.writer().write(buf)
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        self.0.send_close_notify();
+        Ok(())
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.get_alpn_protocol()
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        self.0.server_name()
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.ocsp_response()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        self.0.export_keying_material(label, context, len)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        self.0.channel_binding(kind)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        self.0.impl_info()
+    }
+}
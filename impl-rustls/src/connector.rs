@@ -1,26 +1,108 @@
 use std::convert::TryFrom;
+use std::net::TcpStream;
 use std::sync::Arc;
 
-use rustls::StreamOwned;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::danger::ServerCertVerified;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::CertificateRevocationListDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::ServerName;
+use rustls::pki_types::UnixTime;
+use rustls::sign::CertifiedKey;
+use rustls::sign::SingleCertAndKey;
+use rustls::DigitallySignedStruct;
+use rustls::SignatureScheme;
 
-use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
-use tls_api::BoxFuture;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::QuicConnection;
+use tls_api::RetryableError;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
-use crate::handshake::HandshakeFuture;
-use crate::RustlsStream;
-use std::future::Future;
+use crate::conn::Conn;
+use crate::key_log::RustlsKeyLog;
+use crate::ocsp::OcspClient;
+use crate::quic::SecretsKeyLog;
+use crate::CryptoProviderKind;
 
 pub struct TlsConnectorBuilder {
     pub config: rustls::ClientConfig,
     pub verify_hostname: bool,
     pub root_store: rustls::RootCertStore,
+    pub crls: Vec<CertificateRevocationListDer<'static>>,
+    pub revocation_policy: RevocationPolicy,
+    pub ocsp_stapling: OcspStapling,
+    provider: CryptoProviderKind,
 }
 pub struct TlsConnector {
     pub config: Arc<rustls::ClientConfig>,
+    ocsp: Option<OcspClient>,
+    provider: CryptoProviderKind,
+}
+
+#[derive(Debug)]
+struct NoCertificateVerifier(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoCertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn default_root_store() -> rustls::RootCertStore {
+    rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    }
 }
 
 impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
@@ -39,25 +121,10 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
 
     fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
         if !verify {
-            struct NoCertificateVerifier;
-
-            impl rustls::client::ServerCertVerifier for NoCertificateVerifier {
-                fn verify_server_cert(
-                    &self,
-                    _end_entity: &rustls::Certificate,
-                    _intermediates: &[rustls::Certificate],
-                    _server_name: &rustls::ServerName,
-                    _scts: &mut dyn Iterator<Item = &[u8]>,
-                    _ocsp_response: &[u8],
-                    _now: std::time::SystemTime,
-                ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
-                    Ok(rustls::client::ServerCertVerified::assertion())
-                }
-            }
-
+            let verifier = NoCertificateVerifier(self.config.crypto_provider().clone());
             self.config
                 .dangerous()
-                .set_certificate_verifier(Arc::new(NoCertificateVerifier));
+                .set_certificate_verifier(Arc::new(verifier));
             self.verify_hostname = false;
         } else {
             if !self.verify_hostname {
@@ -69,60 +136,168 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
     }
 
     fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
-        let cert = rustls::Certificate(cert.to_vec());
-        self.root_store.add(&cert).map_err(anyhow::Error::new)?;
+        let cert = CertificateDer::from(cert.to_vec());
+        self.root_store.add(cert).map_err(anyhow::Error::new)?;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, crl: &[u8]) -> anyhow::Result<()> {
+        self.crls
+            .push(CertificateRevocationListDer::from(crl.to_vec()));
+        Ok(())
+    }
+
+    fn set_revocation_policy(&mut self, policy: RevocationPolicy) -> anyhow::Result<()> {
+        self.revocation_policy = policy;
+        Ok(())
+    }
+
+    fn set_client_certificate(&mut self, cert: &[u8], key: &[u8]) -> anyhow::Result<()> {
+        let cert = CertificateDer::from(cert.to_vec());
+        let key = PrivateKeyDer::try_from(key.to_vec()).map_err(crate::Error::PrivateKey)?;
+        let certified_key = CertifiedKey::from_der(vec![cert], key, self.config.crypto_provider())
+            .map_err(anyhow::Error::new)?;
+        self.config.client_auth_cert_resolver = Arc::new(SingleCertAndKey::from(certified_key));
+        Ok(())
+    }
+
+    fn set_ocsp_stapling(&mut self, mode: OcspStapling) -> anyhow::Result<()> {
+        self.ocsp_stapling = mode;
+        Ok(())
+    }
+
+    fn set_key_log(&mut self, key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        self.config.key_log = Arc::new(RustlsKeyLog(key_log));
         Ok(())
     }
 
     fn build(self) -> anyhow::Result<TlsConnector> {
         let mut config = self.config;
-        if !self.root_store.is_empty() {
-            let mut new_config = rustls::ClientConfig::builder()
-                .with_safe_defaults()
-                .with_root_certificates(self.root_store)
-                .with_no_client_auth();
-            new_config.alpn_protocols = config.alpn_protocols;
-            new_config.session_storage = config.session_storage;
-            new_config.max_fragment_size = config.max_fragment_size;
-            new_config.client_auth_cert_resolver = config.client_auth_cert_resolver;
-            new_config.enable_tickets = config.enable_tickets;
-            new_config.enable_sni = config.enable_sni;
-            new_config.key_log = config.key_log;
-            new_config.enable_early_data = config.enable_early_data;
-            config = new_config;
-        }
+        let crypto_provider = config.crypto_provider().clone();
+        // Verifier is replaced only when the default one is not enough,
+        // to keep the verifier possibly configured with `underlying_mut`.
+        let verifier: Option<Arc<dyn ServerCertVerifier>> = if self.verify_hostname
+            && (!self.root_store.is_empty()
+                || !self.crls.is_empty()
+                || self.ocsp_stapling != OcspStapling::Disabled)
+        {
+            let root_store = if self.root_store.is_empty() {
+                default_root_store()
+            } else {
+                self.root_store
+            };
+            let verifier = WebPkiServerVerifier::builder_with_provider(
+                root_store.into(),
+                crypto_provider.clone(),
+            )
+            .with_crls(self.crls);
+            let verifier = match self.revocation_policy {
+                RevocationPolicy::HardFail => verifier,
+                RevocationPolicy::SoftFail => verifier.allow_unknown_revocation_status(),
+                RevocationPolicy::LeafOnly => verifier.only_check_end_entity_revocation(),
+            };
+            let verifier = verifier.build().map_err(anyhow::Error::new)?;
+            config
+                .dangerous()
+                .set_certificate_verifier(verifier.clone());
+            Some(verifier)
+        } else {
+            None
+        };
+        let ocsp = match self.ocsp_stapling {
+            OcspStapling::Disabled => None,
+            mode => Some(OcspClient {
+                verifier: verifier
+                    .unwrap_or_else(|| Arc::new(NoCertificateVerifier(crypto_provider))),
+                must_staple: mode == OcspStapling::MustStaple,
+            }),
+        };
         Ok(TlsConnector {
             config: Arc::new(config),
+            ocsp,
+            provider: self.provider,
         })
     }
 }
 
 impl TlsConnector {
-    pub fn connect_impl<'a, S>(
-        &'a self,
-        domain: &'a str,
+    /// Create a builder using the given crypto provider
+    /// instead of the [default](CryptoProviderKind::default) one.
+    pub fn builder_with_crypto_provider(
+        provider: CryptoProviderKind,
+    ) -> anyhow::Result<TlsConnectorBuilder> {
+        let config = rustls::ClientConfig::builder_with_provider(provider.provider()?)
+            .with_safe_default_protocol_versions()
+            .map_err(anyhow::Error::new)?
+            .with_root_certificates(default_root_store())
+            .with_no_client_auth();
+        Ok(TlsConnectorBuilder {
+            config,
+            verify_hostname: true,
+            root_store: rustls::RootCertStore::empty(),
+            crls: Vec::new(),
+            revocation_policy: RevocationPolicy::default(),
+            ocsp_stapling: OcspStapling::default(),
+            provider,
+        })
+    }
+
+    /// Crypto provider used by this connector.
+    pub fn crypto_provider(&self) -> CryptoProviderKind {
+        self.provider
+    }
+
+    fn new_conn(&self, domain: &str) -> anyhow::Result<Conn> {
+        self.new_conn_with_config(&self.config, domain)
+    }
+
+    fn new_conn_with_config(
+        &self,
+        config: &Arc<rustls::ClientConfig>,
+        domain: &str,
+    ) -> anyhow::Result<Conn> {
+        let dns_name = ServerName::try_from(domain.to_owned()).map_err(anyhow::Error::new)?;
+        // rustls does not send IP addresses in SNI
+        let server_name = match &dns_name {
+            ServerName::DnsName(name) => Some(name.as_ref().to_owned()),
+            _ => None,
+        };
+        let (config, ocsp_response) = match &self.ocsp {
+            Some(ocsp) => {
+                let (config, ocsp_response) = ocsp.config_for_connection(config);
+                (config, Some(ocsp_response))
+            }
+            None => (config.clone(), None),
+        };
+        let conn = rustls::ClientConnection::new(config, dns_name).map_err(anyhow::Error::new)?;
+        Ok(Conn::new_client(
+            conn,
+            server_name,
+            ocsp_response,
+            self.provider,
+        ))
+    }
+
+    pub async fn connect_impl<S>(
+        &self,
+        domain: &str,
         stream: S,
-    ) -> impl Future<Output = anyhow::Result<crate::TlsStream<S>>> + 'a
+    ) -> anyhow::Result<crate::TlsStream<S>>
     where
         S: AsyncSocket,
     {
-        let dns_name = rustls::ServerName::try_from(domain);
-        let dns_name = match dns_name.map_err(|_| anyhow::Error::new(webpki::InvalidDnsNameError)) {
-            Ok(dns_name) => dns_name,
-            Err(e) => return BoxFuture::new(async { Err(e) }),
-        };
-        let conn = rustls::ClientConnection::new(self.config.clone(), dns_name);
-        let conn = match conn.map_err(|e| anyhow::Error::new(e)) {
-            Ok(conn) => conn,
-            Err(e) => return BoxFuture::new(async { Err(e) }),
-        };
-        let tls_stream: crate::TlsStream<S> =
-            crate::TlsStream::new(RustlsStream::Client(StreamOwned {
-                conn,
-                sock: AsyncIoAsSyncIo::new(stream),
-            }));
+        let conn = self.new_conn(domain)?;
+        crate::TlsStream::handshake(stream, crate::TlsConnection(conn)).await
+    }
 
-        BoxFuture::new(HandshakeFuture::MidHandshake(tls_stream))
+    /// Blocking version of [`connect_impl`](Self::connect_impl).
+    pub fn connect_blocking_impl(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<crate::TlsStreamBlocking> {
+        let conn = self.new_conn(domain)?;
+        crate::TlsStreamBlocking::handshake(stream, conn)
     }
 }
 
@@ -136,32 +311,83 @@ impl tls_api::TlsConnector for TlsConnector {
         &mut self.config
     }
 
-    const IMPLEMENTED: bool = true;
-    const SUPPORTS_ALPN: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        crl: true,
+        client_auth: true,
+        ocsp_stapling: true,
+        key_log: true,
+        blocking: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
     }
 
     fn builder() -> anyhow::Result<TlsConnectorBuilder> {
-        let mut roots = rustls::RootCertStore::empty();
-        roots.add_server_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
-            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
-                ta.subject,
-                ta.spki,
-                ta.name_constraints,
-            )
-        }));
-        let config = rustls::ClientConfig::builder()
-            .with_safe_defaults()
-            .with_root_certificates(roots)
-            .with_no_client_auth();
-        Ok(TlsConnectorBuilder {
-            config,
-            verify_hostname: true,
-            root_store: rustls::RootCertStore::empty(),
-        })
+        TlsConnector::builder_with_crypto_provider(CryptoProviderKind::default())
+    }
+
+    fn classify_error(error: &anyhow::Error) -> Option<RetryableError> {
+        crate::error::classify(tls_api::spi::find_error(error)?)
+    }
+
+    fn connect_blocking(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        self.connect_blocking_impl(domain, stream)
+            .map(TlsStreamBlocking::new)
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        Ok(TlsConnection::new(crate::TlsConnection(
+            self.new_conn(domain)?,
+        )))
     }
 
-    spi_connector_common!();
+    spi_connector_common!(crate::TlsStream<S>);
+}
+
+impl tls_api::QuicConnector for TlsConnector {
+    fn new_quic_connection(
+        &self,
+        domain: &str,
+        transport_parameters: &[u8],
+    ) -> anyhow::Result<QuicConnection> {
+        let dns_name = ServerName::try_from(domain.to_owned()).map_err(anyhow::Error::new)?;
+        // Shallow copy to collect secrets of this connection, see `SecretsKeyLog`
+        let mut config = (*self.config).clone();
+        let secrets = SecretsKeyLog::new(config.key_log.clone());
+        config.key_log = secrets.clone();
+        let conn = rustls::quic::ClientConnection::new(
+            Arc::new(config),
+            crate::quic::QUIC_VERSION,
+            dns_name,
+            transport_parameters.to_vec(),
+        )
+        .map_err(anyhow::Error::new)?;
+        let conn = crate::QuicConnection::new(conn.into(), secrets, self.provider)?;
+        Ok(QuicConnection::new(conn))
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl tls_api::KtlsConnector for TlsConnector {
+    fn connect_ktls(
+        &self,
+        domain: &str,
+        stream: TcpStream,
+    ) -> anyhow::Result<tls_api::MaybeKtlsStream> {
+        let mut config = (*self.config).clone();
+        config.enable_secret_extraction = true;
+        let conn = self.new_conn_with_config(&Arc::new(config), domain)?;
+        crate::ktls::handshake(stream, conn)
+    }
 }
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use rustls::crypto::CryptoProvider;
+
+#[cfg(not(any(feature = "ring", feature = "aws-lc-rs")))]
+compile_error!("either `ring` or `aws-lc-rs` feature must be enabled");
+
+/// Crypto provider used by `rustls`.
+///
+/// `ring` requires `ring` crate feature (enabled by default),
+/// `aws-lc-rs` requires `aws-lc-rs` crate feature.
+/// With `fips` crate feature `aws-lc-rs` provider uses the FIPS module,
+/// and `ring` is not offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoProviderKind {
+    /// [ring](https://crates.io/crates/ring).
+    #[cfg(all(feature = "ring", not(feature = "fips")))]
+    Ring,
+    /// [aws-lc-rs](https://crates.io/crates/aws-lc-rs).
+    AwsLcRs,
+}
+
+impl Default for CryptoProviderKind {
+    /// `aws-lc-rs` if `aws-lc-rs` feature is enabled, `ring` otherwise.
+    fn default() -> CryptoProviderKind {
+        #[cfg(any(feature = "aws-lc-rs", not(feature = "ring")))]
+        {
+            CryptoProviderKind::AwsLcRs
+        }
+        #[cfg(all(feature = "ring", not(feature = "aws-lc-rs")))]
+        {
+            CryptoProviderKind::Ring
+        }
+    }
+}
+
+impl CryptoProviderKind {
+    /// Provider name as reported in [`ImplInfo`](tls_api::ImplInfo).
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(all(feature = "ring", not(feature = "fips")))]
+            CryptoProviderKind::Ring => "ring",
+            CryptoProviderKind::AwsLcRs => "aws-lc-rs",
+        }
+    }
+
+    /// Whether this provider is backed by a FIPS validated module.
+    pub fn fips(self) -> bool {
+        matches!(self.provider(), Ok(p) if p.fips())
+    }
+
+    pub(crate) fn provider(self) -> anyhow::Result<Arc<CryptoProvider>> {
+        match self {
+            #[cfg(all(feature = "ring", not(feature = "fips")))]
+            CryptoProviderKind::Ring => Ok(Arc::new(rustls::crypto::ring::default_provider())),
+            #[cfg(feature = "aws-lc-rs")]
+            CryptoProviderKind::AwsLcRs => {
+                Ok(Arc::new(rustls::crypto::aws_lc_rs::default_provider()))
+            }
+            #[cfg(not(feature = "aws-lc-rs"))]
+            CryptoProviderKind::AwsLcRs => {
+                Err(crate::Error::CryptoProviderNotEnabled("aws-lc-rs").into())
+            }
+        }
+    }
+}
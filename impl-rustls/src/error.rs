@@ -1,5 +1,97 @@
+use rustls::AlertDescription;
+use rustls::CertificateError;
+use rustls::PeerIncompatible;
+use tls_api::QuicEncryptionLevel;
+use tls_api::RetryableError;
+
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum Error {
     #[error("cannot set_verify_hostname(true) after set_verify_hostname(false)")]
     VerifyHostnameTrue,
+    #[error("failed to parse private key: {}", _0)]
+    PrivateKey(&'static str),
+    #[error("CRLs can only be used with client authentication enabled")]
+    CrlWithoutClientAuth,
+    #[error("server did not staple OCSP response")]
+    OcspResponseNotStapled,
+    #[error("QUIC secret {} was not logged by rustls", _0)]
+    QuicSecretNotLogged(String),
+    #[error("QUIC initial secrets are derived from the connection id, not by TLS")]
+    QuicInitialSecrets,
+    #[error("QUIC CRYPTO data received at {:?} level, expected {:?}", _0, _1)]
+    QuicUnexpectedLevel(QuicEncryptionLevel, QuicEncryptionLevel),
+    #[cfg(target_os = "linux")]
+    #[error("extracted secrets do not match negotiated cipher {:?}", _0)]
+    KtlsSecretsMismatch(tls_api::spi::KtlsCipher),
+    #[cfg(not(feature = "aws-lc-rs"))]
+    #[error("crypto provider {} is not enabled by crate features", _0)]
+    CryptoProviderNotEnabled(&'static str),
+}
+
+/// Classify handshake errors for [`tls_api::TlsConnector::classify_error`].
+pub(crate) fn classify(error: &rustls::Error) -> Option<RetryableError> {
+    match error {
+        rustls::Error::PeerIncompatible(e) => Some(match e {
+            PeerIncompatible::NoCipherSuitesInCommon => RetryableError::CipherSuite,
+            PeerIncompatible::NoSignatureSchemesInCommon
+            | PeerIncompatible::NoCertificateRequestSignatureSchemesInCommon
+            | PeerIncompatible::SignatureAlgorithmsExtensionRequired => {
+                RetryableError::SignatureAlgorithm
+            }
+            PeerIncompatible::ServerDoesNotSupportTls12Or13
+            | PeerIncompatible::ServerTlsVersionIsDisabledByOurConfig
+            | PeerIncompatible::SupportedVersionsExtensionRequired
+            | PeerIncompatible::Tls12NotOffered
+            | PeerIncompatible::Tls12NotOfferedOrEnabled => RetryableError::ProtocolVersion,
+            _ => RetryableError::HandshakeFailure,
+        }),
+        rustls::Error::AlertReceived(alert) => match alert {
+            AlertDescription::ProtocolVersion => Some(RetryableError::ProtocolVersion),
+            AlertDescription::HandshakeFailure | AlertDescription::InsufficientSecurity => {
+                Some(RetryableError::HandshakeFailure)
+            }
+            _ => None,
+        },
+        rustls::Error::InvalidCertificate(
+            CertificateError::UnsupportedSignatureAlgorithmContext { .. }
+            | CertificateError::UnsupportedSignatureAlgorithmForPublicKeyContext { .. },
+        ) => Some(RetryableError::SignatureAlgorithm),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn classify_in_io_error() {
+        let e = anyhow::Error::new(io::Error::new(
+            io::ErrorKind::InvalidData,
+            rustls::Error::PeerIncompatible(PeerIncompatible::NoCipherSuitesInCommon),
+        ));
+        assert_eq!(
+            Some(RetryableError::CipherSuite),
+            <crate::TlsConnector as tls_api::TlsConnector>::classify_error(&e)
+        );
+
+        let e = anyhow::Error::new(io::Error::new(
+            io::ErrorKind::InvalidData,
+            rustls::Error::AlertReceived(AlertDescription::ProtocolVersion),
+        ));
+        assert_eq!(
+            Some(RetryableError::ProtocolVersion),
+            <crate::TlsConnector as tls_api::TlsConnector>::classify_error(&e)
+        );
+
+        let e = anyhow::Error::new(rustls::Error::InvalidCertificate(
+            CertificateError::UnknownIssuer,
+        ));
+        assert_eq!(
+            None,
+            <crate::TlsConnector as tls_api::TlsConnector>::classify_error(&e)
+        );
+    }
 }
@@ -0,0 +1,13 @@
+use std::sync::Arc;
+
+use tls_api::KeyLog;
+
+/// Adapter of [`tls_api::KeyLog`] to [`rustls::KeyLog`].
+#[derive(Debug)]
+pub(crate) struct RustlsKeyLog(pub Arc<dyn KeyLog>);
+
+impl rustls::KeyLog for RustlsKeyLog {
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        self.0.log(label, client_random, secret)
+    }
+}
@@ -0,0 +1,157 @@
+//! Kernel TLS offload after `rustls` handshake.
+
+use std::io;
+use std::io::Read;
+use std::net::TcpStream;
+
+use rustls::CipherSuite;
+use rustls::ConnectionTrafficSecrets;
+use rustls::ProtocolVersion;
+use tls_api::spi::configure_ktls;
+use tls_api::spi::enable_tls_ulp;
+use tls_api::spi::KtlsCipher;
+use tls_api::spi::KtlsCryptoInfo;
+use tls_api::KtlsStream;
+use tls_api::MaybeKtlsStream;
+use tls_api::TlsStreamBlocking;
+
+use crate::conn::Conn;
+
+/// Reader which never reads past the end of the current TLS record.
+///
+/// After the handshake, all received records must be processed by `rustls`,
+/// the following records are decrypted by the kernel.
+struct RecordReader<'a> {
+    socket: &'a mut TcpStream,
+    /// Bytes left in the current record.
+    remaining: &'a mut usize,
+}
+
+impl<'a> Read for RecordReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if *self.remaining == 0 {
+            // `rustls` reads into a buffer large enough for a record header
+            let mut header = [0; 5];
+            self.socket.read_exact(&mut header)?;
+            *self.remaining = u16::from_be_bytes([header[3], header[4]]) as usize;
+            buf[..header.len()].copy_from_slice(&header);
+            return Ok(header.len());
+        }
+        let len = buf.len().min(*self.remaining);
+        let read = self.socket.read(&mut buf[..len])?;
+        *self.remaining -= read;
+        Ok(read)
+    }
+}
+
+fn write_all_tls(conn: &mut Conn, socket: &mut TcpStream) -> io::Result<()> {
+    while conn.wants_write() {
+        if conn.write_tls(socket)? == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+    }
+    Ok(())
+}
+
+/// Kernel cipher for the negotiated cipher suite, `None` if the kernel cannot do it.
+fn ktls_cipher(suite: CipherSuite) -> Option<KtlsCipher> {
+    match suite {
+        CipherSuite::TLS13_AES_128_GCM_SHA256
+        | CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256
+        | CipherSuite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256 => Some(KtlsCipher::Aes128Gcm),
+        CipherSuite::TLS13_AES_256_GCM_SHA384
+        | CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+        | CipherSuite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384 => Some(KtlsCipher::Aes256Gcm),
+        CipherSuite::TLS13_CHACHA20_POLY1305_SHA256
+        | CipherSuite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256
+        | CipherSuite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256 => {
+            Some(KtlsCipher::Chacha20Poly1305)
+        }
+        _ => None,
+    }
+}
+
+fn crypto_info(
+    version: u16,
+    cipher: KtlsCipher,
+    (seq, secrets): (u64, ConnectionTrafficSecrets),
+) -> anyhow::Result<KtlsCryptoInfo> {
+    let (key, iv) = match (cipher, secrets) {
+        (KtlsCipher::Aes128Gcm, ConnectionTrafficSecrets::Aes128Gcm { key, iv })
+        | (KtlsCipher::Aes256Gcm, ConnectionTrafficSecrets::Aes256Gcm { key, iv })
+        | (KtlsCipher::Chacha20Poly1305, ConnectionTrafficSecrets::Chacha20Poly1305 { key, iv }) => {
+            (key, iv)
+        }
+        _ => return Err(crate::Error::KtlsSecretsMismatch(cipher).into()),
+    };
+    Ok(KtlsCryptoInfo {
+        version,
+        cipher,
+        key: key.as_ref().to_vec(),
+        iv: iv.as_ref().to_vec(),
+        seq,
+    })
+}
+
+/// Perform the handshake, then configure kTLS on the socket.
+///
+/// `conn` must be created with secret extraction enabled.
+pub(crate) fn handshake(mut socket: TcpStream, mut conn: Conn) -> anyhow::Result<MaybeKtlsStream> {
+    let mut remaining = 0;
+    while conn.is_handshaking() {
+        write_all_tls(&mut conn, &mut socket)?;
+        let mut reader = RecordReader {
+            socket: &mut socket,
+            remaining: &mut remaining,
+        };
+        if conn.read_tls(&mut reader)? == 0 {
+            return Err(anyhow::Error::new(io::Error::from(
+                io::ErrorKind::UnexpectedEof,
+            )));
+        }
+        if let Err(e) = conn.process_new_packets() {
+            // Send the alert
+            let _ = write_all_tls(&mut conn, &mut socket);
+            return Err(anyhow::Error::new(e));
+        }
+    }
+    // Client `Finished`, session tickets
+    write_all_tls(&mut conn, &mut socket)?;
+
+    let version = match conn.protocol_version() {
+        Some(ProtocolVersion::TLSv1_2) => Some(0x0303),
+        Some(ProtocolVersion::TLSv1_3) => Some(0x0304),
+        _ => None,
+    };
+    let cipher = conn
+        .negotiated_cipher_suite()
+        .and_then(|s| ktls_cipher(s.suite()));
+
+    // Check everything which can be checked before the connection is consumed
+    // by secret extraction, and `tls` kernel module is available
+    let (version, cipher) = match (version, cipher) {
+        (Some(version), Some(cipher)) if enable_tls_ulp(&socket).is_ok() => (version, cipher),
+        _ => {
+            return Ok(MaybeKtlsStream::Userspace(TlsStreamBlocking::new(
+                crate::TlsStreamBlocking::from_parts(socket, conn),
+            )))
+        }
+    };
+
+    let alpn_protocol = conn.alpn_protocol().map(Vec::from);
+    let info = conn.impl_info();
+    let secrets = conn
+        .into_connection()
+        .dangerous_extract_secrets()
+        .map_err(anyhow::Error::new)?;
+    configure_ktls(
+        &socket,
+        &crypto_info(version, cipher, secrets.tx)?,
+        &crypto_info(version, cipher, secrets.rx)?,
+    )?;
+    Ok(MaybeKtlsStream::Ktls(KtlsStream::new(
+        socket,
+        alpn_protocol,
+        info,
+    )))
+}
@@ -3,27 +3,67 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 mod acceptor;
+mod blocking;
+mod channel_binding;
+mod client_auth;
+mod conn;
+mod connection;
 mod connector;
+mod crypto_provider;
 mod error;
-mod handshake;
+mod key_log;
+#[cfg(target_os = "linux")]
+mod ktls;
+mod ocsp;
+mod quic;
 mod rustls_utils;
 mod stream;
 
 use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
 
 pub use acceptor::TlsAcceptor;
 pub use acceptor::TlsAcceptorBuilder;
 pub use connector::TlsConnector;
 pub use connector::TlsConnectorBuilder;
+pub use crypto_provider::CryptoProviderKind;
+pub use quic::QuicConnection;
 pub use rustls_utils::RustlsSessionRef;
 
-pub(crate) use crate::rustls_utils::RustlsStream;
+pub use blocking::TlsStreamBlocking;
+pub use connection::TlsConnection;
 pub(crate) use error::Error;
 pub use stream::TlsStream;
 
+/// Version requirement of `rustls` in `Cargo.toml`, checked by a test.
+const RUSTLS_VERSION: &str = "^0.23.29";
+
+/// Info for the default crypto provider.
 pub(crate) fn info() -> ImplInfo {
+    info_for(CryptoProviderKind::default())
+}
+
+pub(crate) fn info_for(provider: CryptoProviderKind) -> ImplInfo {
     ImplInfo {
         name: "rustls",
-        version: "unknown",
+        version: RUSTLS_VERSION,
+        crypto_provider: Some(provider.name()),
+        fips: provider.fips(),
+    }
+}
+
+/// `rustls` does not implement TLS 1.0 and 1.1.
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] =
+    &[ProtocolVersion::Tls12, ProtocolVersion::Tls13];
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn dep_version() {
+        tls_api_test::test_dep_version(
+            include_str!("../Cargo.toml"),
+            "rustls",
+            super::RUSTLS_VERSION,
+        );
     }
 }
@@ -0,0 +1,141 @@
+//! OCSP stapling
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::danger::ServerCertVerified;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::ServerName;
+use rustls::pki_types::UnixTime;
+use rustls::server::ClientHello;
+use rustls::server::ResolvesServerCert;
+use rustls::sign::CertifiedKey;
+use rustls::CertificateError;
+use rustls::DigitallySignedStruct;
+use rustls::OtherError;
+use rustls::SignatureScheme;
+
+/// Stapled OCSP response received by a client connection.
+pub(crate) type OcspResponseSlot = Arc<Mutex<Option<Vec<u8>>>>;
+
+/// Server certificate resolver which allows replacing stapled OCSP response
+/// after the acceptor is built.
+#[derive(Debug)]
+pub(crate) struct StaplingCertResolver(RwLock<Arc<CertifiedKey>>);
+
+impl StaplingCertResolver {
+    pub(crate) fn new(certified_key: CertifiedKey) -> StaplingCertResolver {
+        StaplingCertResolver(RwLock::new(Arc::new(certified_key)))
+    }
+
+    pub(crate) fn set_ocsp_response(&self, ocsp_response: &[u8]) {
+        let mut certified_key = self.0.write().unwrap();
+        let mut new_certified_key = CertifiedKey::clone(&certified_key);
+        new_certified_key.ocsp = Some(ocsp_response.to_vec());
+        *certified_key = Arc::new(new_certified_key);
+    }
+
+    /// Server end-entity certificate.
+    pub(crate) fn end_entity_cert(&self) -> Option<CertificateDer<'static>> {
+        self.0.read().unwrap().cert.first().cloned()
+    }
+}
+
+impl ResolvesServerCert for StaplingCertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.read().unwrap().clone())
+    }
+}
+
+/// Client side OCSP stapling configuration.
+///
+/// `rustls` always requests stapling, but does not expose the response,
+/// so it is intercepted by a verifier installed for each connection.
+pub(crate) struct OcspClient {
+    pub(crate) verifier: Arc<dyn ServerCertVerifier>,
+    pub(crate) must_staple: bool,
+}
+
+impl OcspClient {
+    /// Config for a single connection which records stapled response.
+    pub(crate) fn config_for_connection(
+        &self,
+        config: &rustls::ClientConfig,
+    ) -> (Arc<rustls::ClientConfig>, OcspResponseSlot) {
+        let ocsp_response = OcspResponseSlot::default();
+        let mut config = config.clone();
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(OcspRecordingVerifier {
+                verifier: self.verifier.clone(),
+                must_staple: self.must_staple,
+                ocsp_response: ocsp_response.clone(),
+            }));
+        (Arc::new(config), ocsp_response)
+    }
+}
+
+/// Delegate to the actual verifier, and remember stapled OCSP response.
+///
+/// Note the verifier is not invoked for resumed sessions,
+/// so no response is recorded (or required) for them.
+#[derive(Debug)]
+struct OcspRecordingVerifier {
+    verifier: Arc<dyn ServerCertVerifier>,
+    must_staple: bool,
+    ocsp_response: OcspResponseSlot,
+}
+
+impl ServerCertVerifier for OcspRecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        if ocsp_response.is_empty() {
+            if self.must_staple {
+                return Err(rustls::Error::InvalidCertificate(CertificateError::Other(
+                    OtherError(Arc::new(crate::Error::OcspResponseNotStapled)),
+                )));
+            }
+        } else {
+            *self.ocsp_response.lock().unwrap() = Some(ocsp_response.to_vec());
+        }
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.verifier.supported_verify_schemes()
+    }
+}
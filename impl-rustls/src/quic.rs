@@ -0,0 +1,234 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use rustls::quic::KeyChange;
+use rustls::quic::Version;
+use tls_api::ImplInfo;
+use tls_api::QuicConnectionDyn;
+use tls_api::QuicEncryptionLevel;
+use tls_api::QuicEvent;
+use tls_api::QuicSecrets;
+
+use crate::CryptoProviderKind;
+
+/// Key log which remembers traffic secrets of a connection.
+///
+/// `rustls` exposes QUIC keys, but not the secrets; they are taken from the key log.
+/// Secrets are also passed to the key log configured by the user.
+///
+/// The key log does not know which connection it is called for,
+/// so each QUIC connection gets a copy of the config with its own key log.
+/// The copy is cheap: verifiers, resolvers, session storage and the crypto provider
+/// are shared, only ALPN protocols and a few small setting vectors are copied.
+pub(crate) struct SecretsKeyLog {
+    user: Arc<dyn rustls::KeyLog>,
+    secrets: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+impl fmt::Debug for SecretsKeyLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretsKeyLog").finish_non_exhaustive()
+    }
+}
+
+impl SecretsKeyLog {
+    pub(crate) fn new(user: Arc<dyn rustls::KeyLog>) -> Arc<SecretsKeyLog> {
+        Arc::new(SecretsKeyLog {
+            user,
+            secrets: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn take(&self, label: &str) -> anyhow::Result<Vec<u8>> {
+        let mut secrets = self.secrets.lock().unwrap();
+        match secrets.iter().position(|(l, _)| l == label) {
+            Some(pos) => Ok(secrets.remove(pos).1),
+            None => Err(crate::Error::QuicSecretNotLogged(label.to_owned()).into()),
+        }
+    }
+}
+
+impl rustls::KeyLog for SecretsKeyLog {
+    fn log(&self, label: &str, client_random: &[u8], secret: &[u8]) {
+        self.secrets
+            .lock()
+            .unwrap()
+            .push((label.to_owned(), secret.to_vec()));
+        if self.user.will_log(label) {
+            self.user.log(label, client_random, secret);
+        }
+    }
+
+    fn will_log(&self, _label: &str) -> bool {
+        true
+    }
+}
+
+/// [`rustls::quic::Connection`] with handshake output converted to [`QuicEvent`]s.
+pub struct QuicConnection {
+    conn: rustls::quic::Connection,
+    secrets: Arc<SecretsKeyLog>,
+    /// Level of handshake data written by `rustls`.
+    write_level: QuicEncryptionLevel,
+    /// Handshake keys are installed, so the peer no longer sends data at initial level.
+    handshake_keys: bool,
+    events: VecDeque<QuicEvent>,
+    provider: CryptoProviderKind,
+}
+
+impl fmt::Debug for QuicConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuicConnection")
+            .field("write_level", &self.write_level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// QUIC version passed to `rustls`.
+pub(crate) const QUIC_VERSION: Version = Version::V1;
+
+impl QuicConnection {
+    /// Wrap the connection and collect the first flight (client hello).
+    pub(crate) fn new(
+        conn: rustls::quic::Connection,
+        secrets: Arc<SecretsKeyLog>,
+        provider: CryptoProviderKind,
+    ) -> anyhow::Result<QuicConnection> {
+        let mut conn = QuicConnection {
+            conn,
+            secrets,
+            write_level: QuicEncryptionLevel::Initial,
+            handshake_keys: false,
+            events: VecDeque::new(),
+            provider,
+        };
+        conn.collect_output()?;
+        Ok(conn)
+    }
+
+    /// Get the underlying `rustls` connection.
+    pub fn get_rustls_connection(&self) -> &rustls::quic::Connection {
+        &self.conn
+    }
+
+    fn is_client(&self) -> bool {
+        matches!(self.conn, rustls::quic::Connection::Client(_))
+    }
+
+    fn secrets(&self, level: QuicEncryptionLevel) -> anyhow::Result<QuicSecrets> {
+        let (client_label, server_label) = match level {
+            QuicEncryptionLevel::Handshake => (
+                "CLIENT_HANDSHAKE_TRAFFIC_SECRET",
+                "SERVER_HANDSHAKE_TRAFFIC_SECRET",
+            ),
+            QuicEncryptionLevel::OneRtt => ("CLIENT_TRAFFIC_SECRET_0", "SERVER_TRAFFIC_SECRET_0"),
+            QuicEncryptionLevel::Initial => return Err(crate::Error::QuicInitialSecrets.into()),
+        };
+        let client = self.secrets.take(client_label)?;
+        let server = self.secrets.take(server_label)?;
+        let cipher_suite = self
+            .conn
+            .negotiated_cipher_suite()
+            .map(|suite| u16::from(suite.suite()))
+            .unwrap_or_default();
+        let (local, remote) = if self.is_client() {
+            (client, server)
+        } else {
+            (server, client)
+        };
+        Ok(QuicSecrets {
+            level,
+            cipher_suite,
+            local,
+            remote,
+        })
+    }
+
+    /// Level at which the peer sends handshake data now.
+    ///
+    /// Server receives client `Finished` at handshake level after it installs 1-RTT keys,
+    /// so the level changes to 1-RTT only when the handshake is complete.
+    fn read_level(&self) -> QuicEncryptionLevel {
+        if !self.handshake_keys {
+            QuicEncryptionLevel::Initial
+        } else if self.conn.is_handshaking() {
+            QuicEncryptionLevel::Handshake
+        } else {
+            QuicEncryptionLevel::OneRtt
+        }
+    }
+
+    /// Convert handshake data and key changes written by `rustls` into events.
+    fn collect_output(&mut self) -> anyhow::Result<()> {
+        loop {
+            let mut data = Vec::new();
+            let key_change = self.conn.write_hs(&mut data);
+            if !data.is_empty() {
+                self.events.push_back(QuicEvent::CryptoData {
+                    level: self.write_level,
+                    data,
+                });
+            }
+            self.write_level = match key_change {
+                Some(KeyChange::Handshake { .. }) => {
+                    self.handshake_keys = true;
+                    QuicEncryptionLevel::Handshake
+                }
+                Some(KeyChange::OneRtt { .. }) => QuicEncryptionLevel::OneRtt,
+                None => return Ok(()),
+            };
+            let secrets = self.secrets(self.write_level)?;
+            self.events.push_back(QuicEvent::Secrets(secrets));
+        }
+    }
+}
+
+impl QuicConnectionDyn for QuicConnection {
+    fn feed_crypto_data(&mut self, level: QuicEncryptionLevel, data: &[u8]) -> anyhow::Result<()> {
+        let expected = self.read_level();
+        if level != expected {
+            return Err(crate::Error::QuicUnexpectedLevel(level, expected).into());
+        }
+        let r = self.conn.read_hs(data).map_err(anyhow::Error::new);
+        // Even on error, there might be data to send
+        self.collect_output()?;
+        r
+    }
+
+    fn next_event(&mut self) -> Option<QuicEvent> {
+        self.events.pop_front()
+    }
+
+    fn is_handshaking(&self) -> bool {
+        self.conn.is_handshaking()
+    }
+
+    fn peer_transport_parameters(&self) -> Option<Vec<u8>> {
+        self.conn.quic_transport_parameters().map(Vec::from)
+    }
+
+    fn alert(&self) -> Option<u8> {
+        self.conn.alert().map(u8::from)
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.conn.alpn_protocol().map(Vec::from))
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Ok(self
+            .conn
+            .export_keying_material(vec![0; len], label.as_bytes(), context)?)
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::info_for(self.provider)
+    }
+}
@@ -10,9 +10,10 @@ fn connect_bad_hostname() {
             .downcast_ref()
             .expect("rustls::TLSError");
         match err {
-            rustls::Error::InvalidCertificateData(e) => {
-                assert_eq!(e, "invalid peer certificate: CertNotValidForName");
-            }
+            rustls::Error::InvalidCertificate(rustls::CertificateError::NotValidForName)
+            | rustls::Error::InvalidCertificate(
+                rustls::CertificateError::NotValidForNameContext { .. },
+            ) => {}
             err => panic!("wrong error: {:?}", err),
         }
     });
@@ -37,7 +38,54 @@ fn client_server_pkcs12() {
     >();
 }
 
+#[test]
+fn truncation() {
+    tls_api_test::test_truncation::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>();
+}
+
 #[test]
 fn alpn() {
     tls_api_test::test_alpn::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>();
 }
+
+#[test]
+fn quic_connection() {
+    tls_api_test::test_quic_connection::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>(
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn ktls() {
+    tls_api_test::test_ktls::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>();
+}
+
+#[test]
+fn crypto_provider_info() {
+    let info = <tls_api_rustls::TlsConnector as tls_api::TlsConnector>::info();
+    assert_eq!(
+        Some(tls_api_rustls::CryptoProviderKind::default().name()),
+        info.crypto_provider
+    );
+    assert_eq!(cfg!(feature = "fips"), info.fips);
+}
+
+#[test]
+fn builder_with_crypto_provider() {
+    use tls_api::TlsConnectorBuilder;
+    use tls_api_rustls::CryptoProviderKind;
+
+    #[cfg(all(feature = "ring", not(feature = "fips")))]
+    {
+        let connector =
+            tls_api_rustls::TlsConnector::builder_with_crypto_provider(CryptoProviderKind::Ring)
+                .unwrap()
+                .build()
+                .unwrap();
+        assert_eq!(CryptoProviderKind::Ring, connector.crypto_provider());
+    }
+
+    let aws_lc_rs =
+        tls_api_rustls::TlsConnector::builder_with_crypto_provider(CryptoProviderKind::AwsLcRs);
+    assert_eq!(cfg!(feature = "aws-lc-rs"), aws_lc_rs.is_ok());
+}
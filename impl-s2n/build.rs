@@ -0,0 +1,3 @@
+fn main() {
+    tls_api_test::gen_tests_and_benches();
+}
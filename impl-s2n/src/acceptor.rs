@@ -0,0 +1,125 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use s2n_tls::connection::Connection;
+use tls_api::spi_acceptor_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+pub struct TlsAcceptorBuilder(pub s2n_tls::config::Builder);
+
+pub struct TlsAcceptor(pub s2n_tls::config::Config);
+
+impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
+    type Acceptor = TlsAcceptor;
+
+    type Underlying = s2n_tls::config::Builder;
+
+    fn underlying_mut(&mut self) -> &mut s2n_tls::config::Builder {
+        &mut self.0
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.0.set_application_protocol_preference(protocols)?;
+        Ok(())
+    }
+
+    fn add_client_auth_root_certificate(&mut self, _cert: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_ocsp_response(&mut self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
+    fn build(self) -> anyhow::Result<TlsAcceptor> {
+        Ok(TlsAcceptor(self.0.build()?))
+    }
+}
+
+impl TlsAcceptor {
+    async fn accept_impl<S>(&self, stream: S) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn()?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self) -> anyhow::Result<crate::TlsConnection> {
+        let mut conn = Connection::new_server();
+        conn.set_config(self.0.clone())?;
+        crate::TlsConnection::new(conn)
+    }
+}
+
+impl tls_api::TlsAcceptor for TlsAcceptor {
+    type Builder = TlsAcceptorBuilder;
+
+    type Underlying = s2n_tls::config::Config;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut Self::Underlying {
+        &mut self.0
+    }
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        der_keys: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        // TLS 1.3 connections only, see `TlsConnection::export_keying_material`
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::info()
+    }
+
+    fn set_ocsp_response(&self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn builder_from_der_key(cert: &[u8], key: &[u8]) -> anyhow::Result<TlsAcceptorBuilder> {
+        let mut builder = crate::config_builder()?;
+        builder.load_pem(&crate::cert_to_pem(cert), &crate::key_to_pem(key)?)?;
+        Ok(TlsAcceptorBuilder(builder))
+    }
+
+    fn builder_from_pkcs12(
+        _pkcs12: &[u8],
+        _passphrase: &str,
+    ) -> anyhow::Result<TlsAcceptorBuilder> {
+        Err(crate::Error::Pkcs12NotSupported.into())
+    }
+
+    fn accept_blocking(&self, _stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        self.new_conn().map(TlsConnection::new)
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
+}
@@ -0,0 +1,197 @@
+use std::io;
+use std::os::raw::c_int;
+use std::os::raw::c_void;
+use std::ptr;
+use std::slice;
+use std::task::Poll;
+
+use s2n_tls::connection::Connection;
+use s2n_tls::enums::Version;
+use tls_api::spi::TLS_EXPORTER_LABEL;
+use tls_api::spi::TLS_EXPORTER_LEN;
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
+use tls_api::ImplInfo;
+use tls_api::TlsConnectionDyn;
+use tls_api::TlsHandshakeState;
+
+/// Ciphertext buffers, read and written by `s2n` through the I/O callbacks.
+#[derive(Debug, Default)]
+struct MemIo {
+    /// Ciphertext received from the peer, not yet read by `s2n`.
+    incoming: Vec<u8>,
+    /// Ciphertext written by `s2n`, not yet taken.
+    outgoing: Vec<u8>,
+}
+
+unsafe extern "C" fn recv_callback(context: *mut c_void, buf: *mut u8, len: u32) -> c_int {
+    let io = &mut *(context as *mut MemIo);
+    if io.incoming.is_empty() {
+        // `s2n` reports the operation as pending
+        errno::set_errno(errno::Errno(libc::EAGAIN));
+        return -1;
+    }
+    let len = io.incoming.len().min(len as usize);
+    ptr::copy_nonoverlapping(io.incoming.as_ptr(), buf, len);
+    io.incoming.drain(..len);
+    len as c_int
+}
+
+unsafe extern "C" fn send_callback(context: *mut c_void, buf: *const u8, len: u32) -> c_int {
+    let io = &mut *(context as *mut MemIo);
+    io.outgoing
+        .extend_from_slice(slice::from_raw_parts(buf, len as usize));
+    len as c_int
+}
+
+fn poll_to_io<T>(poll: Poll<Result<T, s2n_tls::error::Error>>) -> io::Result<T> {
+    match poll {
+        Poll::Ready(r) => r.map_err(io::Error::from),
+        Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+    }
+}
+
+/// Sans-IO connection: s2n [`Connection`] over memory buffers.
+///
+/// Keying material (and `tls-exporter` channel binding) can be exported
+/// only when TLS 1.3 is negotiated.
+#[derive(Debug)]
+pub struct TlsConnection {
+    conn: Connection,
+    /// Boxed because `s2n` holds a pointer to it; dropped after `conn`.
+    io: Box<MemIo>,
+    handshaking: bool,
+}
+
+impl TlsConnection {
+    /// Wrap configured client or server `Connection`, and start the handshake.
+    pub(crate) fn new(mut conn: Connection) -> anyhow::Result<TlsConnection> {
+        let mut io = Box::<MemIo>::default();
+        let context = &mut *io as *mut MemIo as *mut c_void;
+        conn.set_receive_callback(Some(recv_callback))?;
+        conn.set_send_callback(Some(send_callback))?;
+        // SAFETY: `io` is heap allocated and outlives `conn`
+        unsafe {
+            conn.set_receive_context(context)?;
+            conn.set_send_context(context)?;
+        }
+        let mut conn = TlsConnection {
+            conn,
+            io,
+            handshaking: true,
+        };
+        // Client writes `ClientHello`
+        conn.continue_handshake()?;
+        Ok(conn)
+    }
+
+    /// Get the underlying [`Connection`].
+    pub fn get_connection_ref(&self) -> &Connection {
+        &self.conn
+    }
+
+    fn continue_handshake(&mut self) -> io::Result<()> {
+        match poll_to_io(self.conn.poll_negotiate()) {
+            Ok(_) => {
+                self.handshaking = false;
+                Ok(())
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl TlsConnectionDyn for TlsConnection {
+    fn handshake_state(&self) -> TlsHandshakeState {
+        if self.handshaking {
+            TlsHandshakeState::InProgress
+        } else {
+            TlsHandshakeState::Complete
+        }
+    }
+
+    fn feed_ciphertext(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.io.incoming.extend_from_slice(data);
+        if self.handshaking {
+            self.continue_handshake()?;
+        }
+        Ok(data.len())
+    }
+
+    fn wants_write(&self) -> bool {
+        !self.io.outgoing.is_empty()
+    }
+
+    fn take_ciphertext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let outgoing = &mut self.io.outgoing;
+        let len = outgoing.len().min(buf.len());
+        buf[..len].copy_from_slice(&outgoing[..len]);
+        outgoing.drain(..len);
+        Ok(len)
+    }
+
+    fn read_plaintext(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.handshaking {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        // Zero means `close_notify` received
+        poll_to_io(self.conn.poll_recv(buf))
+    }
+
+    fn write_plaintext(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.handshaking {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        poll_to_io(self.conn.poll_send(buf))
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        poll_to_io(self.conn.poll_shutdown_send())?;
+        Ok(())
+    }
+
+    fn get_alpn_protocol(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.conn.application_protocol().map(Vec::from))
+    }
+
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        Ok(self.conn.server_name().map(str::to_owned))
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &str,
+        context: Option<&[u8]>,
+        len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        // `s2n` implements only TLS 1.3 exporter,
+        // where absent context is the same as empty context
+        if self.conn.actual_protocol_version()? != Version::TLS13 {
+            return Err(crate::Error::ExportRequiresTls13.into());
+        }
+        let mut out = vec![0; len];
+        self.conn
+            .tls_exporter(label.as_bytes(), context.unwrap_or(&[]), &mut out)?;
+        Ok(out)
+    }
+
+    fn channel_binding(&self, kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        match kind {
+            ChannelBindingType::TlsServerEndPoint => {
+                Err(ChannelBindingError::NotSupported(kind, "s2n-tls").into())
+            }
+            ChannelBindingType::TlsExporter => {
+                self.export_keying_material(TLS_EXPORTER_LABEL, Some(&[]), TLS_EXPORTER_LEN)
+            }
+        }
+    }
+
+    fn impl_info(&self) -> ImplInfo {
+        crate::info()
+    }
+}
@@ -0,0 +1,157 @@
+use std::net::TcpStream;
+use std::sync::Arc;
+
+use s2n_tls::callbacks::VerifyHostNameCallback;
+use s2n_tls::connection::Connection;
+use tls_api::spi_connector_common;
+use tls_api::AsyncSocket;
+use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
+use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
+
+pub struct TlsConnectorBuilder {
+    pub builder: s2n_tls::config::Builder,
+    pub verify_hostname: bool,
+}
+
+pub struct TlsConnector {
+    pub config: s2n_tls::config::Config,
+    pub verify_hostname: bool,
+}
+
+/// Accept any certificate host name when hostname verification is disabled.
+struct AnyHostName;
+
+impl VerifyHostNameCallback for AnyHostName {
+    fn verify_host_name(&self, _host_name: &str) -> bool {
+        true
+    }
+}
+
+impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
+    type Connector = TlsConnector;
+
+    type Underlying = s2n_tls::config::Builder;
+
+    fn underlying_mut(&mut self) -> &mut s2n_tls::config::Builder {
+        &mut self.builder
+    }
+
+    fn set_alpn_protocols(&mut self, protocols: &[&[u8]]) -> anyhow::Result<()> {
+        self.builder
+            .set_application_protocol_preference(protocols)?;
+        Ok(())
+    }
+
+    fn set_verify_hostname(&mut self, verify: bool) -> anyhow::Result<()> {
+        self.verify_hostname = verify;
+        Ok(())
+    }
+
+    fn add_root_certificate(&mut self, cert: &[u8]) -> anyhow::Result<()> {
+        self.builder.trust_pem(&crate::cert_to_pem(cert))?;
+        Ok(())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_client_certificate(&mut self, _cert: &[u8], _key: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn set_ocsp_stapling(&mut self, _mode: OcspStapling) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
+    fn build(self) -> anyhow::Result<TlsConnector> {
+        Ok(TlsConnector {
+            config: self.builder.build()?,
+            verify_hostname: self.verify_hostname,
+        })
+    }
+}
+
+impl TlsConnector {
+    pub async fn connect_impl<S>(
+        &self,
+        domain: &str,
+        stream: S,
+    ) -> anyhow::Result<crate::TlsStream<S>>
+    where
+        S: AsyncSocket,
+    {
+        let conn = self.new_conn(domain)?;
+        crate::TlsStream::handshake(stream, conn).await
+    }
+
+    fn new_conn(&self, domain: &str) -> anyhow::Result<crate::TlsConnection> {
+        let mut conn = Connection::new_client();
+        conn.set_config(self.config.clone())?;
+        conn.set_server_name(domain)?;
+        if !self.verify_hostname {
+            conn.set_verify_host_callback(AnyHostName)?;
+        }
+        crate::TlsConnection::new(conn)
+    }
+}
+
+impl tls_api::TlsConnector for TlsConnector {
+    type Builder = TlsConnectorBuilder;
+
+    type Underlying = s2n_tls::config::Config;
+    type TlsStream = crate::TlsStream<AsyncSocketBox>;
+
+    fn underlying_mut(&mut self) -> &mut Self::Underlying {
+        &mut self.config
+    }
+
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: true,
+        alpn: true,
+        connection: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        // TLS 1.3 connections only, see `TlsConnection::export_keying_material`
+        keying_material_exporter: true,
+        ..Capabilities::NONE
+    };
+
+    fn info() -> ImplInfo {
+        crate::info()
+    }
+
+    fn builder() -> anyhow::Result<TlsConnectorBuilder> {
+        Ok(TlsConnectorBuilder {
+            builder: crate::config_builder()?,
+            verify_hostname: true,
+        })
+    }
+
+    fn connect_blocking(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self, domain: &str) -> anyhow::Result<TlsConnection> {
+        self.new_conn(domain).map(TlsConnection::new)
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
+}
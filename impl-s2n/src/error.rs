@@ -0,0 +1,19 @@
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum Error {
+    #[error("failed to parse private key: {}", _0)]
+    PrivateKey(&'static str),
+    #[error("s2n-tls does not support PKCS #12 keys")]
+    Pkcs12NotSupported,
+    #[error("s2n-tls does not support certificate revocation lists")]
+    CrlNotSupported,
+    #[error("client authentication is not implemented for s2n-tls")]
+    ClientAuthNotSupported,
+    #[error("OCSP stapling is not implemented for s2n-tls")]
+    OcspStaplingNotSupported,
+    #[error("s2n-tls does not support key logging")]
+    KeyLogNotSupported,
+    #[error("blocking API is not implemented for s2n-tls")]
+    BlockingNotSupported,
+    #[error("s2n-tls exports keying material only for TLS 1.3 connections")]
+    ExportRequiresTls13,
+}
@@ -0,0 +1,87 @@
+//! [`tls_api`] implementation for [`s2n_tls`].
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod acceptor;
+mod connection;
+mod connector;
+mod error;
+mod stream;
+
+pub use acceptor::TlsAcceptor;
+pub use acceptor::TlsAcceptorBuilder;
+pub use connector::TlsConnector;
+pub use connector::TlsConnectorBuilder;
+
+pub use connection::TlsConnection;
+pub(crate) use error::Error;
+pub(crate) use stream::TlsStream;
+
+use std::convert::TryFrom;
+
+use rustls_pki_types::PrivateKeyDer;
+use s2n_tls::config;
+use s2n_tls::security;
+use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
+
+/// Config builder with the policy used by both client and server.
+fn config_builder() -> anyhow::Result<config::Builder> {
+    let mut builder = config::Builder::new();
+    builder.set_security_policy(&security::DEFAULT_TLS13)?;
+    Ok(builder)
+}
+
+fn cert_to_pem(cert: &[u8]) -> Vec<u8> {
+    pem::encode(&pem::Pem {
+        tag: "CERTIFICATE".to_owned(),
+        contents: cert.to_owned(),
+    })
+    .into_bytes()
+}
+
+fn key_to_pem(key: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let tag = match PrivateKeyDer::try_from(key).map_err(crate::Error::PrivateKey)? {
+        PrivateKeyDer::Pkcs1(_) => "RSA PRIVATE KEY",
+        PrivateKeyDer::Sec1(_) => "EC PRIVATE KEY",
+        PrivateKeyDer::Pkcs8(_) => "PRIVATE KEY",
+        _ => return Err(crate::Error::PrivateKey("unknown private key format").into()),
+    };
+    Ok(pem::encode(&pem::Pem {
+        tag: tag.to_owned(),
+        contents: key.to_owned(),
+    })
+    .into_bytes())
+}
+
+/// Version requirement of `s2n-tls` in `Cargo.toml`, checked by a test.
+const S2N_TLS_VERSION: &str = "^0.3.0";
+
+pub(crate) fn info() -> ImplInfo {
+    ImplInfo {
+        name: "s2n",
+        version: S2N_TLS_VERSION,
+        crypto_provider: None,
+        fips: false,
+    }
+}
+
+/// Versions of `default_tls13` security policy.
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] = &[
+    ProtocolVersion::Tls10,
+    ProtocolVersion::Tls11,
+    ProtocolVersion::Tls12,
+    ProtocolVersion::Tls13,
+];
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn dep_version() {
+        tls_api_test::test_dep_version(
+            include_str!("../Cargo.toml"),
+            "s2n-tls",
+            super::S2N_TLS_VERSION,
+        );
+    }
+}
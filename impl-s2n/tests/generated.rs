@@ -0,0 +1,4 @@
+include!(concat!(env!("OUT_DIR"), "/tests_generated.rs"));
+
+#[test] // Tell Idea this file is a test
+fn dummy() {}
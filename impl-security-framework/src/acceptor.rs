@@ -1,4 +1,6 @@
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 use security_framework::certificate::SecCertificate;
@@ -10,7 +12,12 @@ use security_framework::import_export::Pkcs12ImportOptions;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 /// To be replaced with [`security_framework::secure_transport::ServerBuilder`]
 /// in the next version of the `security_framework`.
@@ -36,6 +43,26 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
         Err(crate::Error::AlpnOnServer.into())
     }
 
+    fn add_client_auth_root_certificate(&mut self, _cert: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_ocsp_response(&mut self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
     fn underlying_mut(&mut self) -> &mut Self::Underlying {
         &mut self.0
     }
@@ -100,15 +127,21 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
         &mut self.0
     }
 
-    const IMPLEMENTED: bool = crate::IMPLEMENTED;
-    const SUPPORTS_ALPN: bool = false;
-    const SUPPORTS_DER_KEYS: bool = false;
-    const SUPPORTS_PKCS12_KEYS: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: crate::IMPLEMENTED,
+        pkcs12_keys: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
     }
 
+    fn set_ocsp_response(&self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
     fn builder_from_pkcs12(pkcs12: &[u8], passphrase: &str) -> anyhow::Result<TlsAcceptorBuilder> {
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         {
@@ -125,5 +158,13 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
         }
     }
 
-    spi_acceptor_common!();
+    fn accept_blocking(&self, _stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        Err(crate::Error::ConnectionNotSupported.into())
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
 }
@@ -3,13 +3,21 @@ use security_framework::certificate::SecCertificate;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 use security_framework::secure_transport::ClientBuilder;
 
+use std::net::TcpStream;
 use std::str;
+use std::sync::Arc;
 
 use std::future::Future;
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 #[cfg(not(any(target_os = "macos", target_os = "ios")))]
 type ClientBuilder = void::Void;
@@ -73,6 +81,26 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
         }
     }
 
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(crate::Error::CrlNotSupported.into())
+    }
+
+    fn set_client_certificate(&mut self, _cert: &[u8], _key: &[u8]) -> anyhow::Result<()> {
+        Err(crate::Error::ClientAuthNotSupported.into())
+    }
+
+    fn set_ocsp_stapling(&mut self, _mode: OcspStapling) -> anyhow::Result<()> {
+        Err(crate::Error::OcspStaplingNotSupported.into())
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(crate::Error::KeyLogNotSupported.into())
+    }
+
     fn build(self) -> anyhow::Result<TlsConnector> {
         Ok(TlsConnector(self.0))
     }
@@ -109,8 +137,12 @@ impl tls_api::TlsConnector for TlsConnector {
         &mut self.0
     }
 
-    const IMPLEMENTED: bool = crate::IMPLEMENTED;
-    const SUPPORTS_ALPN: bool = true;
+    const CAPABILITIES: Capabilities = Capabilities {
+        implemented: crate::IMPLEMENTED,
+        alpn: true,
+        protocol_versions: crate::PROTOCOL_VERSIONS,
+        ..Capabilities::NONE
+    };
 
     fn info() -> ImplInfo {
         crate::info()
@@ -127,5 +159,17 @@ impl tls_api::TlsConnector for TlsConnector {
         }
     }
 
-    spi_connector_common!();
+    fn connect_blocking(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        Err(crate::Error::BlockingNotSupported.into())
+    }
+
+    fn new_connection(&self, _domain: &str) -> anyhow::Result<TlsConnection> {
+        Err(crate::Error::ConnectionNotSupported.into())
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
 }
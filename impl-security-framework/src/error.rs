@@ -17,4 +17,16 @@ pub(crate) enum Error {
     TooManyAlpnProtocols(Vec<String>),
     #[error("returned ALPN protocol is not UTF-8: {}", _0)]
     ReturnedAlpnProtocolIsNotUtf8(Utf8Error),
+    #[error("security-framework does not support certificate revocation lists")]
+    CrlNotSupported,
+    #[error("client authentication is not implemented for security-framework")]
+    ClientAuthNotSupported,
+    #[error("security-framework does not support OCSP stapling")]
+    OcspStaplingNotSupported,
+    #[error("security-framework does not support key logging")]
+    KeyLogNotSupported,
+    #[error("blocking API is not implemented for security-framework")]
+    BlockingNotSupported,
+    #[error("sans-IO connection is not implemented for security-framework")]
+    ConnectionNotSupported,
 }
@@ -17,7 +17,6 @@ use std::task::Poll;
 
 use tls_api::async_as_sync::AsyncIoAsSyncIo;
 use tls_api::AsyncSocket;
-use tls_api::BoxFuture;
 
 use crate::TlsAcceptor;
 use tls_api::spi::save_context;
@@ -93,21 +92,18 @@ enum ServerHandshakeFuture<F, S: Unpin> {
     Done,
 }
 
-pub(crate) fn new_server_handshake<'a, S>(
-    acceptor: &'a TlsAcceptor,
+pub(crate) async fn new_server_handshake<S>(
+    acceptor: &TlsAcceptor,
     stream: S,
-) -> impl Future<Output = anyhow::Result<crate::TlsStream<S>>> + 'a
+) -> anyhow::Result<crate::TlsStream<S>>
 where
     S: AsyncSocket,
 {
-    BoxFuture::new(async move {
-        let mut ctx = SslContext::new(SslProtocolSide::SERVER, SslConnectionType::STREAM)
-            .map_err(anyhow::Error::new)?;
-        ctx.set_certificate(&acceptor.0.identity, &acceptor.0.certs)
-            .map_err(anyhow::Error::new)?;
-        ServerHandshakeFuture::Initial(move |s| ctx.handshake(s), AsyncIoAsSyncIo::new(stream))
-            .await
-    })
+    let mut ctx = SslContext::new(SslProtocolSide::SERVER, SslConnectionType::STREAM)
+        .map_err(anyhow::Error::new)?;
+    ctx.set_certificate(&acceptor.0.identity, &acceptor.0.certs)
+        .map_err(anyhow::Error::new)?;
+    ServerHandshakeFuture::Initial(move |s| ctx.handshake(s), AsyncIoAsSyncIo::new(stream)).await
 }
 
 impl<F, S> Future for ServerHandshakeFuture<F, S>
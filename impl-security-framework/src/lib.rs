@@ -15,6 +15,7 @@ mod error;
 mod handshake;
 
 use tls_api::ImplInfo;
+use tls_api::ProtocolVersion;
 
 pub use acceptor::SecureTransportTlsAcceptorBuilder;
 pub use acceptor::TlsAcceptor;
@@ -34,19 +35,24 @@ pub(crate) fn not_ios_or_macos<T>() -> anyhow::Result<T> {
     Err(Error::NotIosOrMacos.into())
 }
 
+/// Version requirement of `security-framework` in `Cargo.toml`, checked by a test.
+const SECURITY_FRAMEWORK_VERSION: &str = "^2.9.2";
+
 pub(crate) fn info() -> ImplInfo {
     ImplInfo {
         name: "security-framework",
         version: {
             #[cfg(any(target_os = "macos", target_os = "ios"))]
             {
-                "unknown"
+                SECURITY_FRAMEWORK_VERSION
             }
             #[cfg(not(any(target_os = "macos", target_os = "ios")))]
             {
                 "not iOS or macOS"
             }
         },
+        crypto_provider: None,
+        fips: false,
     }
 }
 
@@ -54,3 +60,22 @@ pub(crate) fn info() -> ImplInfo {
 pub(crate) const IMPLEMENTED: bool = true;
 #[cfg(not(any(target_os = "macos", target_os = "ios")))]
 pub(crate) const IMPLEMENTED: bool = false;
+
+/// Secure Transport does not implement TLS 1.3.
+pub(crate) const PROTOCOL_VERSIONS: &[ProtocolVersion] = &[
+    ProtocolVersion::Tls10,
+    ProtocolVersion::Tls11,
+    ProtocolVersion::Tls12,
+];
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn dep_version() {
+        tls_api_test::test_dep_version(
+            include_str!("../Cargo.toml"),
+            "security-framework",
+            super::SECURITY_FRAMEWORK_VERSION,
+        );
+    }
+}
@@ -1,6 +1,7 @@
 #![cfg(any(target_os = "macos", target_os = "ios"))]
 
 use std::fmt;
+use std::io;
 use std::marker::PhantomData;
 
 use security_framework::secure_transport::SslContext;
@@ -12,6 +13,8 @@ use tls_api::async_as_sync::TlsStreamOverSyncIo;
 use tls_api::spi_async_socket_impl_delegate;
 use tls_api::spi_tls_stream_over_sync_io_wrapper;
 use tls_api::AsyncSocket;
+use tls_api::ChannelBindingError;
+use tls_api::ChannelBindingType;
 use tls_api::ImplInfo;
 
 spi_tls_stream_over_sync_io_wrapper!(TlsStream, SslStream);
@@ -60,4 +63,36 @@ where
             Err(crate::Error::TooManyAlpnProtocols(protocols).into())
         }
     }
+
+    fn server_name(w: &Self::SyncWrapper) -> anyhow::Result<Option<String>> {
+        // Only the client side can configure the name,
+        // server does not see the name requested by the client.
+        let name = w.context().peer_domain_name().map_err(anyhow::Error::new)?;
+        Ok(Some(name).filter(|name| !name.is_empty()))
+    }
+
+    fn ocsp_response(_w: &Self::SyncWrapper) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn export_keying_material(
+        _w: &Self::SyncWrapper,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::ExportNotSupported("security-framework").into())
+    }
+
+    fn tls_server_end_point(_w: &Self::SyncWrapper) -> anyhow::Result<Vec<u8>> {
+        Err(ChannelBindingError::NotSupported(
+            ChannelBindingType::TlsServerEndPoint,
+            "security-framework",
+        )
+        .into())
+    }
+
+    fn shutdown(w: &mut Self::SyncWrapper) -> io::Result<()> {
+        w.close()
+    }
 }
@@ -3,10 +3,17 @@ use void::Void;
 use tls_api::spi_acceptor_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 use crate::Error;
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 /// Non-instantiatable.
 pub struct TlsAcceptorBuilder(Void);
@@ -22,6 +29,26 @@ impl tls_api::TlsAcceptorBuilder for TlsAcceptorBuilder {
         Err(anyhow::Error::new(Error))
     }
 
+    fn add_client_auth_root_certificate(&mut self, _cert: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_ocsp_response(&mut self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
     fn underlying_mut(&mut self) -> &mut Void {
         &mut self.0
     }
@@ -46,10 +73,7 @@ impl TlsAcceptor {
 impl tls_api::TlsAcceptor for TlsAcceptor {
     type Builder = TlsAcceptorBuilder;
 
-    const IMPLEMENTED: bool = false;
-    const SUPPORTS_ALPN: bool = false;
-    const SUPPORTS_DER_KEYS: bool = false;
-    const SUPPORTS_PKCS12_KEYS: bool = false;
+    const CAPABILITIES: Capabilities = Capabilities::NONE;
 
     type Underlying = Void;
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
@@ -62,5 +86,17 @@ impl tls_api::TlsAcceptor for TlsAcceptor {
         crate::info()
     }
 
-    spi_acceptor_common!();
+    fn set_ocsp_response(&self, _ocsp_response: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn accept_blocking(&self, _stream: TcpStream) -> anyhow::Result<TlsStreamBlocking> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn new_connection(&self) -> anyhow::Result<TlsConnection> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    spi_acceptor_common!(crate::TlsStream<S>);
 }
@@ -1,12 +1,20 @@
 use tls_api::spi_connector_common;
 use tls_api::AsyncSocket;
 use tls_api::AsyncSocketBox;
+use tls_api::Capabilities;
 use tls_api::ImplInfo;
+use tls_api::KeyLog;
+use tls_api::OcspStapling;
+use tls_api::RevocationPolicy;
+use tls_api::TlsConnection;
+use tls_api::TlsStreamBlocking;
 
 use void::Void;
 
 use crate::Error;
 use std::future::Future;
+use std::net::TcpStream;
+use std::sync::Arc;
 
 /// Non-instantiatable.
 pub struct TlsConnectorBuilder(Void);
@@ -34,6 +42,26 @@ impl tls_api::TlsConnectorBuilder for TlsConnectorBuilder {
         Err(anyhow::Error::new(Error))
     }
 
+    fn add_crl_der(&mut self, _crl: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_revocation_policy(&mut self, _policy: RevocationPolicy) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_client_certificate(&mut self, _cert: &[u8], _key: &[u8]) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_ocsp_stapling(&mut self, _mode: OcspStapling) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn set_key_log(&mut self, _key_log: Arc<dyn KeyLog>) -> anyhow::Result<()> {
+        Err(anyhow::Error::new(Error))
+    }
+
     fn build(self) -> anyhow::Result<TlsConnector> {
         Err(anyhow::Error::new(Error))
     }
@@ -55,8 +83,7 @@ impl TlsConnector {
 impl tls_api::TlsConnector for TlsConnector {
     type Builder = TlsConnectorBuilder;
 
-    const IMPLEMENTED: bool = false;
-    const SUPPORTS_ALPN: bool = false;
+    const CAPABILITIES: Capabilities = Capabilities::NONE;
 
     type Underlying = Void;
     type TlsStream = crate::TlsStream<AsyncSocketBox>;
@@ -73,5 +100,17 @@ impl tls_api::TlsConnector for TlsConnector {
         Err(anyhow::Error::new(Error))
     }
 
-    spi_connector_common!();
+    fn connect_blocking(
+        &self,
+        _domain: &str,
+        _stream: TcpStream,
+    ) -> anyhow::Result<TlsStreamBlocking> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    fn new_connection(&self, _domain: &str) -> anyhow::Result<TlsConnection> {
+        Err(anyhow::Error::new(Error))
+    }
+
+    spi_connector_common!(crate::TlsStream<S>);
 }
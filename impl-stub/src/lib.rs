@@ -33,5 +33,7 @@ pub(crate) fn info() -> ImplInfo {
     ImplInfo {
         name: "stub",
         version: "none",
+        crypto_provider: None,
+        fips: false,
     }
 }
@@ -6,6 +6,7 @@ use std::pin::Pin;
 use tls_api::spi::TlsStreamWithUpcastDyn;
 use tls_api::spi_async_socket_impl_delegate;
 use tls_api::AsyncSocket;
+use tls_api::ChannelBindingType;
 use tls_api::TlsStreamDyn;
 use tls_api::TlsStreamWithSocketDyn;
 use void::Void;
@@ -19,7 +20,7 @@ impl<S: AsyncSocket> TlsStream<S> {
         void::unreachable(self.get_mut().0)
     }
 
-    fn deref_for_impl_socket(&self) -> Pin<&mut dyn AsyncSocket> {
+    fn deref_for_impl_socket(&self) -> &dyn AsyncSocket {
         void::unreachable(self.0)
     }
 }
@@ -29,6 +30,27 @@ impl<S: AsyncSocket> TlsStreamDyn for TlsStream<S> {
         void::unreachable(self.0)
     }
 
+    fn server_name(&self) -> anyhow::Result<Option<String>> {
+        void::unreachable(self.0)
+    }
+
+    fn ocsp_response(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        void::unreachable(self.0)
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &str,
+        _context: Option<&[u8]>,
+        _len: usize,
+    ) -> anyhow::Result<Vec<u8>> {
+        void::unreachable(self.0)
+    }
+
+    fn channel_binding(&self, _kind: ChannelBindingType) -> anyhow::Result<Vec<u8>> {
+        void::unreachable(self.0)
+    }
+
     fn impl_info(&self) -> ImplInfo {
         void::unreachable(self.0)
     }
@@ -6,11 +6,20 @@ use std::fs;
 fn main() {
     let out_dir = env::var("OUT_DIR").unwrap();
 
-    let impls = &["native_tls", "rustls", "openssl", "security_framework"];
+    // BoringSSL exports the same symbols as OpenSSL, so boring cannot be linked
+    // into one binary with openssl (or with native-tls, which uses openssl on Linux)
+    let impls: &[&str] = match env::var_os("CARGO_FEATURE_BORING") {
+        Some(_) => &["boring", "rustls", "security_framework"],
+        None => &["native_tls", "rustls", "openssl", "security_framework"],
+    };
+
+    // s2n only implements the basics, so it joins only client-server and ALPN tests;
+    // unlike BoringSSL, AWS-LC used by s2n prefixes its symbols
+    let basic_impls: Vec<&str> = impls.iter().copied().chain(["s2n"]).collect();
 
     let mut client_server = String::new();
-    for client in impls {
-        for server in impls {
+    for client in &basic_impls {
+        for server in &basic_impls {
             writeln!(client_server, "#[test]").unwrap();
             writeln!(client_server, "fn {}_{}_der() {{", client, server).unwrap();
             writeln!(client_server, "    tls_api_test::test_client_server_der::<").unwrap();
@@ -31,8 +40,8 @@ fn main() {
     }
 
     let mut alpn = String::new();
-    for client in impls {
-        for server in impls {
+    for client in &basic_impls {
+        for server in &basic_impls {
             writeln!(alpn, "#[test]").unwrap();
             writeln!(alpn, "fn {}_{}() {{", client, server).unwrap();
             writeln!(
@@ -45,6 +54,95 @@ fn main() {
         }
     }
 
+    let mut ocsp = String::new();
+    for client in impls {
+        for server in impls {
+            writeln!(ocsp, "#[test]").unwrap();
+            writeln!(ocsp, "fn {}_{}_stapling() {{", client, server).unwrap();
+            writeln!(
+                ocsp,
+                "  tls_api_test::test_ocsp_stapling::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(ocsp, "}}").unwrap();
+            writeln!(ocsp, "#[test]").unwrap();
+            writeln!(ocsp, "fn {}_{}_must_staple() {{", client, server).unwrap();
+            writeln!(
+                ocsp,
+                "  tls_api_test::test_ocsp_must_staple::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(ocsp, "}}").unwrap();
+        }
+    }
+
+    let mut channel_binding = String::new();
+    for client in impls {
+        for server in impls {
+            writeln!(channel_binding, "#[test]").unwrap();
+            writeln!(channel_binding, "fn {}_{}() {{", client, server).unwrap();
+            writeln!(
+                channel_binding,
+                "  tls_api_test::test_channel_binding::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(channel_binding, "}}").unwrap();
+        }
+    }
+
+    let mut key_log = String::new();
+    for client in impls {
+        for server in impls {
+            writeln!(key_log, "#[test]").unwrap();
+            writeln!(key_log, "fn {}_{}() {{", client, server).unwrap();
+            writeln!(
+                key_log,
+                "  tls_api_test::test_key_log::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(key_log, "}}").unwrap();
+        }
+    }
+
+    let mut server_name = String::new();
+    for client in impls {
+        for server in impls {
+            writeln!(server_name, "#[test]").unwrap();
+            writeln!(server_name, "fn {}_{}() {{", client, server).unwrap();
+            writeln!(
+                server_name,
+                "  tls_api_test::test_server_name::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(server_name, "}}").unwrap();
+        }
+    }
+
+    let mut blocking = String::new();
+    for client in impls {
+        for server in impls {
+            writeln!(blocking, "#[test]").unwrap();
+            writeln!(blocking, "fn {}_{}() {{", client, server).unwrap();
+            writeln!(
+                blocking,
+                "  tls_api_test::test_blocking::<tls_api_{}::TlsConnector, tls_api_{}::TlsAcceptor>();",
+                client, server
+            )
+            .unwrap();
+            writeln!(blocking, "}}").unwrap();
+        }
+    }
+
     fs::write(format!("{}/client_server.rs", out_dir), &client_server).unwrap();
     fs::write(format!("{}/alpn.rs", out_dir), &alpn).unwrap();
+    fs::write(format!("{}/ocsp.rs", out_dir), &ocsp).unwrap();
+    fs::write(format!("{}/channel_binding.rs", out_dir), &channel_binding).unwrap();
+    fs::write(format!("{}/key_log.rs", out_dir), &key_log).unwrap();
+    fs::write(format!("{}/server_name.rs", out_dir), &server_name).unwrap();
+    fs::write(format!("{}/blocking.rs", out_dir), &blocking).unwrap();
 }
@@ -0,0 +1,8 @@
+//! Invoke `tls_api_test::blocking` with various implementations
+
+// Dummy test to help Idea regognise this file is a test
+#[test]
+fn dummy() {}
+
+// All permutations.
+include!(concat!(env!("OUT_DIR"), "/blocking.rs"));
@@ -0,0 +1,8 @@
+//! Invoke `tls_api_test::channel_binding` with various implementations
+
+// Dummy test to help Idea regognise this file is a test
+#[test]
+fn dummy() {}
+
+// All permutations.
+include!(concat!(env!("OUT_DIR"), "/channel_binding.rs"));
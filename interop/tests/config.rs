@@ -0,0 +1,169 @@
+//! Build connectors and acceptors from `TlsConnectorConfig` and `TlsAcceptorConfig`
+
+// openssl cannot be linked together with boring
+#![cfg(not(feature = "boring"))]
+
+use std::fs;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::path::Path;
+use std::thread;
+
+use tls_api::TlsAcceptor;
+use tls_api::TlsAcceptorConfig;
+use tls_api::TlsConnector;
+use tls_api::TlsConnectorConfig;
+use tls_api::TlsStreamBlockingDyn;
+
+/// Write test keys to the directory, certificates as PEM, keys as DER.
+fn write_keys(dir: &Path) {
+    let keys = test_cert_gen::keys();
+    let write = |name: &str, data: &[u8]| fs::write(dir.join(name), data).unwrap();
+    write("ca.pem", keys.client.ca.to_pem().as_bytes());
+    write(
+        "server.pem",
+        keys.server.cert_and_key.cert.to_pem().as_bytes(),
+    );
+    write("server.key", keys.server.cert_and_key.key.get_der());
+    write(
+        "client.pem",
+        keys.client.cert_and_key.cert.to_pem().as_bytes(),
+    );
+    write("client.key", keys.client.cert_and_key.key.get_der());
+}
+
+fn connector_config(toml: &str, dir: &Path) -> TlsConnectorConfig {
+    let toml = toml.replace("$DIR", dir.to_str().unwrap());
+    toml::from_str(&toml).unwrap()
+}
+
+fn acceptor_config(toml: &str, dir: &Path) -> TlsAcceptorConfig {
+    let toml = toml.replace("$DIR", dir.to_str().unwrap());
+    toml::from_str(&toml).unwrap()
+}
+
+#[test]
+fn client_server_rustls() {
+    let dir = tempfile::tempdir().unwrap();
+    write_keys(dir.path());
+
+    let acceptor = acceptor_config(
+        r#"
+        certificate = "$DIR/server.pem"
+        key = "$DIR/server.key"
+        alpn_protocols = ["h2"]
+        client_auth_root_certificates = ["$DIR/ca.pem"]
+        "#,
+        dir.path(),
+    )
+    .build(tls_api_rustls::TlsAcceptor::TYPE_DYN)
+    .unwrap();
+    let connector = connector_config(
+        r#"
+        alpn_protocols = ["h2", "http/1.1"]
+        root_certificates = ["$DIR/ca.pem"]
+        client_certificate = { certificate = "$DIR/client.pem", key = "$DIR/client.key" }
+        "#,
+        dir.path(),
+    )
+    .build(tls_api_rustls::TlsConnector::TYPE_DYN)
+    .unwrap();
+
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = thread::spawn(move || {
+        let socket = listener.accept().unwrap().0;
+        let mut socket = acceptor.accept_blocking(socket).unwrap();
+        let mut buf = [0; 5];
+        socket.read_exact(&mut buf).unwrap();
+        assert_eq!(b"hello", &buf);
+        socket.write_all(b"world").unwrap();
+    });
+
+    let socket = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    let mut socket = connector.connect_blocking("localhost", socket).unwrap();
+    assert_eq!(
+        Some(&b"h2"[..]),
+        socket.get_alpn_protocol().unwrap().as_deref()
+    );
+    socket.write_all(b"hello").unwrap();
+    let mut buf = [0; 5];
+    socket.read_exact(&mut buf).unwrap();
+    assert_eq!(b"world", &buf);
+
+    server.join().unwrap();
+}
+
+#[test]
+fn unsupported() {
+    let dir = tempfile::tempdir().unwrap();
+    write_keys(dir.path());
+
+    let config = connector_config(
+        r#"
+        client_certificate = { certificate = "$DIR/client.pem", key = "$DIR/client.key" }
+        "#,
+        dir.path(),
+    );
+    let err = config
+        .build(tls_api_native_tls::TlsConnector::TYPE_DYN)
+        .err()
+        .unwrap();
+    assert_eq!(
+        "config field `client_certificate`: native-tls does not support client authentication",
+        err.to_string()
+    );
+    // Supported by another implementation
+    config
+        .validate(tls_api_openssl::TlsConnector::TYPE_DYN)
+        .unwrap();
+}
+
+#[test]
+fn missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    write_keys(dir.path());
+
+    let err = connector_config(
+        r#"root_certificates = ["$DIR/ca.pem", "$DIR/missing.pem"]"#,
+        dir.path(),
+    )
+    .build(tls_api_rustls::TlsConnector::TYPE_DYN)
+    .err()
+    .unwrap();
+    assert_eq!("config field `root_certificates[1]`", err.to_string());
+    let chain = format!("{:#}", err);
+    assert!(chain.contains("missing.pem"), "{}", chain);
+}
+
+#[test]
+fn server_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let build = |toml: &str| {
+        acceptor_config(toml, dir.path())
+            .build(tls_api_rustls::TlsAcceptor::TYPE_DYN)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        "config requires server key: `certificate` and `key`, or `pkcs12`",
+        build(r#"alpn_protocols = ["h2"]"#)
+    );
+    assert_eq!(
+        "config field `key` is required with `certificate`",
+        build(r#"certificate = "server.pem""#)
+    );
+    assert_eq!(
+        "config fields `pkcs12` and `certificate` cannot be used together",
+        build(
+            r#"
+            certificate = "server.pem"
+            key = "server.key"
+            pkcs12 = "server.p12"
+            "#
+        )
+    );
+}
@@ -0,0 +1,8 @@
+//! Invoke `tls_api_test::key_log` with various implementations
+
+// Dummy test to help Idea regognise this file is a test
+#[test]
+fn dummy() {}
+
+// All permutations.
+include!(concat!(env!("OUT_DIR"), "/key_log.rs"));
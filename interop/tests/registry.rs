@@ -0,0 +1,78 @@
+//! Select implementations with `TlsImplRegistry`
+
+// openssl cannot be linked together with boring
+#![cfg(not(feature = "boring"))]
+
+use tls_api::TlsImplRegistry;
+
+fn registry() -> TlsImplRegistry {
+    let mut registry = TlsImplRegistry::new();
+    registry.register::<tls_api_security_framework::TlsConnector, tls_api_security_framework::TlsAcceptor>();
+    registry.register::<tls_api_rustls::TlsConnector, tls_api_rustls::TlsAcceptor>();
+    registry.register::<tls_api_openssl::TlsConnector, tls_api_openssl::TlsAcceptor>();
+    registry.register::<tls_api_native_tls::TlsConnector, tls_api_native_tls::TlsAcceptor>();
+    registry
+}
+
+#[test]
+fn get() {
+    let registry = registry();
+    assert_eq!("rustls", registry.get("rustls").unwrap().name());
+    assert_eq!("native-tls", registry.get("Native_TLS").unwrap().name());
+
+    let err = registry.get("gnutls").unwrap_err().to_string();
+    assert!(err.contains("`gnutls`"), "{}", err);
+    assert!(err.contains("rustls, openssl, native-tls"), "{}", err);
+}
+
+#[test]
+fn not_implemented() {
+    let registry = registry();
+    let security_framework = registry.all()[0];
+    assert_eq!("security-framework", security_framework.name());
+    if security_framework.implemented() {
+        return;
+    }
+    let err = registry.get("security-framework").unwrap_err().to_string();
+    assert!(err.contains("not available"), "{}", err);
+    assert!(registry
+        .to_string()
+        .contains("security-framework: not implemented"));
+    // First available
+    assert_eq!("rustls", registry.default_impl().unwrap().name());
+}
+
+#[test]
+fn get_from_env_var() {
+    let registry = registry();
+    let var = "TLS_API_IMPL_INTEROP_TEST";
+    std::env::set_var(var, "openssl");
+    assert_eq!("openssl", registry.get_from_env_var(var).unwrap().name());
+    std::env::set_var(var, "");
+    assert_eq!(
+        registry.default_impl().unwrap().name(),
+        registry.get_from_env_var(var).unwrap().name()
+    );
+    std::env::remove_var(var);
+}
+
+#[test]
+fn display() {
+    let registry = registry();
+    let rustls = registry.get("rustls").unwrap().to_string();
+    assert!(rustls.starts_with("rustls="), "{}", rustls);
+    assert!(
+        rustls.contains("connector: TLSv1.2, TLSv1.3, alpn"),
+        "{}",
+        rustls
+    );
+}
+
+#[test]
+fn empty() {
+    let err = TlsImplRegistry::new().default_impl().unwrap_err();
+    assert_eq!(
+        "no TLS implementation is available on this platform",
+        err.to_string()
+    );
+}
@@ -142,6 +142,48 @@ pub fn pem_to_cert_key_pair(pem: &[u8]) -> (Cert, PrivateKey) {
     (cert, key)
 }
 
+/// DER-encoded X.509 certificate revocation list.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Crl(Vec<u8>);
+
+impl Crl {
+    /// Construct from DER-encoded.
+    pub fn from_der(crl_der: impl Into<Vec<u8>>) -> Crl {
+        let crl_der = crl_der.into();
+        // `CertificateList` is a `SEQUENCE` too
+        if !crl_der.starts_with(b"\x30") {
+            panic!("not a DER-encoded CRL");
+        }
+        Crl(crl_der)
+    }
+
+    /// Construct from PEM-DER-encoded.
+    pub fn from_pem(crl_pem: impl AsRef<[u8]>) -> Crl {
+        let mut crls: Vec<Crl> = pem::parse_many(crl_pem.as_ref())
+            .into_iter()
+            .filter(|p| p.tag == "X509 CRL")
+            .map(|p| Self::from_der(p.contents))
+            .collect();
+        if crls.len() != 1 {
+            panic!("PEM file should contain a single CRL, got {}", crls.len());
+        }
+        crls.swap_remove(0)
+    }
+
+    /// Get CRL as DER.
+    pub fn get_der(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Convert a CRL to PEM format.
+    pub fn to_pem(&self) -> String {
+        pem::encode(&pem::Pem {
+            tag: "X509 CRL".to_owned(),
+            contents: self.0.clone(),
+        })
+    }
+}
+
 /// DER-encoded
 pub struct Pkcs12(pub Vec<u8>);
 
@@ -12,6 +12,7 @@ use tempfile::Builder as TempBuilder;
 
 pub use cert::pem_to_cert_key_pair;
 pub use cert::Cert;
+pub use cert::Crl;
 pub use cert::Pkcs12;
 pub use cert::Pkcs12AndPassword;
 pub use cert::PrivateKey;
@@ -35,6 +36,8 @@ impl CertAndPrivateKey {
 /// Client certificate
 pub struct ClientKeys {
     pub ca: Cert,
+    /// Client certificate signed by the same CA, used for client authentication.
+    pub cert_and_key: CertAndPrivateKey,
 }
 
 /// Server keys
@@ -44,6 +47,22 @@ pub struct ServerKeys {
 
     /// Server certificate
     pub cert_and_key: CertAndPrivateKey,
+
+    /// DER-encoded OCSP response with "good" status of the server certificate.
+    pub ocsp_response: Vec<u8>,
+    /// Another valid OCSP response, to test replacing the stapled response.
+    pub ocsp_response_next: Vec<u8>,
+}
+
+/// Certificate revocation lists
+pub struct Crls {
+    /// CRL issued by the CA, with no revoked certificates.
+    pub empty: Crl,
+    /// CRL issued by the CA, revoking both server and client certificates.
+    pub revoked: Crl,
+    /// Empty CRL issued by unrelated CA, so revocation status
+    /// of the certificates cannot be determined with it.
+    pub foreign: Crl,
 }
 
 /// Client and server keys
@@ -52,9 +71,17 @@ pub struct Keys {
     pub client: ClientKeys,
     /// Server keys
     pub server: ServerKeys,
+    /// Revocation lists
+    pub crls: Crls,
 }
 
-fn gen_root_ca() -> CertAndPrivateKey {
+fn gen_root_ca(name: &str) -> CertAndPrivateKey {
+    gen_self_signed(name, &[])
+}
+
+/// Generate self-signed CA certificate, `req_args` are passed to `openssl req`
+/// (e. g. `-sha512` to sign with SHA-512).
+pub fn gen_self_signed(name: &str, req_args: &[&str]) -> CertAndPrivateKey {
     let temp_dir = TempBuilder::new()
         .prefix("rust-test-cert-gen-gen-root-ca")
         .tempdir()
@@ -66,22 +93,25 @@ fn gen_root_ca() -> CertAndPrivateKey {
 
     fs::write(
         &config,
-        b"\
+        format!(
+            "\
                 [req]\n\
                 distinguished_name=dn\n\
                 [dn]\n\
-                CN=my.ca\n\
+                CN={0}\n\
                 [ext]\n\
                 basicConstraints=CA:TRUE,pathlen:0\n\
                 subjectAltName = @alt_names\n\
                 extendedKeyUsage=serverAuth,clientAuth\n\
                 [alt_names]\n\
-                DNS.1 = my.ca\n\
+                DNS.1 = {0}\n\
             ",
+            name
+        ),
     )
     .unwrap();
 
-    let subj = "/C=US/ST=Denial/L=Sprintfield/O=Dis/CN=my.ca";
+    let subj = format!("/C=US/ST=Denial/L=Sprintfield/O=Dis/CN={}", name);
     // Making root CA
     let gen_ca = Command::new("openssl")
         .arg("req")
@@ -92,12 +122,13 @@ fn gen_root_ca() -> CertAndPrivateKey {
         .arg(&config)
         .args(["-extensions", "ext"])
         .arg("-subj")
-        .arg(subj)
+        .arg(&subj)
         .arg("-keyout")
         .arg(&keyfile)
         .arg("-out")
         .arg(&certfile)
         .args(["-days", "1"])
+        .args(req_args)
         // TODO: print on error
         // .stderr(Stdio::inherit())
         .output()
@@ -116,7 +147,11 @@ fn gen_root_ca() -> CertAndPrivateKey {
     }
 }
 
-fn gen_cert_for_domain(domain: &str, ca: &CertAndPrivateKey) -> CertAndPrivateKey {
+fn gen_cert_for_domain(
+    domain: &str,
+    extended_key_usage: &str,
+    ca: &CertAndPrivateKey,
+) -> CertAndPrivateKey {
     assert!(!domain.is_empty());
 
     let temp_dir = TempBuilder::new().prefix("pem-to-der").tempdir().unwrap();
@@ -151,12 +186,12 @@ fn gen_cert_for_domain(domain: &str, ca: &CertAndPrivateKey) -> CertAndPrivateKe
             [v3_req]\n\
             basicConstraints = CA:FALSE\n\
             keyUsage = digitalSignature, keyEncipherment\n\
-            extendedKeyUsage = serverAuth\n\
+            extendedKeyUsage = {}\n\
             subjectAltName = DNS.0:{}\n\
             [req_distinguished_name]\n\
             # empty\n\
         ",
-            domain
+            extended_key_usage, domain
         ),
     )
     .unwrap();
@@ -187,9 +222,9 @@ fn gen_cert_for_domain(domain: &str, ca: &CertAndPrivateKey) -> CertAndPrivateKe
         format!(
             "\
                 subjectAltName = DNS.0:{}\n\
-                extendedKeyUsage = serverAuth\n\
+                extendedKeyUsage = {}\n\
               ",
-            domain
+            domain, extended_key_usage
         ),
     )
     .unwrap();
@@ -229,21 +264,189 @@ fn gen_cert_for_domain(domain: &str, ca: &CertAndPrivateKey) -> CertAndPrivateKe
     }
 }
 
+/// Generate a CRL signed by given CA, revoking given certificates.
+fn gen_crl(ca: &CertAndPrivateKey, revoked: &[&Cert]) -> Crl {
+    let temp_dir = TempBuilder::new().prefix("gen-crl").tempdir().unwrap();
+
+    let ca_pem = temp_dir.path().join("ca.pem");
+    let ca_key_path = temp_dir.path().join("ca-key.pem");
+    let conf_path = temp_dir.path().join("conf");
+    let crl_path = temp_dir.path().join("crl.pem");
+
+    fs::write(&ca_pem, ca.cert.to_pem()).unwrap();
+    fs::write(&ca_key_path, ca.key.to_pem_incorrect()).unwrap();
+    fs::write(temp_dir.path().join("index.txt"), b"").unwrap();
+    // CRL number is required for CRL v2, which is required by webpki
+    fs::write(temp_dir.path().join("crlnumber"), b"01\n").unwrap();
+
+    fs::write(
+        &conf_path,
+        "\
+            [ca]\n\
+            default_ca = ca_default\n\
+            [ca_default]\n\
+            database = index.txt\n\
+            crlnumber = crlnumber\n\
+            default_md = sha256\n\
+            default_crl_days = 1\n\
+        ",
+    )
+    .unwrap();
+
+    let openssl_ca = || {
+        let mut command = Command::new("openssl");
+        command
+            .current_dir(temp_dir.path())
+            .arg("ca")
+            .arg("-config")
+            .arg(&conf_path)
+            .arg("-cert")
+            .arg(&ca_pem)
+            .arg("-keyfile")
+            .arg(&ca_key_path);
+        command
+    };
+
+    for (i, cert) in revoked.iter().enumerate() {
+        let cert_path = temp_dir.path().join(format!("revoked-{}.pem", i));
+        fs::write(&cert_path, cert.to_pem()).unwrap();
+
+        assert!(openssl_ca()
+            .arg("-revoke")
+            .arg(&cert_path)
+            .output()
+            .unwrap()
+            .status
+            .success());
+    }
+
+    assert!(openssl_ca()
+        .arg("-gencrl")
+        .arg("-out")
+        .arg(&crl_path)
+        .output()
+        .unwrap()
+        .status
+        .success());
+
+    Crl::from_pem(fs::read(&crl_path).unwrap())
+}
+
+/// Generate OCSP response signed by given CA with "good" status of the certificate.
+fn gen_ocsp_response(ca: &CertAndPrivateKey, cert: &Cert, days: u32) -> Vec<u8> {
+    let temp_dir = TempBuilder::new().prefix("gen-ocsp").tempdir().unwrap();
+
+    let ca_pem = temp_dir.path().join("ca.pem");
+    let ca_key_path = temp_dir.path().join("ca-key.pem");
+    let cert_path = temp_dir.path().join("cert.pem");
+    let conf_path = temp_dir.path().join("conf");
+    let req_path = temp_dir.path().join("req.der");
+    let resp_path = temp_dir.path().join("resp.der");
+
+    fs::write(&ca_pem, ca.cert.to_pem()).unwrap();
+    fs::write(&ca_key_path, ca.key.to_pem_incorrect()).unwrap();
+    fs::write(&cert_path, cert.to_pem()).unwrap();
+    fs::write(temp_dir.path().join("index.txt"), b"").unwrap();
+
+    fs::write(
+        &conf_path,
+        "\
+            [ca]\n\
+            default_ca = ca_default\n\
+            [ca_default]\n\
+            database = index.txt\n\
+            default_md = sha256\n\
+        ",
+    )
+    .unwrap();
+
+    // Register the certificate as valid in the responder database
+    assert!(Command::new("openssl")
+        .current_dir(temp_dir.path())
+        .arg("ca")
+        .arg("-config")
+        .arg(&conf_path)
+        .arg("-cert")
+        .arg(&ca_pem)
+        .arg("-keyfile")
+        .arg(&ca_key_path)
+        .arg("-valid")
+        .arg(&cert_path)
+        .output()
+        .unwrap()
+        .status
+        .success());
+
+    assert!(Command::new("openssl")
+        .arg("ocsp")
+        .arg("-issuer")
+        .arg(&ca_pem)
+        .arg("-cert")
+        .arg(&cert_path)
+        .arg("-no_nonce")
+        .arg("-reqout")
+        .arg(&req_path)
+        .output()
+        .unwrap()
+        .status
+        .success());
+
+    assert!(Command::new("openssl")
+        .current_dir(temp_dir.path())
+        .arg("ocsp")
+        .args(["-index", "index.txt"])
+        .arg("-CA")
+        .arg(&ca_pem)
+        .arg("-rsigner")
+        .arg(&ca_pem)
+        .arg("-rkey")
+        .arg(&ca_key_path)
+        .arg("-reqin")
+        .arg(&req_path)
+        .arg("-respout")
+        .arg(&resp_path)
+        .args(["-ndays", &days.to_string()])
+        .output()
+        .unwrap()
+        .status
+        .success());
+
+    fs::read(&resp_path).unwrap()
+}
+
 pub fn gen_keys() -> Keys {
-    let root_ca_pem = gen_root_ca();
+    let root_ca_pem = gen_root_ca("my.ca");
 
-    let server_cert_pem = gen_cert_for_domain("localhost", &root_ca_pem);
+    let server_cert_pem = gen_cert_for_domain("localhost", "serverAuth", &root_ca_pem);
 
     let server_cert_pkcs12 = pem_to_pkcs12_some_password(&server_cert_pem);
 
+    let client_cert_pem = gen_cert_for_domain("client.localhost", "clientAuth", &root_ca_pem);
+
+    let ocsp_response = gen_ocsp_response(&root_ca_pem, &server_cert_pem.cert, 1);
+    let ocsp_response_next = gen_ocsp_response(&root_ca_pem, &server_cert_pem.cert, 2);
+
+    let crls = Crls {
+        empty: gen_crl(&root_ca_pem, &[]),
+        revoked: gen_crl(
+            &root_ca_pem,
+            &[&server_cert_pem.cert, &client_cert_pem.cert],
+        ),
+        foreign: gen_crl(&gen_root_ca("other.ca"), &[]),
+    };
+
     Keys {
         client: ClientKeys {
             ca: root_ca_pem.cert,
+            cert_and_key: client_cert_pem,
         },
         server: ServerKeys {
             cert_and_key: server_cert_pem,
             cert_and_key_pkcs12: server_cert_pkcs12,
+            ocsp_response,
+            ocsp_response_next,
         },
+        crls,
     }
 }
 
@@ -365,6 +568,72 @@ mod test {
         assert!(status.success())
     }
 
+    #[test]
+    fn verify_crl() {
+        let temp_dir = TempBuilder::new().prefix("t").tempdir().unwrap();
+
+        let keys = gen_keys();
+
+        let ca_pem = temp_dir.path().join("ca.pem");
+        let server_pem = temp_dir.path().join("server.pem");
+        let empty_crl_pem = temp_dir.path().join("empty.crl");
+        let revoked_crl_pem = temp_dir.path().join("revoked.crl");
+
+        fs::write(&ca_pem, keys.client.ca.to_pem()).unwrap();
+        fs::write(&server_pem, keys.server.cert_and_key.cert.to_pem()).unwrap();
+        fs::write(&empty_crl_pem, keys.crls.empty.to_pem()).unwrap();
+        fs::write(&revoked_crl_pem, keys.crls.revoked.to_pem()).unwrap();
+
+        let verify = |crl: &std::path::Path| {
+            Command::new("openssl")
+                .arg("verify")
+                .arg("-crl_check")
+                .arg("-CAfile")
+                .arg(&ca_pem)
+                .arg("-CRLfile")
+                .arg(crl)
+                .arg(&server_pem)
+                .output()
+                .unwrap()
+                .status
+                .success()
+        };
+
+        assert!(verify(&empty_crl_pem));
+        assert!(!verify(&revoked_crl_pem));
+    }
+
+    #[test]
+    fn verify_ocsp_response() {
+        let temp_dir = TempBuilder::new().prefix("t").tempdir().unwrap();
+
+        let keys = gen_keys();
+
+        let ca_pem = temp_dir.path().join("ca.pem");
+        let server_pem = temp_dir.path().join("server.pem");
+        let resp_der = temp_dir.path().join("resp.der");
+
+        fs::write(&ca_pem, keys.client.ca.to_pem()).unwrap();
+        fs::write(&server_pem, keys.server.cert_and_key.cert.to_pem()).unwrap();
+        fs::write(&resp_der, &keys.server.ocsp_response).unwrap();
+
+        let output = Command::new("openssl")
+            .arg("ocsp")
+            .arg("-respin")
+            .arg(&resp_der)
+            .arg("-CAfile")
+            .arg(&ca_pem)
+            .arg("-issuer")
+            .arg(&ca_pem)
+            .arg("-cert")
+            .arg(&server_pem)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains(": good"), "{}", stdout);
+    }
+
     #[test]
     #[ignore] // TODO: hangs on CI
     fn client_server() {